@@ -16,6 +16,8 @@ extern crate core as std;
 #[cfg(feature = "serde")]
 mod serde_imp;
 
+#[cfg(feature = "std")]
+use std::env::{self, VarError};
 use std::fmt::{self, Debug, Formatter};
 
 #[cfg(all(not(feature = "std"), feature = "alloc"))]
@@ -81,6 +83,25 @@ pub struct Credentials<T> {
     pub secret: T,
 }
 
+/// An error returned by [`Credentials::validate`](struct.Credentials.html#method.validate).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CredentialError {
+    /// The identifier is empty.
+    EmptyIdentifier,
+    /// The identifier contains a control character.
+    ControlCharacterInIdentifier,
+    /// The secret contains a control character.
+    ControlCharacterInSecret,
+}
+
+/// An error returned by [`Credentials::from_env`](struct.Credentials.html#method.from_env).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg(feature = "std")]
+pub struct EnvVarError {
+    var: String,
+    error: VarError,
+}
+
 /// A set of OAuth client credentials and token/temporary credentials used for authorizing requests
 /// on behalf of a resource owner.
 #[derive(Clone, Copy, Debug)]
@@ -112,6 +133,27 @@ impl<T: AsRef<str>> Credentials<T> {
         }
     }
 
+    /// Creates a new `Credentials` with the given identifier and an empty secret.
+    ///
+    /// This is useful when only the identifier is transmitted and the secret is not known to
+    /// the client (e.g. with the `PLAINTEXT` signature method and a secret exchanged out of band).
+    /// The empty secret still takes part in the signature as an empty string.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use oauth_credentials::Credentials;
+    /// let client = Credentials::from_identifier_only("consumer_key");
+    /// assert_eq!(client.identifier(), "consumer_key");
+    /// assert_eq!(client.secret(), "");
+    /// ```
+    pub fn from_identifier_only(identifier: T) -> Self
+    where
+        T: Default,
+    {
+        Credentials::new(identifier, T::default())
+    }
+
     /// Returns the unique identifier part of the credentials pair.
     pub fn identifier(&self) -> &str {
         self.identifier.as_ref()
@@ -126,6 +168,42 @@ impl<T: AsRef<str>> Credentials<T> {
     pub fn as_ref(&self) -> Credentials<&str> {
         Credentials::new(self.identifier(), self.secret())
     }
+
+    /// Checks that the credentials pair is well-formed.
+    ///
+    /// This returns an error if the identifier is empty or if the identifier or the secret
+    /// contains a control character (like a trailing newline of a configuration file), which is
+    /// most likely a misconfiguration that would otherwise surface as a confusing signature
+    /// mismatch. An empty secret is allowed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use oauth_credentials::{CredentialError, Credentials};
+    /// assert_eq!(Credentials::new("key", "secret").validate(), Ok(()));
+    /// assert_eq!(Credentials::new("key", "").validate(), Ok(()));
+    /// assert_eq!(
+    ///     Credentials::new("", "secret").validate(),
+    ///     Err(CredentialError::EmptyIdentifier),
+    /// );
+    /// assert_eq!(
+    ///     Credentials::new("key", "secret\n").validate(),
+    ///     Err(CredentialError::ControlCharacterInSecret),
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), CredentialError> {
+        let identifier = self.identifier();
+        if identifier.is_empty() {
+            return Err(CredentialError::EmptyIdentifier);
+        }
+        if identifier.chars().any(|c| c.is_control()) {
+            return Err(CredentialError::ControlCharacterInIdentifier);
+        }
+        if self.secret().chars().any(|c| c.is_control()) {
+            return Err(CredentialError::ControlCharacterInSecret);
+        }
+        Ok(())
+    }
 }
 
 impl<T> Credentials<T> {
@@ -154,6 +232,62 @@ impl<T> Credentials<T> {
     }
 }
 
+#[cfg(feature = "std")]
+impl Credentials<String> {
+    /// Creates a new `Credentials` from the values of the environment variables named
+    /// `identifier_var` and `secret_var`.
+    ///
+    /// The error names the variable that is not present or not valid unicode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use oauth_credentials::Credentials;
+    /// std::env::set_var("CONSUMER_KEY", "consumer_key");
+    /// std::env::set_var("CONSUMER_SECRET", "consumer_secret");
+    ///
+    /// let client = Credentials::from_env("CONSUMER_KEY", "CONSUMER_SECRET").unwrap();
+    /// assert_eq!(client.identifier(), "consumer_key");
+    /// assert_eq!(client.secret(), "consumer_secret");
+    ///
+    /// let err = Credentials::from_env("CONSUMER_KEY", "NO_SUCH_VARIABLE").unwrap_err();
+    /// assert_eq!(err.var(), "NO_SUCH_VARIABLE");
+    /// assert_eq!(*err.error(), std::env::VarError::NotPresent);
+    /// ```
+    pub fn from_env(identifier_var: &str, secret_var: &str) -> Result<Self, EnvVarError> {
+        fn var(name: &str) -> Result<String, EnvVarError> {
+            env::var(name).map_err(|error| EnvVarError {
+                var: name.to_owned(),
+                error: error,
+            })
+        }
+
+        Ok(Credentials::new(
+            try!(var(identifier_var)),
+            try!(var(secret_var)),
+        ))
+    }
+}
+
+impl<'a> Credentials<&'a str> {
+    /// Creates a new `Credentials<&str>`.
+    ///
+    /// This is the same as `Credentials::new` but spells out the type of the credentials, which
+    /// would otherwise default to `Credentials<String>` in some contexts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use oauth_credentials::Credentials;
+    /// let client = Credentials::borrowed("consumer_key", "consumer_secret");
+    /// assert_eq!(client.identifier(), "consumer_key");
+    /// assert_eq!(client.secret(), "consumer_secret");
+    /// ```
+    pub fn borrowed(identifier: &'a str, secret: &'a str) -> Self {
+        Credentials::new(identifier, secret)
+    }
+}
+
 impl<'a, T: AsRef<str>> From<&'a Credentials<T>> for Credentials<&'a str> {
     fn from(credentials: &'a Credentials<T>) -> Self {
         credentials.as_ref()
@@ -185,6 +319,63 @@ impl<T: Debug> Debug for Credentials<T> {
     }
 }
 
+impl CredentialError {
+    fn description_str(&self) -> &'static str {
+        match *self {
+            CredentialError::EmptyIdentifier => "empty credentials identifier",
+            CredentialError::ControlCharacterInIdentifier => {
+                "control character in credentials identifier"
+            }
+            CredentialError::ControlCharacterInSecret => "control character in credentials secret",
+        }
+    }
+}
+
+impl fmt::Display for CredentialError {
+    fn fmt<'a>(&self, f: &mut Formatter<'a>) -> fmt::Result {
+        f.write_str(self.description_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CredentialError {
+    fn description(&self) -> &str {
+        self.description_str()
+    }
+}
+
+#[cfg(feature = "std")]
+impl EnvVarError {
+    /// Returns the name of the environment variable that could not be read.
+    pub fn var(&self) -> &str {
+        &self.var
+    }
+
+    /// Returns the underlying error.
+    pub fn error(&self) -> &VarError {
+        &self.error
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for EnvVarError {
+    fn fmt<'a>(&self, f: &mut Formatter<'a>) -> fmt::Result {
+        write!(f, "failed to read `{}`: {}", self.var, self.error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EnvVarError {
+    fn description(&self) -> &str {
+        "failed to read an environment variable"
+    }
+
+    #[allow(bare_trait_objects)]
+    fn cause(&self) -> Option<&std::error::Error> {
+        Some(&self.error)
+    }
+}
+
 impl<C: AsRef<str>, T: AsRef<str>> Token<C, T> {
     /// Creates a new `Token`.
     pub fn new(client: Credentials<C>, token: Credentials<T>) -> Self {
@@ -1,9 +1,12 @@
 use proc_macro2::{Literal, Span, TokenStream};
+use proc_macro_error::emit_error;
 use quote::{ToTokens, TokenStreamExt};
 use syn::ext::IdentExt;
+use syn::spanned::Spanned;
 use syn::{ExprPath, Ident, LitBool, LitStr, Type};
 
 use crate::meta::UriSafe;
+use crate::method_body::option_inner_type;
 
 pub struct Field {
     pub ident: Ident,
@@ -17,8 +20,10 @@ def_meta! {
         pub fmt: Option<ExprPath>,
         pub option: Option<LitBool>,
         pub rename: Option<UriSafe>,
+        pub serialize_with_serde: bool,
         pub skip: bool,
         pub skip_if: Option<ExprPath>,
+        pub with: Option<ExprPath>,
     }
 }
 
@@ -33,10 +38,57 @@ impl Field {
             attrs, ident, ty, ..
         } = field;
         let meta = FieldMeta::new(attrs);
+        if let (Some(_), Some(with)) = (&meta.fmt, &meta.with) {
+            emit_error!(
+                with.span(),
+                "`fmt` and `with` attributes are mutually exclusive"
+            );
+        }
+        if meta.serialize_with_serde {
+            if let Some(fmt) = &meta.fmt {
+                emit_error!(
+                    fmt.span(),
+                    "`fmt` and `serialize_with_serde` attributes are mutually exclusive"
+                );
+            }
+            if let Some(with) = &meta.with {
+                emit_error!(
+                    with.span(),
+                    "`with` and `serialize_with_serde` attributes are mutually exclusive"
+                );
+            }
+        }
         let ident = ident.unwrap().unraw();
         Self { ident, ty, meta }
     }
 
+    /// Returns `true` if the field's value is an `Option` to be unwrapped.
+    pub fn is_option(&self) -> bool {
+        self.meta
+            .option
+            .as_ref()
+            .map(|v| v.value)
+            .unwrap_or_else(|| option_inner_type(&self.ty).is_some())
+    }
+
+    /// Returns the type that needs to implement `Display` for the field to be serialized, if any.
+    ///
+    /// This is `None` if the field is skipped or is formatted with a `fmt` or `with` function or
+    /// through serde, or the inner type of an `Option` field cannot be determined syntactically.
+    pub fn display_type(&self) -> Option<&Type> {
+        if self.meta.skip
+            || self.meta.fmt.is_some()
+            || self.meta.with.is_some()
+            || self.meta.serialize_with_serde
+        {
+            None
+        } else if self.is_option() {
+            option_inner_type(&self.ty)
+        } else {
+            Some(&self.ty)
+        }
+    }
+
     /// Returns the (`rename`-ed) field name.
     pub fn name(&self) -> Name<'_> {
         if let Some(ref name) = self.meta.rename {
@@ -24,10 +24,10 @@ mod field;
 mod method_body;
 mod util;
 
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Span, TokenStream, TokenTree};
 use proc_macro_crate::FoundCrate;
 use proc_macro_error::{abort, abort_if_dirty, emit_error, proc_macro_error};
-use quote::{quote, quote_spanned};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
 use syn::{
     parse_macro_input, parse_quote, Data, DataStruct, DeriveInput, Fields, GenericParam, Generics,
@@ -125,6 +125,9 @@ fn expand_derive_oauth1_authorize(mut input: DeriveInput) -> TokenStream {
 
     abort_if_dirty();
 
+    add_field_bounds(&mut input.generics, &fields);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
     let body = MethodBody::new(&fields);
 
     quote_spanned! {Span::mixed_site()=>
@@ -148,6 +151,35 @@ fn expand_derive_oauth1_authorize(mut input: DeriveInput) -> TokenStream {
     }
 }
 
+/// Adds a `Display` bound for the type of each serialized field that mentions a type parameter,
+/// so that an unsatisfied bound is reported at the field's declaration rather than in
+/// the generated method body.
+///
+/// The bounds on non-generic field types are omitted because they would be trivial bounds,
+/// which are checked at the `impl` as usual.
+fn add_field_bounds(generics: &mut Generics, fields: &[Field]) {
+    let params: Vec<Ident> = generics.type_params().map(|p| p.ident.clone()).collect();
+    if params.is_empty() {
+        return;
+    }
+
+    let predicates = &mut generics.make_where_clause().predicates;
+    for ty in fields.iter().filter_map(Field::display_type) {
+        if mentions_any(ty.to_token_stream(), &params) {
+            let bound = quote_spanned! {ty.span()=> #ty: ::core::fmt::Display };
+            predicates.push(parse_quote!(#bound));
+        }
+    }
+}
+
+fn mentions_any(tokens: TokenStream, idents: &[Ident]) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        TokenTree::Ident(ref ident) => idents.contains(ident),
+        TokenTree::Group(ref group) => mentions_any(group.stream(), idents),
+        TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+    })
+}
+
 fn add_trait_bounds(generics: &mut Generics) {
     for param in &mut generics.params {
         if let GenericParam::Type(ref mut type_param) = *param {
@@ -218,10 +218,13 @@ impl Parse for Meta {
 
 impl FromExprExt for ExprPath {
     fn from_expr(expr: Expr) -> syn::Result<Self> {
-        if let Expr::Path(path) = expr {
-            Ok(path)
-        } else {
-            Err(syn::Error::new(expr.span(), "expected path"))
+        match expr {
+            Expr::Path(path) => Ok(path),
+            // Also accept a path in a string literal, like `#[serde(skip_serializing_if = "...")]`.
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }) => lit.parse(),
+            _ => Err(syn::Error::new(expr.span(), "expected path")),
         }
     }
 }
@@ -3,7 +3,7 @@ mod helper;
 use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::{quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
-use syn::{Ident, PathArguments, Type};
+use syn::{GenericArgument, Ident, PathArguments, Type};
 
 use crate::field::Field;
 use crate::util::OAuthParameter;
@@ -70,12 +70,7 @@ impl<'a> ToTokens for MethodBody<'a> {
                 next_param = next_param.next();
             }
 
-            let ty_is_option = f
-                .meta
-                .option
-                .as_ref()
-                .map(|v| v.value)
-                .unwrap_or_else(|| is_option(&f.ty));
+            let ty_is_option = f.is_option();
 
             let unwrapped = if ty_is_option {
                 TokenStream::from(TokenTree::Ident(bind.clone()))
@@ -109,6 +104,14 @@ impl<'a> ToTokens for MethodBody<'a> {
                 quote_spanned! {Span::mixed_site()=>
                     #helper.fmt(fmt, #tmp)
                 }
+            } else if let Some(ref with) = f.meta.with {
+                quote_spanned! {with.span()=>
+                    #with::display(#tmp)
+                }
+            } else if f.meta.serialize_with_serde {
+                quote_spanned! {f.ty.span()=>
+                    _oauth1_request::request::serde_scalar::display(#tmp)
+                }
             } else {
                 TokenStream::from(TokenTree::Ident(tmp.clone()))
             };
@@ -180,7 +183,8 @@ impl<'a> ToTokens for MethodBody<'a> {
     }
 }
 
-fn is_option(mut ty: &Type) -> bool {
+/// Returns the type argument of `ty` if `ty` is of the form `Option<T>`.
+pub fn option_inner_type(mut ty: &Type) -> Option<&Type> {
     // Types that are interpolated through `macro_rules!` may be enclosed in a `Group`.
     // <https://github.com/rust-lang/rust/pull/72388>
     while let Type::Group(ref g) = *ty {
@@ -189,14 +193,20 @@ fn is_option(mut ty: &Type) -> bool {
 
     if let Type::Path(ref ty_path) = *ty {
         let path = &ty_path.path;
-        path.leading_colon.is_none()
-            && path.segments.len() == 1
-            && path.segments[0].ident == "Option"
-            && match path.segments[0].arguments {
-                PathArguments::AngleBracketed(ref args) => args.args.len() == 1,
-                PathArguments::None | PathArguments::Parenthesized(_) => false,
-            }
+        if path.leading_colon.is_some()
+            || path.segments.len() != 1
+            || path.segments[0].ident != "Option"
+        {
+            return None;
+        }
+        match path.segments[0].arguments {
+            PathArguments::AngleBracketed(ref args) if args.args.len() == 1 => match args.args[0] {
+                GenericArgument::Type(ref ty) => Some(ty),
+                _ => None,
+            },
+            _ => None,
+        }
     } else {
-        false
+        None
     }
 }
@@ -84,3 +84,25 @@ pub fn fmt_ignore<T>(_: &T, _: &mut Formatter<'_>) -> fmt::Result {
 pub fn fmt_str(s: &str, f: &mut Formatter<'_>) -> fmt::Result {
     Display::fmt(s, f)
 }
+
+pub mod comma_separated {
+    use core::fmt::{self, Display, Formatter};
+
+    pub struct CommaSeparated<'a>(&'a [&'a str]);
+
+    impl<'a> Display for CommaSeparated<'a> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            for (i, s) in self.0.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                f.write_str(s)?;
+            }
+            Ok(())
+        }
+    }
+
+    pub fn display<'a>(list: &'a [&'a str]) -> CommaSeparated<'a> {
+        CommaSeparated(list)
+    }
+}
@@ -176,6 +176,51 @@ assert_expand! {
     }
 }
 
+assert_expand! {
+    #[derive(oauth::Request)]
+    struct Rename[][] {
+        #[oauth1(rename = "screen_name")]
+        a_screen_name: &'static str = "foo",
+        count: u64,
+        #[oauth1(rename = "cursor")]
+        z_cursor: u64,
+        #[oauth1(rename = "oauth_callback_confirmed")]
+        confirmed: bool,
+    }
+    |this, mut ser| {
+        ser.serialize_parameter("count", this.count);
+        ser.serialize_parameter("cursor", this.z_cursor);
+        ser.serialize_oauth_callback();
+        ser.serialize_parameter("oauth_callback_confirmed", this.confirmed);
+        ser.serialize_oauth_consumer_key();
+        ser.serialize_oauth_nonce();
+        ser.serialize_oauth_signature_method();
+        ser.serialize_oauth_timestamp();
+        ser.serialize_oauth_token();
+        ser.serialize_oauth_verifier();
+        ser.serialize_oauth_version();
+        ser.serialize_parameter("screen_name", &this.a_screen_name);
+        ser.end()
+    }
+}
+
+assert_expand! {
+    #[derive(oauth::Request)]
+    struct StrPath[][] {
+        #[oauth1(skip_if = "str::is_empty")]
+        empty: &'static str = "",
+        #[oauth1(skip_if = "std::option::Option::is_none", fmt = "super::fmt_option_str")]
+        none: std::option::Option<&'static str> = None,
+        #[oauth1(skip_if = "std::option::Option::is_none", fmt = "super::fmt_option_str")]
+        some: std::option::Option<&'static str> = Some("some"),
+    }
+    |_this, mut ser| {
+        ser.serialize_oauth_parameters();
+        ser.serialize_parameter("some", "some");
+        ser.end()
+    }
+}
+
 // Just checking that this compiles.
 #[derive(oauth::Request)]
 #[oauth1(crate = oauth)]
@@ -237,6 +282,23 @@ assert_expand! {
     }
 }
 
+assert_expand! {
+    #[derive(oauth::Request)]
+    struct With['a][] {
+        #[oauth1(with = crate::common::comma_separated)]
+        list: &'a [&'a str] = &["foo", "bar"],
+
+        #[oauth1(option = true, with = crate::common::comma_separated)]
+        some: std::option::Option<&'a [&'a str]> = Some(&["baz"]),
+    }
+    |_this, mut ser| {
+        ser.serialize_parameter("list", "foo,bar");
+        ser.serialize_oauth_parameters();
+        ser.serialize_parameter("some", "baz");
+        ser.end()
+    }
+}
+
 // Just checking that these compile. They are tests for the code generation around the internal
 // `DeriveRequestAssertion` struct which `fmt` and `skip_if` attributes share, checking that
 // the attributes don't interfere with or depend on each other.
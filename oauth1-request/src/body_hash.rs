@@ -0,0 +1,121 @@
+//! The [OAuth Request Body Hash][body-hash] extension.
+//!
+//! [body-hash]: https://datatracker.ietf.org/doc/html/draft-eaton-oauth-bodyhash-00
+
+use alloc::string::String;
+use core::fmt::Display;
+
+use crate::request::Request;
+use crate::serializer::auth;
+use crate::signature_method::SignatureMethod;
+use crate::Builder;
+
+impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T> {
+    doc_auto_cfg! {
+        /// Authorizes a request to `uri` with a JSON (or any non-form) request body, returning
+        /// an HTTP `Authorization` header value that includes the `oauth_body_hash` parameter of
+        /// the [OAuth Request Body Hash][body-hash] extension.
+        ///
+        /// The body hash is computed with the hash function of the signature method, i.e. SHA-1
+        /// for `HMAC-SHA1` and `RSA-SHA1`, SHA-256 for `HMAC-SHA256` and SHA-512 for `HMAC-SHA512`.
+        /// The extension is not defined for other signature methods like `PLAINTEXT`, so
+        /// `oauth_body_hash` is omitted for them. The parameters of `query` are included in
+        /// the signature along with the query parameters of `uri`, if any, and you should send
+        /// the `query` parameters in the request URI by yourself.
+        ///
+        /// [body-hash]: https://datatracker.ietf.org/doc/html/draft-eaton-oauth-bodyhash-00
+        #[cfg(feature = "alloc")]
+        pub fn build_json<U, R>(&self, method: &str, uri: U, json: &[u8], query: &R) -> String
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            let hash = body_hash(self.signature_method.name(), json);
+            let mut options: auth::Options<'_> = self.options.clone();
+            options.body_hash(hash.as_deref());
+
+            let signature_method = self.signature_method.clone();
+            self.build_with(signature_method, &options, method, uri, query)
+        }
+    }
+}
+
+/// Computes the `oauth_body_hash` value of `body` with the hash function of the signature method
+/// named `signature_method`, or returns `None` if the method is not based on a hash function
+/// supported by this crate.
+#[cfg(feature = "alloc")]
+fn body_hash(signature_method: &str, body: &[u8]) -> Option<String> {
+    use alloc::string::ToString;
+    use base64::display::Base64Display;
+
+    let encode = |hash: &[u8]| Base64Display::with_config(hash, base64::STANDARD).to_string();
+    let _ = (&encode, body);
+    match signature_method {
+        #[cfg(any(feature = "hmac-sha1", feature = "rsa-sha1-06"))]
+        "HMAC-SHA1" | "RSA-SHA1" => {
+            use sha1::{Digest, Sha1};
+            Some(encode(&Sha1::digest(body)))
+        }
+        #[cfg(feature = "hmac-sha256")]
+        "HMAC-SHA256" => Some(encode(&hmac_sha256_1::Hash::hash(body))),
+        #[cfg(feature = "hmac-sha512")]
+        "HMAC-SHA512" => Some(encode(&hmac_sha512_1::Hash::hash(body))),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::NonZeroU64;
+
+    use oauth_credentials::Credentials;
+
+    use crate::signature_method::PLAINTEXT;
+
+    use super::*;
+
+    fn build_json<SM: SignatureMethod + Clone>(signature_method: SM) -> String {
+        let client = Credentials::new("ck", "cs");
+        let mut builder = Builder::<_, &str>::new(client, signature_method);
+        builder.nonce("nonce").timestamp(NonZeroU64::new(1));
+        let uri = "https://example.com/api/items/1";
+        builder.build_json("PATCH", uri, br#"{"name":"foo"}"#, &())
+    }
+
+    #[cfg(feature = "hmac-sha256")]
+    #[test]
+    fn build_json_sha256() {
+        use crate::signature_method::hmac_sha256::HmacSha256;
+
+        assert!(build_json(HmacSha256::new()).starts_with(
+            "OAuth oauth_body_hash=\"XcqF52mJ5V673snlMEgywGqerXE4sENyxlVTADz9KEk%3D\",",
+        ));
+    }
+
+    #[cfg(feature = "hmac-sha512")]
+    #[test]
+    fn build_json_sha512() {
+        use crate::signature_method::hmac_sha512::HmacSha512;
+
+        assert!(build_json(HmacSha512::new()).starts_with(
+            "OAuth oauth_body_hash=\"AnCriNgwS5jiYWJYieQuzIdy3QVYVUuAY4skjF1lZJA4CdN1AgiFfQ6W\
+             jXM6b4vfcFrpO4rPkNaocOgT%2B6iB0w%3D%3D\",",
+        ));
+    }
+
+    #[cfg(feature = "hmac-sha1")]
+    #[test]
+    fn build_json_sha1() {
+        use crate::signature_method::HMAC_SHA1;
+
+        assert!(build_json(HMAC_SHA1).starts_with(
+            "OAuth oauth_body_hash=\"HMzhEUx3526K9M%2FDwl6a1%2FAt6Ns%3D\",oauth_consumer_key=",
+        ));
+    }
+
+    #[test]
+    fn build_json_plaintext() {
+        assert!(!build_json(PLAINTEXT).contains("oauth_body_hash"));
+    }
+}
@@ -0,0 +1,326 @@
+//! The structured form of the signature base string.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use percent_encoding::percent_decode_str;
+
+use crate::request::Request;
+use crate::signature_method::{Sign, SignatureMethod};
+use crate::util::PercentEncode;
+use crate::Builder;
+
+/// The normalized components of a request that are signed, i.e. the structured form of
+/// the signature base string ([RFC 5849 section 3.4.1.][rfc]).
+///
+/// The `Display` implementation produces the signature base string.
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CanonicalRequest {
+    /// The HTTP request method.
+    pub method: String,
+    /// The base string URI, without the query part.
+    pub base_uri: String,
+    /// The sorted request parameters, including the OAuth protocol parameters except for
+    /// `oauth_signature`, with the names and values percent-encoded once.
+    pub params: Vec<(String, String)>,
+}
+
+impl CanonicalRequest {
+    /// Parses a signature base string into a `CanonicalRequest`.
+    ///
+    /// Returns `None` if `base_string` is not a well-formed signature base string.
+    pub fn from_base_string(base_string: &str) -> Option<Self> {
+        let mut parts = base_string.split('&');
+        let method = parts.next()?;
+        let base_uri = parts.next()?;
+        let params = parts.next()?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        let params = percent_decode_str(params).decode_utf8().ok()?;
+        let params = if params.is_empty() {
+            Vec::new()
+        } else {
+            params
+                .split('&')
+                .map(|pair| {
+                    let i = pair.find('=')?;
+                    Some((pair[..i].to_string(), pair[i + 1..].to_string()))
+                })
+                .collect::<Option<_>>()?
+        };
+
+        Some(CanonicalRequest {
+            method: method.to_string(),
+            base_uri: percent_decode_str(base_uri)
+                .decode_utf8()
+                .ok()?
+                .into_owned(),
+            params,
+        })
+    }
+}
+
+impl Display for CanonicalRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}&{}&", self.method, PercentEncode(&self.base_uri))?;
+        for (i, (k, v)) in self.params.iter().enumerate() {
+            if i > 0 {
+                f.write_str("%26")?;
+            }
+            write!(f, "{}%3D{}", PercentEncode(k), PercentEncode(v))?;
+        }
+        Ok(())
+    }
+}
+
+/// A pseudo signature method that collects the components of the signature base string into
+/// a `CanonicalRequest`, mimicking another signature method.
+pub(crate) struct Canonicalizer<'a> {
+    canonical: &'a mut CanonicalRequest,
+    signature_method_name: &'static str,
+    use_nonce: bool,
+    use_timestamp: bool,
+}
+
+impl<'a> Canonicalizer<'a> {
+    /// Creates a `Canonicalizer` that mimics the signature method of `sign`, including whether it
+    /// uses the `oauth_nonce` and `oauth_timestamp` parameters.
+    pub fn like<S: Sign>(canonical: &'a mut CanonicalRequest, sign: &S) -> Self {
+        Canonicalizer {
+            canonical,
+            signature_method_name: sign.get_signature_method_name(),
+            use_nonce: sign.use_nonce(),
+            use_timestamp: sign.use_timestamp(),
+        }
+    }
+}
+
+impl<'a> SignatureMethod for Canonicalizer<'a> {
+    type Sign = Self;
+
+    fn name(&self) -> &'static str {
+        self.signature_method_name
+    }
+
+    fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Self {
+        self
+    }
+}
+
+impl<'a> Sign for Canonicalizer<'a> {
+    type Signature = &'static str;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.signature_method_name
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.canonical.method = method.to_string();
+    }
+
+    // The URI and the parameters are given percent-encoded for the signature base string, so we
+    // decode them once. The results are ASCII since they are percent-encoded at least once.
+    fn uri<T: Display>(&mut self, uri: T) {
+        let uri = uri.to_string();
+        self.canonical.base_uri = percent_decode_str(&uri).decode_utf8_lossy().into_owned();
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        let value = value.to_string();
+        let decode = |s: &str| percent_decode_str(s).decode_utf8_lossy().into_owned();
+        self.canonical.params.push((decode(key), decode(&value)));
+    }
+
+    fn delimiter(&mut self) {}
+
+    fn use_nonce(&self) -> bool {
+        self.use_nonce
+    }
+
+    fn use_timestamp(&self) -> bool {
+        self.use_timestamp
+    }
+
+    fn end(self) -> &'static str {
+        ""
+    }
+}
+
+impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T> {
+    doc_auto_cfg! {
+        /// Builds an HTTP `Authorization` header value without the `oauth_signature` parameter,
+        /// returning it along with the signature base string of the request.
+        ///
+        /// This is useful for signing requests with an external signer (e.g. an HSM or a KMS):
+        /// sign the base string with the signer and append the `oauth_signature` parameter to
+        /// the header value yourself. The OAuth protocol parameters in the header value are the
+        /// ones the signature method of the `Builder` would use. The client and token secrets are
+        /// not passed to the signature method, so the results do not depend on them.
+        ///
+        /// Set the nonce and the timestamp with [`nonce`](Self::nonce) and
+        /// [`timestamp`](Self::timestamp) if you need to reproduce the same parameters later.
+        #[cfg(feature = "alloc")]
+        pub fn build_unsigned<U, R>(&self, method: &str, uri: U, request: &R) -> (String, String)
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            use crate::signature_method::BaseString;
+
+            // The `Sign` is only consulted for the parameters it uses, so the secrets are left out.
+            let sign = self.signature_method.clone().sign_with("", None);
+            let mut base_string = String::new();
+            let mut header = self.build_with(
+                BaseString::like(&mut base_string, &sign),
+                &self.options,
+                method,
+                uri,
+                request,
+            );
+
+            // `BaseString` produces an empty signature, which always comes last.
+            const SIGNATURE: &str = ",oauth_signature=\"\"";
+            debug_assert!(header.ends_with(SIGNATURE));
+            header.truncate(header.len() - SIGNATURE.len());
+
+            (header, base_string)
+        }
+    }
+
+    doc_auto_cfg! {
+        /// Returns the normalized components of a request that would be signed, i.e. the
+        /// structured form of the signature base string.
+        ///
+        /// The result reflects all the normalization done by the `Builder` and its options,
+        /// including folding the query parameters of `uri` into the parameters and sorting them.
+        #[cfg(feature = "alloc")]
+        pub fn canonicalize<U, R>(&self, method: &str, uri: U, request: &R) -> CanonicalRequest
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            let sign = self.signature_method.clone().sign_with("", None);
+            let mut canonical = CanonicalRequest::default();
+            self.build_with(
+                Canonicalizer::like(&mut canonical, &sign),
+                &self.options,
+                method,
+                uri,
+                request,
+            );
+            canonical
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::NonZeroU64;
+
+    use oauth_credentials::Credentials;
+
+    use crate::request;
+    use crate::signature_method::PLAINTEXT;
+
+    use super::*;
+
+    #[cfg(feature = "hmac-sha1")]
+    #[test]
+    fn build_unsigned_ignores_secrets() {
+        use crate::signature_method::HMAC_SHA1;
+
+        let build_unsigned = |client_secret, token_secret| {
+            let client = Credentials::new("ck", client_secret);
+            let token = Credentials::new("tk", token_secret);
+            let mut builder = Builder::new(client, HMAC_SHA1);
+            builder
+                .token(token)
+                .nonce("nonce")
+                .timestamp(NonZeroU64::new(1));
+            let request = request! { "q" => "rust" };
+            builder.build_unsigned("GET", "https://example.com/api/items", &request)
+        };
+
+        let unsigned = build_unsigned("cs", "ts");
+        assert_eq!(unsigned, build_unsigned("other", ""));
+        assert!(!unsigned.0.contains("oauth_signature="));
+    }
+
+    #[test]
+    fn canonicalize_matches_base_string() {
+        use alloc::string::ToString;
+
+        let client = Credentials::new("ck", "cs");
+        let mut builder = Builder::<_, &str>::new(client, PLAINTEXT);
+        builder.nonce("nonce").timestamp(NonZeroU64::new(1));
+
+        let uri = "HTTPS://Example.com/a%20b?x=%25&y=caf%C3%A9";
+        let request = request! { "q" => "a&b=c" };
+        let canonical = builder.canonicalize("POST", uri, &request);
+        let (_, base_string) = builder.build_unsigned("POST", uri, &request);
+        assert_eq!(canonical.to_string(), base_string);
+        assert_eq!(canonical.base_uri, "https://example.com/a%20b");
+        assert_eq!(
+            canonical.params[0],
+            ("oauth_consumer_key".into(), "ck".into())
+        );
+        assert_eq!(canonical.params[4], ("q".into(), "a%26b%3Dc".into()));
+    }
+
+    #[test]
+    fn canonicalize_keeps_port() {
+        let client = Credentials::new("ck", "cs");
+        let mut builder = Builder::<_, &str>::new(client, PLAINTEXT);
+        builder.nonce("nonce").timestamp(NonZeroU64::new(1));
+
+        let uri = "HTTPS://Example.com:443/api/search?q=caf%C3%A9";
+        let canonical = builder.canonicalize("GET", uri, &request! { "lang" => "fr" });
+        assert_eq!(canonical.method, "GET");
+        assert_eq!(canonical.base_uri, "https://example.com:443/api/search");
+        let keys: Vec<_> = canonical.params.iter().map(|(k, _)| &**k).collect();
+        assert_eq!(
+            keys,
+            [
+                "lang",
+                "oauth_consumer_key",
+                "oauth_nonce",
+                "oauth_signature_method",
+                "oauth_timestamp",
+                "q",
+            ],
+        );
+        assert_eq!(canonical.params[5].1, "caf%C3%A9");
+    }
+
+    #[test]
+    fn build_unsigned() {
+        let client = Credentials::new("ck", "cs");
+        let mut builder = Builder::<_, &str>::new(client, PLAINTEXT);
+        builder.nonce("nonce").timestamp(NonZeroU64::new(1));
+
+        let (header, base_string) = builder.build_unsigned("GET", "https://example.com/api", &());
+        assert_eq!(
+            header,
+            "OAuth \
+             oauth_consumer_key=\"ck\",\
+             oauth_nonce=\"nonce\",\
+             oauth_signature_method=\"PLAINTEXT\",\
+             oauth_timestamp=\"1\"",
+        );
+        assert_eq!(
+            base_string,
+            "GET&https%3A%2F%2Fexample.com%2Fapi&\
+             oauth_consumer_key%3Dck%26\
+             oauth_nonce%3Dnonce%26\
+             oauth_signature_method%3DPLAINTEXT%26\
+             oauth_timestamp%3D1",
+        );
+    }
+}
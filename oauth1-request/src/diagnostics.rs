@@ -0,0 +1,254 @@
+//! Utilities for debugging signature mismatches.
+//!
+//! When a server rejects a signature, the cause is usually a difference in the signature base
+//! string ([RFC 5849 section 3.4.1.][rfc]) the client and the server have constructed. You can
+//! capture the base string of your request with [`BaseString`](crate::signature_method::BaseString)
+//! and compare it with the one the server expects (some providers include it in the error
+//! response) using the functions of this module.
+//!
+//! [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter, Write};
+
+use percent_encoding::percent_decode_str;
+
+use crate::util::{LowercaseSchemeAndHost, WithoutDefaultPort};
+
+/// A likely cause of a mismatch between two signature base strings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MismatchCause {
+    /// Either of the strings is not a well-formed signature base string.
+    Malformed,
+    /// The HTTP request methods differ only in case.
+    MethodCase,
+    /// The HTTP request methods differ.
+    Method,
+    /// The base string URIs differ in the presence of the default port of the scheme
+    /// (`80` for `http` and `443` for `https`).
+    DefaultPort,
+    /// The base string URIs differ only in the case of the scheme or the authority.
+    UriCase,
+    /// The base string URIs differ.
+    Uri,
+    /// The parameters are the same but are in a different order.
+    Ordering,
+    /// The parameters are the same after percent-decoding but are encoded differently.
+    Encoding,
+    /// The parameters differ.
+    Parameters,
+}
+
+/// The components of a signature base string, with the URI and the parameters percent-decoded
+/// once.
+struct Parts<'a> {
+    method: &'a str,
+    uri: Cow<'a, str>,
+    parameters: Cow<'a, str>,
+}
+
+/// Produces a human-readable line-by-line diff of two signature base strings.
+///
+/// The output consists of the HTTP request method, the base string URI and each of the
+/// (still percent-encoded) parameters on their own lines. The lines only in `a` are prefixed with
+/// `- `, the ones only in `b` with `+ ` and the common ones with two spaces.
+///
+/// If either of the strings is not a well-formed signature base string, the whole strings are
+/// compared as single lines.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::diagnostics::diff_base_strings;
+///
+/// let a = "GET&http%3A%2F%2Fexample.com%2F&a%3D1%26b%3D2";
+/// let b = "GET&http%3A%2F%2Fexample.com%3A80%2F&a%3D1%26b%3D3";
+/// assert_eq!(
+///     diff_base_strings(a, b),
+///     "  GET\n\
+///      - http://example.com/\n\
+///      + http://example.com:80/\n  \
+///        a=1\n\
+///      - b=2\n\
+///      + b=3\n",
+/// );
+/// ```
+pub fn diff_base_strings(a: &str, b: &str) -> String {
+    let mut ret = String::new();
+
+    let (a, b) = match (Parts::parse(a), Parts::parse(b)) {
+        (Some(a), Some(b)) => (a, b),
+        _ => {
+            diff_lines(&[a], &[b], &mut ret);
+            return ret;
+        }
+    };
+
+    diff_lines(&[a.method], &[b.method], &mut ret);
+    diff_lines(&[&*a.uri], &[&*b.uri], &mut ret);
+    diff_lines(&a.parameter_list(), &b.parameter_list(), &mut ret);
+
+    ret
+}
+
+/// Checks two signature base strings for common causes of signature mismatches.
+///
+/// Returns an empty `Vec` if the strings are identical.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::diagnostics::{explain_signature_mismatch, MismatchCause};
+///
+/// let a = "GET&https%3A%2F%2Fexample.com%2F&a%3D1%26b%3D%2525";
+/// let b = "GET&https%3A%2F%2Fexample.com%3A443%2F&a%3D1%26b%3D%2525";
+/// assert_eq!(explain_signature_mismatch(a, b), [MismatchCause::DefaultPort]);
+///
+/// let b = "GET&https%3A%2F%2Fexample.com%2F&b%3D%2525%26a%3D1";
+/// assert_eq!(explain_signature_mismatch(a, b), [MismatchCause::Ordering]);
+///
+/// let b = "GET&https%3A%2F%2Fexample.com%2F&a%3D1%26b%3D%25";
+/// assert_eq!(explain_signature_mismatch(a, b), [MismatchCause::Encoding]);
+/// ```
+pub fn explain_signature_mismatch(a: &str, b: &str) -> Vec<MismatchCause> {
+    let mut ret = Vec::new();
+    if a == b {
+        return ret;
+    }
+
+    let (a, b) = match (Parts::parse(a), Parts::parse(b)) {
+        (Some(a), Some(b)) => (a, b),
+        _ => {
+            ret.push(MismatchCause::Malformed);
+            return ret;
+        }
+    };
+
+    if a.method != b.method {
+        ret.push(if a.method.eq_ignore_ascii_case(b.method) {
+            MismatchCause::MethodCase
+        } else {
+            MismatchCause::Method
+        });
+    }
+
+    if a.uri != b.uri {
+        let normalize = |uri| WithoutDefaultPort(LowercaseSchemeAndHost(uri), true).to_string();
+        let lowercase = |uri| LowercaseSchemeAndHost(uri).to_string();
+        ret.push(if normalize(&a.uri) == normalize(&b.uri) {
+            if lowercase(&a.uri) == lowercase(&b.uri) {
+                MismatchCause::UriCase
+            } else {
+                MismatchCause::DefaultPort
+            }
+        } else {
+            MismatchCause::Uri
+        });
+    }
+
+    if a.parameters != b.parameters {
+        let mut a = a.parameter_list();
+        let mut b = b.parameter_list();
+        a.sort_unstable();
+        b.sort_unstable();
+        ret.push(if a == b {
+            MismatchCause::Ordering
+        } else {
+            if decode_sorted(&a) == decode_sorted(&b) {
+                MismatchCause::Encoding
+            } else {
+                MismatchCause::Parameters
+            }
+        });
+    }
+
+    ret
+}
+
+impl<'a> Parts<'a> {
+    fn parse(base_string: &'a str) -> Option<Self> {
+        let mut parts = base_string.split('&');
+        let method = parts.next()?;
+        let uri = parts.next()?;
+        let parameters = parts.next()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Parts {
+            method,
+            uri: percent_decode_str(uri).decode_utf8_lossy(),
+            parameters: percent_decode_str(parameters).decode_utf8_lossy(),
+        })
+    }
+
+    fn parameter_list(&self) -> Vec<&str> {
+        if self.parameters.is_empty() {
+            Vec::new()
+        } else {
+            self.parameters.split('&').collect()
+        }
+    }
+}
+
+/// Percent-decodes the parameters and sorts them.
+fn decode_sorted<'a>(list: &[&'a str]) -> Vec<Cow<'a, str>> {
+    let mut list: Vec<_> = list
+        .iter()
+        .map(|p| percent_decode_str(p).decode_utf8_lossy())
+        .collect();
+    list.sort_unstable();
+    list
+}
+
+/// Writes the diff of two sequences of lines based on their longest common subsequence.
+fn diff_lines(a: &[&str], b: &[&str], out: &mut String) {
+    // `lcs[i][j]` is the length of the longest common subsequence of `a[i..]` and `b[j..]`.
+    let mut lcs = alloc::vec![alloc::vec![0_usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            writeln!(out, "  {}", a[i]).unwrap();
+            i += 1;
+            j += 1;
+        } else if j == b.len() || (i < a.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            writeln!(out, "- {}", a[i]).unwrap();
+            i += 1;
+        } else {
+            writeln!(out, "+ {}", b[j]).unwrap();
+            j += 1;
+        }
+    }
+}
+
+impl Display for MismatchCause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match *self {
+            MismatchCause::Malformed => "either of the strings is not a signature base string",
+            MismatchCause::MethodCase => "the HTTP request methods differ in case",
+            MismatchCause::Method => "the HTTP request methods differ",
+            MismatchCause::DefaultPort => "the URIs differ in the presence of the default port",
+            MismatchCause::UriCase => "the URIs differ in the case of the scheme or authority",
+            MismatchCause::Uri => "the URIs differ",
+            MismatchCause::Ordering => "the parameters are in a different order",
+            MismatchCause::Encoding => "the parameters are percent-encoded differently",
+            MismatchCause::Parameters => "the parameters differ",
+        })
+    }
+}
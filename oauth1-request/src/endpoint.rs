@@ -0,0 +1,95 @@
+//! A `Builder` bound to an HTTP request method and a URI.
+
+use alloc::string::String;
+use core::fmt::Display;
+
+use crate::request::Request;
+use crate::signature_method::SignatureMethod;
+use crate::Builder;
+
+impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T> {
+    doc_auto_cfg! {
+        /// Returns an [`Endpoint`] that authorizes requests with the given HTTP request method and
+        /// `uri`, so that only the request parameters need to be given for each request.
+        #[cfg(feature = "alloc")]
+        pub fn endpoint<'b, U: Display>(
+            &'b self,
+            method: &'b str,
+            uri: U,
+        ) -> Endpoint<'b, 'a, SM, C, T, U> {
+            Endpoint {
+                builder: self,
+                method,
+                uri,
+            }
+        }
+    }
+}
+
+doc_auto_cfg! {
+    /// A [`Builder`] bound to an HTTP request method and a URI, created by [`Builder::endpoint`].
+    ///
+    /// This is useful for a client of a single endpoint, where only the request parameters vary
+    /// between requests.
+    #[cfg(feature = "alloc")]
+    pub struct Endpoint<'b, 'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>, U> {
+        builder: &'b Builder<'a, SM, C, T>,
+        method: &'b str,
+        uri: U,
+    }
+
+    #[cfg(feature = "alloc")]
+    impl<'b, 'a, SM, C, T, U> Endpoint<'b, 'a, SM, C, T, U>
+    where
+        SM: SignatureMethod + Clone,
+        C: AsRef<str>,
+        T: AsRef<str>,
+        U: Display,
+    {
+        /// Authorizes `request` with the method and URI of the endpoint, returning an HTTP
+        /// `Authorization` header value.
+        ///
+        /// The query parameters of the URI, if any, are included in the signature.
+        pub fn sign<R: Request + ?Sized>(&self, request: &R) -> String {
+            self.builder.authorize(self.method, &self.uri, request)
+        }
+
+        /// Returns the HTTP request method of the endpoint.
+        pub fn method(&self) -> &'b str {
+            self.method
+        }
+
+        /// Returns the URI of the endpoint.
+        pub fn uri(&self) -> &U {
+            &self.uri
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::NonZeroU64;
+
+    use oauth_credentials::Credentials;
+
+    use crate::request;
+    use crate::signature_method::PLAINTEXT;
+
+    use super::*;
+
+    #[test]
+    fn endpoint() {
+        let client = Credentials::new("ck", "cs");
+        let mut builder = Builder::<_, &str>::new(client, PLAINTEXT);
+        builder.nonce("nonce").timestamp(NonZeroU64::new(1));
+
+        let uri = "https://example.com/api/search";
+        let search = builder.endpoint("GET", uri);
+        assert_eq!(search.method(), "GET");
+        assert_eq!(*search.uri(), uri);
+        for &q in &["foo", "bar"] {
+            let request = request! { "q" => q };
+            assert_eq!(search.sign(&request), builder.get(uri, &request));
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Errors returned by `Builder`.
+
+use core::fmt::{self, Display};
+
+doc_auto_cfg! {
+    /// The error returned by [`Builder::try_authorize`](crate::Builder::try_authorize) when a request has more parameters than
+    /// the limit set with [`Builder::max_parameters`](crate::Builder::max_parameters).
+    #[cfg(feature = "alloc")]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct TooManyParameters {
+        pub(crate) count: usize,
+        pub(crate) max: usize,
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TooManyParameters {
+    /// Returns the number of the parameters of the request.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the maximum number of parameters that was set with `Builder::max_parameters`.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Display for TooManyParameters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "request has {} parameters, exceeding the maximum of {}",
+            self.count, self.max,
+        )
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "std"))]
+impl std::error::Error for TooManyParameters {}
+
+doc_auto_cfg! {
+    /// The error returned by [`Builder::try_build`](crate::Builder::try_build).
+    #[cfg(feature = "alloc")]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum BuilderError {
+        /// The URI does not have a scheme.
+        MissingScheme,
+        /// The URI does not have a host.
+        MissingHost,
+        /// The URI has a fragment part, which is not a part of the request URI.
+        Fragment,
+        /// The URI has a `%` character that is not followed by two hexadecimal digits.
+        InvalidPercentEncoding,
+        /// The URI has an `oauth_*` query parameter, which would be duplicated by the protocol
+        /// parameters (see [`Builder::strip_oauth_query`](crate::Builder::strip_oauth_query)).
+        OAuthQueryParameter,
+        /// The request has more parameters than the limit set with [`Builder::max_parameters`](crate::Builder::max_parameters).
+        TooManyParameters(TooManyParameters),
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Display for BuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            BuilderError::MissingScheme => f.write_str("URI does not have a scheme"),
+            BuilderError::MissingHost => f.write_str("URI does not have a host"),
+            BuilderError::Fragment => f.write_str("URI must not have a fragment"),
+            BuilderError::InvalidPercentEncoding => {
+                f.write_str("URI has a malformed percent-encoded octet")
+            }
+            BuilderError::OAuthQueryParameter => {
+                f.write_str("URI has an `oauth_*` query parameter")
+            }
+            BuilderError::TooManyParameters(ref e) => Display::fmt(e, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BuilderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            BuilderError::TooManyParameters(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<TooManyParameters> for BuilderError {
+    fn from(e: TooManyParameters) -> Self {
+        BuilderError::TooManyParameters(e)
+    }
+}
+
+/// Checks that `uri` can be signed as a request URI.
+#[cfg(feature = "alloc")]
+pub(crate) fn validate_uri(uri: &str) -> Result<(), BuilderError> {
+    let colon = uri.find(':').ok_or(BuilderError::MissingScheme)?;
+    let (scheme, rest) = (&uri[..colon], &uri[colon + 1..]);
+    let mut chars = scheme.chars();
+    let valid_scheme = matches!(chars.next(), Some(c) if c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    if !valid_scheme {
+        return Err(BuilderError::MissingScheme);
+    }
+
+    let authority = rest.strip_prefix("//").ok_or(BuilderError::MissingHost)?;
+    let authority = &authority[..authority
+        .find(&['/', '?', '#'][..])
+        .unwrap_or(authority.len())];
+    let host = authority.rsplit('@').next().unwrap_or(authority);
+    let host = if host.starts_with('[') {
+        &host[..host.find(']').map_or(host.len(), |i| i + 1)]
+    } else {
+        host.split(':').next().unwrap_or(host)
+    };
+    if host.is_empty() {
+        return Err(BuilderError::MissingHost);
+    }
+
+    if uri.contains('#') {
+        return Err(BuilderError::Fragment);
+    }
+
+    let bytes = uri.as_bytes();
+    for (i, _) in uri.match_indices('%') {
+        let valid = bytes.len() > i + 2
+            && bytes[i + 1].is_ascii_hexdigit()
+            && bytes[i + 2].is_ascii_hexdigit();
+        if !valid {
+            return Err(BuilderError::InvalidPercentEncoding);
+        }
+    }
+
+    Ok(())
+}
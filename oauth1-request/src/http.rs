@@ -0,0 +1,233 @@
+//! Integration with the `http` crate.
+
+use alloc::string::String;
+use core::fmt::Display;
+
+use crate::request::Request;
+use crate::serializer;
+use crate::signature_method::SignatureMethod;
+use crate::Builder;
+
+impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T> {
+    builder_header_value_shorthand! {
+        get_header("GET");
+        put_header("PUT");
+        post_header("POST");
+        delete_header("DELETE");
+        options_header("OPTIONS");
+        head_header("HEAD");
+        connect_header("CONNECT");
+        patch_header("PATCH");
+        trace_header("TRACE");
+    }
+
+    doc_auto_cfg! {
+        /// Authorizes a request to `uri` with a custom HTTP request method, returning an HTTP
+        /// `Authorization` header value as an [`http::HeaderValue`][http1::HeaderValue].
+        ///
+        /// The query parameters of `uri`, if any, are included in the signature.
+        ///
+        /// The header value is built from the same string as [`authorize`](Self::authorize)
+        /// without copying it. All the parameter values in the string are percent-encoded and
+        /// thus consist of visible ASCII characters, so the conversion always succeeds unless
+        /// the `realm` value, which is written as is unless the `encode_realm` option is set,
+        /// contains a control character.
+        ///
+        /// The returned value is marked as sensitive
+        /// ([`HeaderValue::set_sensitive`][http1::HeaderValue::set_sensitive]) since it contains
+        /// the `oauth_token` value.
+        ///
+        /// ## Panics
+        ///
+        /// Panics if the `realm` value contains a control character.
+        #[cfg(feature = "http")]
+        pub fn authorize_header_value<U, R>(
+            &self,
+            method: &str,
+            uri: U,
+            request: &R,
+        ) -> http1::HeaderValue
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            use core::convert::TryFrom;
+
+            let authorization = self.authorize(method, uri, request);
+            let mut ret = http1::HeaderValue::try_from(authorization)
+                .expect("the `realm` value is not a valid header value");
+            ret.set_sensitive(true);
+            ret
+        }
+    }
+
+    doc_auto_cfg! {
+        /// Authorizes a request to an [`http::Uri`][http1::Uri], returning an HTTP
+        /// `Authorization` header value.
+        ///
+        /// The query parameters of `uri` are percent-decoded and merged with the parameters of
+        /// `extra` into the sorted parameter set of the signature, so you don't need to repeat
+        /// them in `extra`.
+        #[cfg(feature = "http")]
+        pub fn build_http_uri<R>(&self, method: &str, uri: &http1::Uri, extra: &R) -> String
+        where
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            // `authorize` folds the query part of the URI into the signature.
+            self.authorize(method, uri, extra)
+        }
+
+        /// Authorizes a request to an [`http::Uri`][http1::Uri] and converts it into an
+        /// [`http::Request`][http1::Request] whose `Authorization` header is set to the value
+        /// returned by [`build_http_uri`](Self::build_http_uri).
+        ///
+        /// If `method` is `POST`, the parameters of `request` are sent in an
+        /// `x-www-form-urlencoded` body. Otherwise, they are appended to the query part of the URI
+        /// and the body is left empty. Convert the body with e.g. `req.map(hyper::Body::from)`.
+        ///
+        /// ## Panics
+        ///
+        /// Panics if the `realm` value is not a valid header value.
+        ///
+        /// ## Example
+        ///
+        #[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+        #[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+        /// # extern crate oauth1_request as oauth;
+        /// # extern crate http1 as http;
+        /// #
+        /// use http::header::{AUTHORIZATION, CONTENT_TYPE};
+        /// use http::{Method, Uri};
+        ///
+        /// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+        /// let builder = oauth::Builder::<_, _>::new(client, oauth::HMAC_SHA1);
+        ///
+        /// let uri: Uri = "https://example.com/api/update?draft=1".parse().unwrap();
+        /// let request = oauth::request! { "status" => "Hello, world!" };
+        /// let req = builder.to_http_request(Method::POST, uri, &request);
+        /// assert!(req.headers()[AUTHORIZATION].to_str().unwrap().starts_with("OAuth "));
+        /// assert_eq!(
+        ///     req.headers()[CONTENT_TYPE],
+        ///     "application/x-www-form-urlencoded",
+        /// );
+        /// assert_eq!(req.body(), "status=Hello%2C%20world%21");
+        ///
+        /// let uri: Uri = "https://example.com/api/search?lang=fr".parse().unwrap();
+        /// let request = oauth::request! { "q" => "café" };
+        /// let req = builder.to_http_request(Method::GET, uri, &request);
+        /// assert_eq!(req.uri(), "https://example.com/api/search?lang=fr&q=caf%C3%A9");
+        /// assert!(req.body().is_empty());
+        /// ```
+        #[cfg(feature = "http")]
+        pub fn to_http_request<R>(
+            &self,
+            method: http1::Method,
+            uri: http1::Uri,
+            request: &R,
+        ) -> http1::Request<String>
+        where
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            use http1::header::{AUTHORIZATION, CONTENT_TYPE};
+
+            let authorization = self.build_http_uri(method.as_str(), &uri, request);
+            let params = serialize_request!(self, request, serializer::Urlencoder::form());
+
+            let builder = http1::Request::builder().header(AUTHORIZATION, authorization);
+            let ret = if method == http1::Method::POST {
+                builder
+                    .method(method)
+                    .uri(uri)
+                    .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+                    .body(params)
+            } else {
+                let uri = if params.is_empty() {
+                    uri
+                } else {
+                    let delim = if uri.query().is_some() { '&' } else { '?' };
+                    let uri = alloc::format!("{}{}{}", uri, delim, params);
+                    uri.parse().expect("appending a query string to a URI yields a valid URI")
+                };
+                builder.method(method).uri(uri).body(String::new())
+            };
+            ret.expect("invalid `Authorization` header value")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::NonZeroU64;
+
+    use oauth_credentials::Credentials;
+
+    use crate::request;
+    use crate::signature_method::PLAINTEXT;
+
+    use super::*;
+
+    #[test]
+    fn to_http_request_query() {
+        use http1::header::{AUTHORIZATION, CONTENT_TYPE};
+        use http1::{Method, Uri};
+
+        let client = Credentials::new("ck", "cs");
+        let mut builder = Builder::<_, &str>::new(client, PLAINTEXT);
+        builder.nonce("nonce").timestamp(NonZeroU64::new(1));
+
+        let request = request! { "q" => "a b" };
+        for method in [Method::GET, Method::PUT, Method::DELETE].iter() {
+            let uri: Uri = "https://example.com/api/items".parse().unwrap();
+            let req = builder.to_http_request(method.clone(), uri, &request);
+            assert_eq!(req.method(), method);
+            assert_eq!(req.uri(), "https://example.com/api/items?q=a%20b");
+            assert!(req.headers().get(CONTENT_TYPE).is_none());
+            assert!(req.body().is_empty());
+            assert_eq!(
+                req.headers()[AUTHORIZATION],
+                builder.build(method.as_str(), "https://example.com/api/items", &request),
+            );
+        }
+
+        let uri: Uri = "https://example.com/api/items".parse().unwrap();
+        let req = builder.to_http_request(Method::GET, uri, &());
+        assert_eq!(req.uri(), "https://example.com/api/items");
+    }
+
+    #[test]
+    fn authorize_header_value() {
+        let client = Credentials::new("ck", "cs");
+        let mut builder = Builder::<_, &str>::new(client, PLAINTEXT);
+        builder.nonce("nonce").timestamp(NonZeroU64::new(1));
+
+        let uri = "https://example.com/api/update";
+        let request = request! { "status" => "Hello, world!" };
+        let value = builder.post_header(uri, &request);
+        assert_eq!(value, builder.post(uri, &request));
+        assert!(value.is_sensitive());
+    }
+
+    #[cfg(feature = "hmac-sha1")]
+    #[test]
+    fn build_http_uri() {
+        use crate::signature_method::HMAC_SHA1;
+
+        let client = Credentials::new("ck", "cs");
+        let mut builder = Builder::<_, &str>::new(client, HMAC_SHA1);
+        builder.nonce("nonce").timestamp(NonZeroU64::new(1));
+
+        let uri = "https://example.com/api/search?q=caf%C3%A9"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            builder.build_http_uri("GET", &uri, &request! { "lang" => "fr" }),
+            builder.get(
+                "https://example.com/api/search",
+                &request! { "lang" => "fr", "q" => "café" },
+            ),
+        );
+    }
+}
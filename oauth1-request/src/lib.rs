@@ -16,9 +16,22 @@
 //!
 //! A typical authorization flow looks like this:
 //!
-#![cfg_attr(all(feature = "derive", feature = "hmac-sha1"), doc = " ```")]
 #![cfg_attr(
-    not(all(feature = "derive", feature = "hmac-sha1")),
+    all(
+        feature = "std",
+        feature = "default-nonce",
+        feature = "derive",
+        feature = "hmac-sha1"
+    ),
+    doc = " ```"
+)]
+#![cfg_attr(
+    not(all(
+        feature = "std",
+        feature = "default-nonce",
+        feature = "derive",
+        feature = "hmac-sha1"
+    )),
     doc = " ```ignore"
 )]
 //! # extern crate oauth1_request as oauth;
@@ -100,8 +113,14 @@
 //!
 //! Use [`oauth::Builder`][Builder] if you need to specify a callback URI or verifier:
 //!
-#![cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
-#![cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+#![cfg_attr(
+    all(feature = "std", feature = "default-nonce", feature = "hmac-sha1"),
+    doc = " ```"
+)]
+#![cfg_attr(
+    not(all(feature = "std", feature = "default-nonce", feature = "hmac-sha1")),
+    doc = " ```ignore"
+)]
 //! # extern crate oauth1_request as oauth;
 //! #
 //! let uri = "https://example.com/oauth/request_temp_credentials";
@@ -113,6 +132,21 @@
 //!     .callback(callback)
 //!     .post(uri, &());
 //! ```
+//!
+//! ## `no_std` support
+//!
+//! The crate supports `no_std` environments with a global allocator. Disable the default
+//! features and enable the `alloc` feature along with the signature methods you need:
+//!
+//! ```toml
+//! [dependencies]
+//! oauth = { version = "0.6", package = "oauth1-request", default-features = false, features = ["alloc", "hmac-sha1"] }
+//! ```
+//!
+//! Without the `std` feature, the current time is not available and you need to set
+//! the timestamp of each request with [`Builder::timestamp`]. Likewise, without
+//! the `default-nonce` feature, you need to set the nonce with [`Builder::nonce`] or
+//! [`Builder::nonce_generator`].
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![doc(html_root_url = "https://docs.rs/oauth1-request/0.6.0")]
@@ -124,7 +158,31 @@ extern crate alloc;
 
 #[macro_use]
 mod util;
+#[macro_use]
+mod macros;
 
+#[cfg(feature = "alloc")]
+mod body_hash;
+#[cfg(feature = "alloc")]
+mod canonical;
+#[cfg(feature = "alloc")]
+mod endpoint;
+#[cfg(feature = "alloc")]
+mod error;
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "alloc")]
+mod oauth_params;
+mod two_legged;
+#[cfg(feature = "alloc")]
+mod verify;
+
+doc_auto_cfg! {
+    #[cfg(feature = "test")]
+    pub mod diagnostics;
+    #[cfg(feature = "alloc")]
+    pub mod parse;
+}
 pub mod request;
 pub mod serializer;
 pub mod signature_method;
@@ -185,6 +243,13 @@ doc_auto_cfg! {
     ///
     /// Use the given string as the parameter's key. The given string must be URI-safe.
     ///
+    /// - `#[oauth1(serialize_with_serde)]`
+    ///
+    /// Serialize the value through its `serde::Serialize` implementation instead of `Display`.
+    /// This requires the `serde` feature and only supports values that serialize as scalars, like
+    /// numbers, strings and unit enum variants. See the `request::serde_scalar` module for details.
+    /// This attribute cannot be used along with `fmt` or `with`.
+    ///
     /// - `#[oauth1(skip)]`
     ///
     /// Do not serialize the field.
@@ -194,6 +259,15 @@ doc_auto_cfg! {
     /// Call the function at `path` and do not serialize the field if the function returns `true`.
     /// The function must be callable as `fn(&T) -> bool`.
     ///
+    /// - `#[oauth1(with = path)]`
+    ///
+    /// Serialize the value returned by the function `display` in the module at `path` instead of
+    /// the field's value. The function must be callable as `fn(&T) -> impl Display`.
+    /// This attribute cannot be used along with `fmt`.
+    ///
+    /// The `path` of the `fmt`, `skip_if` and `with` attributes can also be written in a string
+    /// literal as in `serde`, e.g. `#[oauth1(skip_if = "Option::is_none")]`.
+    ///
     /// ## Container attributes
     ///
     /// - `#[oauth1(crate = "name")]`
@@ -212,6 +286,14 @@ doc_auto_cfg! {
 pub use oauth_credentials::{Credentials, Token};
 
 doc_auto_cfg! {
+    #[cfg(feature = "alloc")]
+    pub use self::canonical::CanonicalRequest;
+    #[cfg(feature = "alloc")]
+    pub use self::endpoint::Endpoint;
+    #[cfg(feature = "alloc")]
+    pub use self::error::{BuilderError, TooManyParameters};
+    #[cfg(feature = "alloc")]
+    pub use self::oauth_params::OAuthParams;
     pub use self::request::ParameterList;
     pub use self::request::Request;
     #[cfg(feature = "hmac-sha1")]
@@ -223,21 +305,37 @@ doc_auto_cfg! {
     pub use self::signature_method::HMAC_SHA1;
     #[cfg(feature = "alloc")]
     pub use self::signature_method::PLAINTEXT;
+    pub use self::two_legged::TwoLeggedBuilder;
+    #[cfg(feature = "alloc")]
+    pub use self::verify::verify;
+    #[cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+    pub use self::verify::{verify_hmac_sha1, verify_hmac_sha1_with_any};
 }
 
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
 #[cfg(feature = "alloc")]
 use alloc::string::String;
-use core::fmt::Debug;
+use core::fmt::{self, Debug};
 use core::fmt::{Display, Write};
 use core::num::NonZeroU64;
 use core::str;
 
+#[cfg(all(feature = "default-nonce", feature = "std"))]
+use rand::{CryptoRng, RngCore};
+
+#[cfg(feature = "alloc")]
+use self::error::validate_uri;
 use self::serializer::auth;
 use self::signature_method::SignatureMethod;
 
 cfg_type_param_hack! {
     /// A builder for OAuth `Authorization` header string.
-    #[derive(Clone, Debug)]
+    ///
+    /// The `Debug` representation of `Builder` is safe to log: it never includes the secrets, and
+    /// shows only a prefix of the token identifier and whether the `oauth_callback`,
+    /// `oauth_verifier` and `oauth_nonce` values are set.
+    #[derive(Clone)]
     pub struct Builder<
         'a,
         SM,
@@ -249,189 +347,1030 @@ cfg_type_param_hack! {
         client: Credentials<C>,
         token: Option<Credentials<T>>,
         options: auth::Options<'a>,
+        #[cfg(feature = "alloc")]
+        parameter_name_transform: Option<fn(&str) -> Cow<'_, str>>,
+        #[cfg(feature = "alloc")]
+        max_parameters: Option<usize>,
     }
 }
 
-macro_rules! builder_authorize_shorthand {
-    ($($name:ident($method:expr);)*) => {doc_auto_cfg! {$(
-        #[doc = concat!("Authorizes a `", $method, "` request to `uri`,")]
+impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T> {
+    /// Creates a `Builder` that signs requests using the specified client credentials
+    /// and signature method.
+    pub fn new(client: Credentials<C>, signature_method: SM) -> Self {
+        Builder {
+            signature_method,
+            client,
+            token: None,
+            options: auth::Options::new(),
+            #[cfg(feature = "alloc")]
+            parameter_name_transform: None,
+            #[cfg(feature = "alloc")]
+            max_parameters: None,
+        }
+    }
+
+    /// Creates a `Builder` that signs requests using the specified client credentials,
+    /// signature method and options.
+    ///
+    /// This is useful for signing many requests with the same options, like a `realm` or
+    /// a `nonce_config`, by configuring an [`auth::Options`] once and cloning it for each
+    /// `Builder`.
+    ///
+    /// Note that the `nonce` and `timestamp` options are sent as is with every request signed
+    /// with the `Builder`. Leave them unset in a shared `Options` so that a fresh nonce and
+    /// the current timestamp are generated for each request.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(
+        all(feature = "std", feature = "default-nonce", feature = "hmac-sha1"),
+        doc = " ```"
+    )]
+    #[cfg_attr(
+        not(all(feature = "std", feature = "default-nonce", feature = "hmac-sha1")),
+        doc = " ```ignore"
+    )]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// use oauth::serializer::auth;
+    ///
+    /// let mut options = auth::Options::new();
+    /// options.realm("Example").version(true);
+    ///
+    /// for &(key, secret) in &[("key1", "secret1"), ("key2", "secret2")] {
+    ///     let client = oauth::Credentials::new(key, secret);
+    ///     let options = options.clone();
+    ///     let builder = oauth::Builder::<_, _>::with_options(client, oauth::HMAC_SHA1, options);
+    ///     let authorization = builder.get("https://example.com/api/foo", &());
+    ///     assert!(authorization.starts_with("OAuth realm=\"Example\","));
+    ///     assert!(authorization.contains("oauth_version=\"1.0\""));
+    /// }
+    /// ```
+    pub fn with_options(
+        client: Credentials<C>,
+        signature_method: SM,
+        options: auth::Options<'a>,
+    ) -> Self {
+        let mut ret = Builder::new(client, signature_method);
+        ret.options = options;
+        ret
+    }
+
+    /// Creates a `Builder` that uses the token credentials from `token`.
+    pub fn with_token(token: Token<C, T>, signature_method: SM) -> Self {
+        let mut ret = Builder::new(token.client, signature_method);
+        ret.token(token.token);
+        ret
+    }
+
+    /// Sets/unsets the token credentials pair to sign requests with.
+    ///
+    /// Passing `None` omits the `oauth_token` parameter altogether, while passing credentials
+    /// with an empty identifier (e.g. `Credentials::new("", "")`) sends an empty `oauth_token=""`
+    /// parameter, which is included in the signature. Some providers expect the latter in
+    /// certain steps of the authorization flow.
+    pub fn token(&mut self, token: impl Into<Option<Credentials<T>>>) -> &mut Self {
+        self.token = token.into();
+        self
+    }
+
+    /// Returns a copy of the `Builder` that signs requests with the token credentials obtained
+    /// from a Temporary Credential Request or Token Request endpoint
+    /// ([RFC 5849 section 2][rfc]).
+    ///
+    /// [rfc]: https://tools.ietf.org/html/rfc5849#section-2
+    ///
+    /// The `oauth_callback` and `oauth_verifier` values are unset in the returned `Builder` since
+    /// they are only relevant to the previous step of the authorization flow. The other options
+    /// are kept.
+    ///
+    /// With the `serde` feature, you can deserialize the `token` from the response body with
+    /// an `x-www-form-urlencoded` deserializer like `serde_urlencoded`.
+    ///
+    /// ## Example
+    ///
+    /// A three-legged authorization flow:
+    ///
+    #[cfg_attr(
+        all(feature = "std", feature = "default-nonce", feature = "hmac-sha1"),
+        doc = " ```"
+    )]
+    #[cfg_attr(
+        not(all(feature = "std", feature = "default-nonce", feature = "hmac-sha1")),
+        doc = " ```ignore"
+    )]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+    /// let mut builder = oauth::Builder::<_, _>::new(client, oauth::HMAC_SHA1);
+    ///
+    /// // 1. Obtain temporary credentials.
+    /// builder.callback("https://client.example.net/oauth/callback");
+    /// let authorization = builder.post("https://example.com/oauth/request_token", &());
+    /// assert!(authorization.contains("oauth_callback="));
+    /// // Suppose that the server responded with
+    /// // `oauth_token=temporary&oauth_token_secret=temporary_secret&oauth_callback_confirmed=true`.
+    /// let temporary = oauth::Credentials::new("temporary", "temporary_secret");
+    ///
+    /// // 2. Redirect the user to the authorization endpoint, which redirects the user back to
+    /// // the callback URI with an `oauth_verifier` value.
+    /// let verifier = "verifier";
+    ///
+    /// // 3. Exchange the temporary credentials for token credentials.
+    /// let mut builder = builder.with_token_from_response(temporary);
+    /// builder.verifier(verifier);
+    /// let authorization = builder.post("https://example.com/oauth/access_token", &());
+    /// assert!(authorization.contains("oauth_token=\"temporary\""));
+    /// assert!(authorization.contains("oauth_verifier=\"verifier\""));
+    /// assert!(!authorization.contains("oauth_callback="));
+    /// // Suppose that the server responded with `oauth_token=token&oauth_token_secret=secret`.
+    /// let token = oauth::Credentials::new("token", "secret");
+    ///
+    /// // 4. Access the protected resources.
+    /// let builder = builder.with_token_from_response(token);
+    /// let authorization = builder.get("https://example.com/api/foo", &());
+    /// assert!(authorization.contains("oauth_token=\"token\""));
+    /// assert!(!authorization.contains("oauth_verifier="));
+    /// ```
+    pub fn with_token_from_response(&self, token: Credentials<T>) -> Self
+    where
+        SM: Clone,
+        C: Clone,
+        T: Clone,
+    {
+        let mut ret = self.clone();
+        ret.token(token).callback(None).verifier(None);
+        ret
+    }
+
+    /// Sets/unsets the `realm` parameter of the `Authorization` header.
+    ///
+    /// See [`auth::Options::realm`] for details.
+    pub fn realm(&mut self, realm: impl Into<Option<&'a str>>) -> &mut Self {
+        self.options.realm(realm);
+        self
+    }
+
+    /// Sets whether to percent encode the `realm` value.
+    ///
+    /// By default, the value is written as is. See [`auth::Options::encode_realm`] for details.
+    pub fn encode_realm(&mut self, encode_realm: bool) -> &mut Self {
+        self.options.encode_realm(encode_realm);
+        self
+    }
+
+    /// Sets/unsets the `oauth_callback` URI.
+    pub fn callback(&mut self, callback: impl Into<Option<&'a str>>) -> &mut Self {
+        self.options.callback(callback);
+        self
+    }
+
+    /// Sets/unsets the `oauth_verifier` value.
+    pub fn verifier(&mut self, verifier: impl Into<Option<&'a str>>) -> &mut Self {
+        self.options.verifier(verifier);
+        self
+    }
+
+    /// Sets whether to leave the `oauth_verifier` value out of the `Authorization` header,
+    /// for providers that expect it in the request body of the access token request.
+    ///
+    /// The value is still included in the signature. See [`auth::Options::verifier_in_body`] for
+    /// details.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(
+        all(feature = "std", feature = "default-nonce", feature = "hmac-sha1"),
+        doc = " ```"
+    )]
+    #[cfg_attr(
+        not(all(feature = "std", feature = "default-nonce", feature = "hmac-sha1")),
+        doc = " ```ignore"
+    )]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// let token = oauth::Token::from_parts("consumer_key", "consumer_secret", "token", "secret");
+    /// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+    /// builder.verifier("verifier").verifier_in_body(true);
+    ///
+    /// let authorization = builder.post("https://example.com/oauth/access_token", &());
+    /// assert!(!authorization.contains("oauth_verifier="));
+    /// // Send this as the request body.
+    /// let body = oauth::to_form(&oauth::request! { "oauth_verifier" => "verifier" });
+    /// assert_eq!(body, "oauth_verifier=verifier");
+    /// ```
+    pub fn verifier_in_body(&mut self, verifier_in_body: bool) -> &mut Self {
+        self.options.verifier_in_body(verifier_in_body);
+        self
+    }
+
+    /// Sets whether to panic if both the `oauth_callback` URI and the `oauth_verifier` value are
+    /// set when authorizing a request.
+    ///
+    /// The `oauth_callback` URI belongs to the temporary credentials request and the
+    /// `oauth_verifier` value to the token request, so having both set usually means that an
+    /// option of a previous step was left on the `Builder`. By default, this is only checked with
+    /// a debug assertion. See [`auth::Options::strict_flow`] for details.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(
+        all(feature = "alloc", feature = "hmac-sha1"),
+        doc = " ```should_panic"
+    )]
+    #[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+    /// let mut builder = oauth::Builder::<_, _>::new(client, oauth::HMAC_SHA1);
+    /// builder
+    ///     .strict_flow(true)
+    ///     .callback("https://client.example.net/oauth/callback")
+    ///     .verifier("verifier");
+    ///
+    /// // Panics even in release builds.
+    /// builder.post("https://example.com/oauth/access_token", &());
+    /// ```
+    pub fn strict_flow(&mut self, strict_flow: bool) -> &mut Self {
+        self.options.strict_flow(strict_flow);
+        self
+    }
+
+    /// Sets/unsets the `oauth_nonce` value.
+    ///
+    /// By default, `Builder` generates a random nonce for each request.
+    /// This method overrides that behavior and forces the `Builder` to use the specified nonce.
+    ///
+    /// This method is for debugging/testing purpose only and should not be used in production,
+    /// unless the `default-nonce` feature is disabled, in which case you must set a nonce for every
+    /// request whose signature method uses one.
+    ///
+    /// ## Panics
+    ///
+    /// Without the `default-nonce` feature, the methods that build a request panic if the
+    /// signature method uses a nonce and this is not set.
+    pub fn nonce(&mut self, nonce: impl Into<Option<&'a str>>) -> &mut Self {
+        self.options.nonce(nonce);
+        self
+    }
+
+    /// Sets the random number generator to generate `oauth_nonce` values with.
+    ///
+    /// By default, the nonces are generated with `rand::rngs::OsRng`. This method lets you use
+    /// your own cryptographically secure RNG instead, while keeping the format of the nonces.
+    ///
+    /// This is a shorthand for [`nonce_generator`](Self::nonce_generator). The RNG is wrapped in
+    /// a `Mutex` so that the `Builder` can be shared across threads.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+    #[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// use std::sync::Mutex;
+    ///
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let rng = Mutex::new(StdRng::from_entropy());
+    ///
+    /// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+    /// let mut builder = oauth::Builder::<_, _>::new(client, oauth::HMAC_SHA1);
+    /// builder.nonce_rng(&rng);
+    ///
+    /// let authorization_header = builder.get("https://example.com/api/foo", &());
+    /// ```
+    #[cfg(all(feature = "default-nonce", feature = "std"))]
+    pub fn nonce_rng<R>(&mut self, rng: &'a std::sync::Mutex<R>) -> &mut Self
+    where
+        R: RngCore + CryptoRng + Send,
+    {
+        self.nonce_generator(rng)
+    }
+
+    /// Sets the generator to generate `oauth_nonce` values with.
+    ///
+    /// This has no effect if a nonce is set with the `nonce` method. The generator receives
+    /// the configuration set with `nonce_config`, which it may ignore. Unlike the default random
+    /// nonces, this does not require the `default-nonce` feature, which is useful in environments
+    /// where the default RNG is not available.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(all(feature = "std", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "std", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// use std::fmt::{self, Write};
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    ///
+    /// use oauth::serializer::auth::{NonceConfig, NonceGenerator};
+    ///
+    /// struct Counter(AtomicU64);
+    ///
+    /// impl NonceGenerator for Counter {
+    ///     fn generate(&self, _: Option<NonceConfig>, w: &mut dyn Write) -> fmt::Result {
+    ///         write!(w, "nonce-{}", self.0.fetch_add(1, Ordering::Relaxed) + 1)
+    ///     }
+    /// }
+    ///
+    /// let counter = Counter(AtomicU64::new(0));
+    ///
+    /// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+    /// let mut builder = oauth::Builder::<_, _>::new(client, oauth::HMAC_SHA1);
+    /// builder.nonce_generator(&counter);
+    ///
+    /// let authorization = builder.get("https://example.com/api/foo", &());
+    /// assert!(authorization.contains("oauth_nonce=\"nonce-1\""));
+    /// let authorization = builder.get("https://example.com/api/foo", &());
+    /// assert!(authorization.contains("oauth_nonce=\"nonce-2\""));
+    /// ```
+    pub fn nonce_generator<G: auth::NonceGenerator>(&mut self, generator: &'a G) -> &mut Self {
+        self.options
+            .nonce_generator(generator as &dyn auth::NonceGenerator);
+        self
+    }
+
+    /// Sets the length and character set of the `oauth_nonce` values generated by the `Builder`.
+    ///
+    /// This has no effect if a nonce is set with the `nonce` method.
+    ///
+    /// By default, the nonces are 12 (or less) characters long and consist of the URL-safe
+    /// Base64 alphabet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is zero or greater than
+    /// [`NonceConfig::MAX_LEN`](auth::NonceConfig::MAX_LEN) (64).
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(all(feature = "std", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "std", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// use oauth::serializer::auth::NonceAlphabet;
+    ///
+    /// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+    /// let mut builder = oauth::Builder::<_, _>::new(client, oauth::HMAC_SHA1);
+    /// // Some providers only accept 32 hexadecimal digits.
+    /// builder.nonce_config(32, NonceAlphabet::Hex);
+    ///
+    /// let authorization_header = builder.get("https://example.com/api/foo", &());
+    /// ```
+    pub fn nonce_config(&mut self, len: usize, alphabet: auth::NonceAlphabet) -> &mut Self {
+        self.options
+            .nonce_config(auth::NonceConfig::new(len, alphabet));
+        self
+    }
+
+    /// Sets/unsets the `oauth_timestamp` value.
+    ///
+    /// By default, `Builder` uses the timestamp of the time when `authorize`-like method is called.
+    /// This method overrides that behavior and forces the `Builder` to use the specified timestamp.
+    ///
+    /// This method is for debugging/testing purpose only and should not be used in production.
+    pub fn timestamp(&mut self, timestamp: impl Into<Option<NonZeroU64>>) -> &mut Self {
+        self.options.timestamp(timestamp);
+        self
+    }
+
+    doc_auto_cfg! {
+        /// Sets both of the `oauth_nonce` and `oauth_timestamp` values, making the signatures
+        /// fully deterministic.
+        ///
+        /// This is a shorthand for calling [`nonce`](Self::nonce) and
+        /// [`timestamp`](Self::timestamp) and is meant for testing, for example, the replay
+        /// protection of a server by sending requests with a reused nonce.
+        ///
+        /// ## Example
+        ///
+        #[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+        #[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+        /// # extern crate oauth1_request as oauth;
+        /// #
+        /// use std::num::NonZeroU64;
+        ///
+        /// let token = oauth::Token::from_parts("consumer_key", "consumer_secret", "token", "secret");
+        /// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+        /// builder.fixed("nonce", NonZeroU64::new(1234567890).unwrap());
+        ///
+        /// let uri = "https://example.com/api/foo";
+        /// let first = builder.get(uri, &());
+        /// // Replaying the same request yields the same signature, which the server should reject.
+        /// assert_eq!(builder.get(uri, &()), first);
+        ///
+        /// // Reusing the nonce for a different request yields a different signature.
+        /// assert_ne!(builder.get("https://example.com/api/bar", &()), first);
+        /// ```
+        #[cfg(feature = "test")]
+        pub fn fixed(&mut self, nonce: &'a str, timestamp: NonZeroU64) -> &mut Self {
+            self.nonce(nonce).timestamp(timestamp)
+        }
+    }
+
+    /// Sets whether to include the `oauth_version` value in requests.
+    ///
+    /// Like the other options, the setting applies to every request built with the `Builder`
+    /// (and its clones) until it is changed or reset with [`reset_options`](Self::reset_options).
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(
+        all(feature = "std", feature = "default-nonce", feature = "hmac-sha1"),
+        doc = " ```"
+    )]
+    #[cfg_attr(
+        not(all(feature = "std", feature = "default-nonce", feature = "hmac-sha1")),
+        doc = " ```ignore"
+    )]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+    /// let mut builder = oauth::Builder::<_, _>::new(client, oauth::HMAC_SHA1);
+    /// builder.version(true);
+    ///
+    /// let uri = "https://example.com/api/foo";
+    /// assert!(builder.get(uri, &()).contains("oauth_version=\"1.0\""));
+    /// assert!(builder.post(uri, &()).contains("oauth_version=\"1.0\""));
+    /// assert!(builder.clone().get(uri, &()).contains("oauth_version=\"1.0\""));
+    /// ```
+    pub fn version(&mut self, version: bool) -> &mut Self {
+        self.options.version(version);
+        self
+    }
+
+    /// Sets whether to keep the query part of `uri` in the base string URI.
+    ///
+    /// By default, the query parameters of `uri` are folded out of the base string URI as
+    /// the OAuth standard requires. See [`auth::Options::keep_query_in_uri`] for details.
+    pub fn keep_query_in_uri(&mut self, keep_query_in_uri: bool) -> &mut Self {
+        self.options.keep_query_in_uri(keep_query_in_uri);
+        self
+    }
+
+    /// Sets whether to strip `oauth_*` parameters in the query part of `uri` from the signature.
+    ///
+    /// By default, the `Builder` panics in debug builds if `uri` contains such a parameter and
+    /// signs the parameter as is in release builds, while `try_build` returns an error on it.
+    /// See [`auth::Options::strip_oauth_query`] for details.
+    pub fn strip_oauth_query(&mut self, strip_oauth_query: bool) -> &mut Self {
+        self.options.strip_oauth_query(strip_oauth_query);
+        self
+    }
+
+    /// Sets whether to trim leading and trailing ASCII whitespace from the request parameter
+    /// values before signing and encoding them.
+    ///
+    /// By default, the values are signed as is, as the OAuth standard requires. This is only
+    /// useful for talking to nonconforming servers that trim the values before verifying
+    /// the signature. See [`auth::Options::trim_values`] for details.
+    pub fn trim_values(&mut self, trim_values: bool) -> &mut Self {
+        self.options.trim_values(trim_values);
+        self
+    }
+
+    /// Sets the set of characters to percent encode in the request parameter values.
+    ///
+    /// Defaults to [`EncodeSet::RFC5849`](serializer::EncodeSet::RFC5849) as the OAuth standard
+    /// requires. See [`auth::Options::encode_set`] for details.
+    pub fn encode_set(&mut self, encode_set: serializer::EncodeSet) -> &mut Self {
+        self.options.encode_set(encode_set);
+        self
+    }
+
+    /// Sets whether to percent encode the request parameter values with the conventions of
+    /// legacy OAuth Core 1.0 servers (prior to the 1.0a revision).
+    ///
+    /// This is a compatibility shim for ancient services which expect `~` to be encoded as `%7E`.
+    /// Setting this to `true` is equivalent to setting `encode_set` to
+    /// [`EncodeSet::LEGACY`](serializer::EncodeSet::LEGACY), and setting this to `false` resets
+    /// `encode_set` to the default.
+    pub fn legacy_encoding(&mut self, legacy_encoding: bool) -> &mut Self {
+        self.encode_set(if legacy_encoding {
+            serializer::EncodeSet::LEGACY
+        } else {
+            serializer::EncodeSet::RFC5849
+        })
+    }
+
+    /// Sets whether to strictly follow the signature base string construction of the OAuth
+    /// standard.
+    ///
+    /// Setting this to `true` removes the default port (`80` for `http` and `443` for `https`)
+    /// from the base string URI and overrides the options for nonconforming servers
+    /// (`keep_query_in_uri`, `trim_values` and `encode_set`) with their conforming defaults.
+    /// See [`auth::Options::rfc_strict`] for the full list of normalizations.
+    pub fn rfc_strict(&mut self, rfc_strict: bool) -> &mut Self {
+        self.options.rfc_strict(rfc_strict);
+        self
+    }
+
+    doc_auto_cfg! {
+        /// Sets a function to transform the keys of the request parameters with.
+        ///
+        /// This is a last-resort workaround for nonconforming servers that expect parameter names
+        /// in a form different from the one of your `Request`, like uppercased names.
+        /// The function is applied to the keys of the request parameters (but not to the `oauth_*`
+        /// protocol parameters) and the resulting keys are used both in the signature and in the
+        /// output, so the signature stays valid. The parameters are re-sorted by the resulting
+        /// keys, which requires buffering them on each signing.
+        ///
+        /// By default, the keys are used as is.
+        #[cfg(feature = "alloc")]
+        pub fn parameter_name_transform(
+            &mut self,
+            transform: fn(&str) -> Cow<'_, str>,
+        ) -> &mut Self {
+            self.parameter_name_transform = Some(transform);
+            self
+        }
+    }
+
+    doc_auto_cfg! {
+        /// Sets/unsets the maximum number of the parameters of a request.
+        ///
+        /// If set, [`try_authorize`](Builder::try_authorize) returns an error instead of signing
+        /// a request that has more parameters than `max_parameters`, which is useful for catching
+        /// requests that a provider would reject for exceeding its limit. The count includes only
+        /// the parameters of the `Request` and neither the query parameters of the URI nor
+        /// the OAuth protocol parameters.
+        ///
+        /// The other methods of `Builder` do not check the limit.
+        #[cfg(feature = "alloc")]
+        pub fn max_parameters(&mut self, max_parameters: impl Into<Option<usize>>) -> &mut Self {
+            self.max_parameters = max_parameters.into();
+            self
+        }
+    }
+
+    /// Resets all the options (`realm`, `encode_realm`, `callback`, `verifier`,
+    /// `verifier_in_body`, `strict_flow`, `nonce`, `nonce_generator`, `nonce_config`,
+    /// `timestamp`, `version`, `keep_query_in_uri`, `strip_oauth_query`, `trim_values`,
+    /// `encode_set` and `rfc_strict`) to their default values.
+    ///
+    /// The client and token credentials and the signature method are left untouched.
+    /// This is useful to prevent the options of a previous request, like an `oauth_verifier`
+    /// of a token request, from leaking into subsequent requests when reusing a `Builder`.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(
+        all(feature = "std", feature = "default-nonce", feature = "hmac-sha1"),
+        doc = " ```"
+    )]
+    #[cfg_attr(
+        not(all(feature = "std", feature = "default-nonce", feature = "hmac-sha1")),
+        doc = " ```ignore"
+    )]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// let token = oauth::Token::from_parts("consumer_key", "consumer_secret", "token", "secret");
+    /// let mut builder = oauth::Builder::with_token(token, oauth::HMAC_SHA1);
+    ///
+    /// builder.verifier("verifier").nonce("nonce");
+    /// let authorization = builder.post("https://example.com/oauth/access_token", &());
+    /// assert!(authorization.contains("oauth_verifier=\"verifier\""));
+    ///
+    /// builder.reset_options();
+    /// let authorization = builder.get("https://example.com/api/foo", &());
+    /// assert!(authorization.contains("oauth_token=\"token\""));
+    /// assert!(!authorization.contains("oauth_verifier="));
+    /// assert!(!authorization.contains("oauth_nonce=\"nonce\""));
+    /// ```
+    pub fn reset_options(&mut self) -> &mut Self {
+        self.options = auth::Options::new();
+        self
+    }
+
+    /// Returns the options of the `Builder`.
+    ///
+    /// The returned `Options` can be cloned to create another `Builder` with the same options
+    /// with [`with_options`](Self::with_options).
+    pub fn get_options(&self) -> &auth::Options<'a> {
+        &self.options
+    }
+
+    builder_authorize_shorthand! {
+        get("GET");
+        put("PUT");
+        post("POST");
+        delete("DELETE");
+        options("OPTIONS");
+        head("HEAD");
+        connect("CONNECT");
+        patch("PATCH");
+        trace("TRACE");
+    }
+
+    builder_to_form_shorthand! {
+        put_form("PUT");
+        post_form("POST");
+        options_form("OPTIONS");
+        patch_form("PATCH");
+    }
+
+    builder_to_query_shorthand! {
+        get_query("GET");
+        put_query("PUT");
+        post_query("POST");
+        delete_query("DELETE");
+        options_query("OPTIONS");
+        head_query("HEAD");
+        connect_query("CONNECT");
+        patch_query("PATCH");
+        trace_query("TRACE");
+    }
+
+    doc_auto_cfg! {
+        /// Authorizes a request to `uri` with a custom HTTP request method,
         /// returning an HTTP `Authorization` header value.
         ///
-        /// `uri` must not contain a query part, which would result in a wrong signature.
+        /// The query parameters of `uri`, if any, are included in the signature.
+        #[cfg(feature = "alloc")]
+        pub fn authorize<U, R>(&self, method: &str, uri: U, request: &R) -> String
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            let signature_method = self.signature_method.clone();
+            self.build_with(signature_method, &self.options, method, uri, request)
+        }
+    }
+
+    doc_auto_cfg! {
+        /// Authorizes the same request to `uri` under each of the given HTTP request methods,
+        /// returning pairs of the method and its HTTP `Authorization` header value.
+        ///
+        /// The request method is a part of the signature base string, so each header has
+        /// a different signature.
+        ///
+        /// The query parameters of `uri`, if any, are included in the signature.
+        #[cfg(feature = "alloc")]
+        pub fn authorize_methods<U, R>(
+            &self,
+            methods: &[&str],
+            uri: U,
+            request: &R,
+        ) -> alloc::vec::Vec<(String, String)>
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            methods
+                .iter()
+                .map(|&method| (method.into(), self.authorize(method, &uri, request)))
+                .collect()
+        }
+    }
+
+    doc_auto_cfg! {
+        /// Authorizes a request to `uri`, returning an HTTP `Authorization` header value along
+        /// with the parameters of `request` as key-value pairs.
+        ///
+        /// The values of the pairs are not percent encoded, which is useful for HTTP clients that
+        /// take form fields as a list of pairs and encode them by themselves. The pairs are
+        /// sorted and are exactly the parameters that are signed, except that they do not include
+        /// the query parameters of `uri` nor the OAuth protocol parameters. In particular, the
+        /// values are trimmed if [`trim_values`](Builder::trim_values) is set. If you set
+        /// a custom [`encode_set`](Builder::encode_set), have the HTTP client encode the values
+        /// with the same set, since the signature covers their encoded form.
+        #[cfg(feature = "alloc")]
+        pub fn build_with_pairs<U, R>(
+            &self,
+            method: &str,
+            uri: U,
+            request: &R,
+        ) -> (String, alloc::vec::Vec<(String, String)>)
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            let params = serialize_request!(
+                self,
+                request,
+                request::merge::Collect::signing(&self.options)
+            );
+            let params = request::merge::Collected::new(params);
+
+            let serializer = serializer::auth::Authorizer::authorization(
+                method,
+                uri,
+                self.client.as_ref(),
+                self.token.as_ref().map(Credentials::as_ref),
+                &self.options,
+                self.signature_method.clone(),
+            );
+
+            (params.serialize(serializer), params.into_pairs())
+        }
+    }
+
+    doc_auto_cfg! {
+        /// Same as `authorize` except that this returns an error if `request` has more
+        /// parameters than the limit set with [`max_parameters`](Builder::max_parameters).
+        ///
+        /// ## Example
+        ///
+        #[cfg_attr(all(feature = "std", feature = "default-nonce", feature = "hmac-sha1"), doc = " ```")]
+        #[cfg_attr(not(all(feature = "std", feature = "default-nonce", feature = "hmac-sha1")), doc = " ```ignore")]
+        /// # extern crate oauth1_request as oauth;
+        /// #
+        /// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+        /// let mut builder = oauth::Builder::<_, _>::new(client, oauth::HMAC_SHA1);
+        /// builder.max_parameters(2);
+        ///
+        /// let uri = "https://example.com/api/foo";
+        /// let request = oauth::request! { "a" => 1, "b" => 2 };
+        /// assert!(builder.try_authorize("GET", uri, &request).is_ok());
+        ///
+        /// let request = oauth::request! { "a" => 1, "b" => 2, "c" => 3 };
+        /// let error = builder.try_authorize("GET", uri, &request).unwrap_err();
+        /// assert_eq!(error.count(), 3);
+        /// assert_eq!(error.max(), 2);
+        /// ```
+        #[cfg(feature = "alloc")]
+        pub fn try_authorize<U, R>(
+            &self,
+            method: &str,
+            uri: U,
+            request: &R,
+        ) -> Result<String, TooManyParameters>
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            // Counts the parameters without formatting their values.
+            struct Count(usize);
+            impl serializer::Serializer for Count {
+                type Output = usize;
+
+                fn serialize_parameter<V: Display>(&mut self, _: &str, _: V) {
+                    self.0 += 1;
+                }
+
+                fn serialize_parameter_encoded<V: Display>(&mut self, _: &str, _: V) {
+                    self.0 += 1;
+                }
+
+                serializer::skip_serialize_oauth_parameters!();
+
+                fn end(self) -> usize {
+                    self.0
+                }
+            }
+
+            if let Some(max) = self.max_parameters {
+                let count = request.serialize(Count(0));
+                if count > max {
+                    return Err(TooManyParameters { count, max });
+                }
+            }
+
+            Ok(self.authorize(method, uri, request))
+        }
+    }
+
+    doc_auto_cfg! {
+        /// Same as `try_authorize` except that this also validates `uri` before signing the
+        /// request, returning an error instead of producing a signature that the server would
+        /// reject.
+        ///
+        /// `uri` must be an absolute URI with a scheme and a host, must not contain a fragment
+        /// part ([RFC 5849 section 3.4.1.2.][rfc]) and must not contain a malformed
+        /// percent-encoded octet. It must not contain an `oauth_*` query parameter either, unless
+        /// [`strip_oauth_query`](Builder::strip_oauth_query) is set.
+        ///
+        /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1.2
+        ///
+        /// ## Example
+        ///
+        #[cfg_attr(all(feature = "std", feature = "default-nonce", feature = "hmac-sha1"), doc = " ```")]
+        #[cfg_attr(not(all(feature = "std", feature = "default-nonce", feature = "hmac-sha1")), doc = " ```ignore")]
+        /// # extern crate oauth1_request as oauth;
+        /// #
+        /// use oauth::BuilderError;
+        ///
+        /// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+        /// let builder = oauth::Builder::<_, _>::new(client, oauth::HMAC_SHA1);
+        ///
+        /// assert!(builder.try_build("GET", "https://example.com/api", &()).is_ok());
+        /// assert_eq!(
+        ///     builder.try_build("GET", "/api", &()),
+        ///     Err(BuilderError::MissingScheme),
+        /// );
+        /// assert_eq!(
+        ///     builder.try_build("GET", "https://example.com/api#top", &()),
+        ///     Err(BuilderError::Fragment),
+        /// );
+        /// assert_eq!(
+        ///     builder.try_build("GET", "https://example.com/api?q=100%", &()),
+        ///     Err(BuilderError::InvalidPercentEncoding),
+        /// );
+        /// assert_eq!(
+        ///     builder.try_build("GET", "https://example.com/api?oauth_token=foo", &()),
+        ///     Err(BuilderError::OAuthQueryParameter),
+        /// );
+        /// ```
+        #[cfg(feature = "alloc")]
+        pub fn try_build<U, R>(
+            &self,
+            method: &str,
+            uri: U,
+            request: &R,
+        ) -> Result<String, BuilderError>
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            use alloc::string::ToString;
+
+            let uri = uri.to_string();
+            validate_uri(&uri)?;
+            if !self.options.strips_oauth_query() {
+                if let Some(i) = uri.find('?') {
+                    let query = util::QueryParameters::parse(&uri[i + 1..], Default::default());
+                    if query.contains_oauth_parameters() {
+                        return Err(BuilderError::OAuthQueryParameter);
+                    }
+                }
+            }
+            Ok(self.try_authorize(method, &uri, request)?)
+        }
+    }
+
+    doc_auto_cfg! {
+        /// Same as `try_build` except that this panics instead of returning an error.
+        ///
+        /// ## Panics
+        ///
+        /// Panics if [`try_build`](Builder::try_build) would return an error, i.e. if `uri` is
+        /// not a valid request URI or `request` has too many parameters.
         #[cfg(feature = "alloc")]
-        pub fn $name<U, R>(&self, uri: U, request: &R) -> String
+        pub fn build<U, R>(&self, method: &str, uri: U, request: &R) -> String
         where
             U: Display,
             R: Request + ?Sized,
             SM: Clone,
         {
-            self.authorize($method, uri, request)
+            match self.try_build(method, uri, request) {
+                Ok(authorization) => authorization,
+                Err(e) => panic!("{}", e),
+            }
         }
-    )*}};
-}
+    }
 
-macro_rules! builder_to_form_shorthand {
-    ($($name:ident($method:expr);)*) => {doc_auto_cfg! {$(
-        #[doc = concat!("Authorizes a `", $method, "` request to `uri`,")]
-        /// writing the OAuth protocol parameters to an `x-www-form-urlencoded` string
-        /// along with the other request parameters.
+    doc_auto_cfg! {
+        /// Authorizes a request to `uri` with the parameters of `request` and the additional
+        /// parameters of `extra`, returning an HTTP `Authorization` header value.
+        ///
+        /// The parameters of `request` and `extra` are merged into a single sorted parameter set
+        /// in the signature. This is useful when most of the parameters are known at compile time
+        /// (e.g. a type deriving `Request`) but some are only known at runtime, which you can put
+        /// in a [`ParameterList`] that sorts them. It also signs a request whose parameters are
+        /// sent in both of the query part of the URI and an `x-www-form-urlencoded` body, which
+        /// the OAuth standard requires to be signed together regardless of `method`.
+        ///
+        /// The query parameters of `uri`, if any, are included in the signature.
         ///
-        /// `uri` must not contain a query part, which would result in a wrong signature.
+        /// ## Example
+        ///
+        #[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+        #[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+        /// # extern crate oauth1_request as oauth;
+        /// #
+        /// #[derive(oauth::Request)]
+        /// struct Search<'a> {
+        ///     q: &'a str,
+        /// }
+        ///
+        /// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+        /// let builder = oauth::Builder::<_, _>::new(client, oauth::HMAC_SHA1);
+        ///
+        /// let uri = "https://example.com/api/search";
+        /// let extra = oauth::ParameterList::new([("page", 2), ("count", 20)]);
+        /// let authorization = builder.build_with_extra("GET", uri, &Search { q: "rust" }, &extra);
+        /// # let _ = authorization;
+        /// ```
         #[cfg(feature = "alloc")]
-        pub fn $name<U, R>(&self, uri: U, request: &R) -> String
+        pub fn build_with_extra<U, R, E>(
+            &self,
+            method: &str,
+            uri: U,
+            request: &R,
+            extra: &E,
+        ) -> String
         where
             U: Display,
             R: Request + ?Sized,
+            E: Request + ?Sized,
             SM: Clone,
         {
-            self.to_form($method, uri, request)
-        }
-    )*}};
-}
-
-macro_rules! builder_to_query_shorthand {
-    ($($name:ident($method:expr);)*) => {$(
-        doc_coerce_expr! {
-            #[doc = concat!("Authorizes a `", $method, "` request to `uri`, appending")]
-            /// the OAuth protocol parameters to `uri` along with the other request parameters.
-            ///
-            /// `uri` must not contain a query part, which would result in a wrong signature.
-            pub fn $name<W, R>(&self, uri: W, request: &R) -> W
-            where
-                W: Display + Write,
-                R: Request + ?Sized,
-                SM: Clone,
-            {
-                self.to_query($method, uri, request)
-            }
-        }
-    )*};
-}
-
-impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T> {
-    /// Creates a `Builder` that signs requests using the specified client credentials
-    /// and signature method.
-    pub fn new(client: Credentials<C>, signature_method: SM) -> Self {
-        Builder {
-            signature_method,
-            client,
-            token: None,
-            options: auth::Options::new(),
+            self.authorize(method, uri, &request::Merge::new(request, extra))
         }
     }
 
-    /// Creates a `Builder` that uses the token credentials from `token`.
-    pub fn with_token(token: Token<C, T>, signature_method: SM) -> Self {
-        let mut ret = Builder::new(token.client, signature_method);
-        ret.token(token.token);
-        ret
-    }
-
-    /// Sets/unsets the token credentials pair to sign requests with.
-    pub fn token(&mut self, token: impl Into<Option<Credentials<T>>>) -> &mut Self {
-        self.token = token.into();
-        self
-    }
-
-    /// Sets/unsets the `oauth_callback` URI.
-    pub fn callback(&mut self, callback: impl Into<Option<&'a str>>) -> &mut Self {
-        self.options.callback(callback);
-        self
-    }
-
-    /// Sets/unsets the `oauth_verifier` value.
-    pub fn verifier(&mut self, verifier: impl Into<Option<&'a str>>) -> &mut Self {
-        self.options.verifier(verifier);
-        self
-    }
-
-    /// Sets/unsets the `oauth_nonce` value.
-    ///
-    /// By default, `Builder` generates a random nonce for each request.
-    /// This method overrides that behavior and forces the `Builder` to use the specified nonce.
-    ///
-    /// This method is for debugging/testing purpose only and should not be used in production.
-    pub fn nonce(&mut self, nonce: impl Into<Option<&'a str>>) -> &mut Self {
-        self.options.nonce(nonce);
-        self
-    }
-
-    /// Sets/unsets the `oauth_timestamp` value.
-    ///
-    /// By default, `Builder` uses the timestamp of the time when `authorize`-like method is called.
-    /// This method overrides that behavior and forces the `Builder` to use the specified timestamp.
-    ///
-    /// This method is for debugging/testing purpose only and should not be used in production.
-    pub fn timestamp(&mut self, timestamp: impl Into<Option<NonZeroU64>>) -> &mut Self {
-        self.options.timestamp(timestamp);
-        self
-    }
-
-    /// Sets whether to include the `oauth_version` value in requests.
-    pub fn version(&mut self, version: bool) -> &mut Self {
-        self.options.version(version);
-        self
-    }
-
-    builder_authorize_shorthand! {
-        get("GET");
-        put("PUT");
-        post("POST");
-        delete("DELETE");
-        options("OPTIONS");
-        head("HEAD");
-        connect("CONNECT");
-        patch("PATCH");
-        trace("TRACE");
-    }
-
-    builder_to_form_shorthand! {
-        put_form("PUT");
-        post_form("POST");
-        options_form("OPTIONS");
-        patch_form("PATCH");
-    }
-
-    builder_to_query_shorthand! {
-        get_query("GET");
-        put_query("PUT");
-        post_query("POST");
-        delete_query("DELETE");
-        options_query("OPTIONS");
-        head_query("HEAD");
-        connect_query("CONNECT");
-        patch_query("PATCH");
-        trace_query("TRACE");
-    }
-
     doc_auto_cfg! {
-        /// Authorizes a request to `uri` with a custom HTTP request method,
-        /// returning an HTTP `Authorization` header value.
+        /// Authorizes a request to `uri` with the given `signature_method` and `options` in place
+        /// of those of the `Builder`, returning an HTTP `Authorization` header value.
+        ///
+        /// This is useful for overriding the options (e.g. the nonce or the callback URI) of
+        /// a single request with a `Builder` shared across threads, whose `&mut self` option
+        /// setters cannot be called, or for choosing the signature method at the call site, e.g.
+        /// after negotiating with the server. Pass [`get_options`](Self::get_options) to keep
+        /// the options of the `Builder`. The credentials of the `Builder` are used as usual.
+        ///
+        /// The query parameters of `uri`, if any, are included in the signature.
         ///
-        /// `uri` must not contain a query part, which would result in a wrong signature.
+        /// ## Example
+        ///
+        /// Sign a request with each of the signature methods in order of preference, and try
+        /// the headers in sequence until the server accepts one. Use `either::Either` (with
+        /// the `either` feature) to mix signature methods of different types:
+        ///
+        #[cfg_attr(
+            all(feature = "either", feature = "hmac-sha1", feature = "hmac-sha256"),
+            doc = " ```"
+        )]
+        #[cfg_attr(
+            not(all(feature = "either", feature = "hmac-sha1", feature = "hmac-sha256")),
+            doc = " ```ignore"
+        )]
+        /// # extern crate oauth1_request as oauth;
+        /// #
+        /// use either::Either::{Left, Right};
+        /// use oauth::signature_method::hmac_sha256::HmacSha256;
+        ///
+        /// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+        /// let builder = oauth::Builder::<_, _>::new(client, oauth::PLAINTEXT);
+        ///
+        /// let uri = "https://example.com/api/items";
+        /// let options = builder.get_options();
+        /// let headers: Vec<String> = [Left(HmacSha256::new()), Right(oauth::HMAC_SHA1)]
+        ///     .iter()
+        ///     .map(|&method| builder.build_with(method, options, "GET", uri, &()))
+        ///     .collect();
+        /// # let _ = headers;
+        /// ```
         #[cfg(feature = "alloc")]
-        pub fn authorize<U, R>(&self, method: &str, uri: U, request: &R) -> String
+        pub fn build_with<M, U, R>(
+            &self,
+            signature_method: M,
+            options: &auth::Options<'_>,
+            method: &str,
+            uri: U,
+            request: &R,
+        ) -> String
         where
+            M: SignatureMethod,
             U: Display,
             R: Request + ?Sized,
-            SM: Clone,
         {
             let serializer = serializer::auth::Authorizer::authorization(
                 method,
                 uri,
                 self.client.as_ref(),
                 self.token.as_ref().map(Credentials::as_ref),
-                &self.options,
-                self.signature_method.clone(),
+                options,
+                signature_method,
             );
 
-            request.serialize(serializer)
+            serialize_request!(self, request, serializer)
+        }
+    }
+
+    doc_auto_cfg! {
+        /// Authorizes a `GET` request to `service_provider_uri` for [OAuth Echo][echo], returning
+        /// the name and value of the `X-Verify-Credentials-Authorization` header.
+        ///
+        /// The header lets a third-party service (the delegator) verify the identity of the user by
+        /// sending the request to the service provider on their behalf. Send the header along with
+        /// an `X-Auth-Service-Provider` header containing `service_provider_uri` to the delegator.
+        ///
+        /// [echo]: https://developer.twitter.com/en/docs/authentication/oauth-echo
+        #[cfg(feature = "alloc")]
+        pub fn build_echo<U>(&self, service_provider_uri: U) -> (&'static str, String)
+        where
+            U: Display,
+            SM: Clone,
+        {
+            let authorization = self.authorize("GET", service_provider_uri, &());
+            ("X-Verify-Credentials-Authorization", authorization)
         }
+    }
 
+    doc_auto_cfg! {
         /// Authorizes a request to `uri` with a custom HTTP request method, writing the OAuth protocol
         /// parameters to an `x-www-form-urlencoded` string along with the other request parameters.
         ///
-        /// `uri` must not contain a query part, which would result in a wrong signature.
+        /// The query parameters of `uri`, if any, are included in the signature.
         #[cfg(feature = "alloc")]
         pub fn to_form<U, R>(&self, method: &str, uri: U, request: &R) -> String
         where
@@ -448,14 +1387,17 @@ impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T
                 self.signature_method.clone(),
             );
 
-            request.serialize(serializer)
+            serialize_request!(self, request, serializer)
         }
     }
 
     /// Authorizes a request to `uri` with a custom HTTP request method, appending the OAuth
     /// protocol parameters to `uri` along with the other request parameters.
     ///
-    /// `uri` must not contain a query part, which would result in a wrong signature.
+    /// The query parameters of `uri`, if any, are included in the signature.
+    /// Without the `alloc` feature, `uri` must not contain a query part, which would result
+    /// in a wrong signature. `uri` must not contain a fragment part either, and this panics in
+    /// debug builds otherwise.
     pub fn to_query<W, R>(&self, method: &str, uri: W, request: &R) -> W
     where
         W: Display + Write,
@@ -471,7 +1413,7 @@ impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T
             self.signature_method.clone(),
         );
 
-        request.serialize(serializer)
+        serialize_request!(self, request, serializer)
     }
 
     /// Same as `authorize` except that this writes the resulting `Authorization` header value
@@ -493,7 +1435,91 @@ impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T
             self.signature_method.clone(),
         );
 
-        request.serialize(serializer)
+        serialize_request!(self, request, serializer)
+    }
+
+    /// Writes the `Authorization` header value for the request into `writer`.
+    ///
+    /// Unlike `authorize_with_buf`, this takes the writer by reference and returns the error of
+    /// the writer, if any, instead of panicking. This is useful for streaming the header value
+    /// into a sink like a log formatter without allocating a `String`.
+    ///
+    /// If `writer` returns an error, nothing is written into it afterwards.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(all(feature = "std", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "std", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// use std::fmt::Write;
+    ///
+    /// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+    /// let builder = oauth::Builder::<_, _>::new(client, oauth::HMAC_SHA1);
+    ///
+    /// let mut log = String::new();
+    /// write!(log, "Authorization: ").unwrap();
+    /// builder
+    ///     .write_header(&mut log, "GET", "https://example.com/api/foo", &())
+    ///     .unwrap();
+    /// assert!(log.starts_with("Authorization: OAuth oauth_consumer_key=\"consumer_key\","));
+    /// ```
+    pub fn write_header<W, U, R>(
+        &self,
+        writer: &mut W,
+        method: &str,
+        uri: U,
+        request: &R,
+    ) -> core::fmt::Result
+    where
+        W: Write + ?Sized,
+        U: Display,
+        R: Request + ?Sized,
+        SM: Clone,
+    {
+        let writer = util::CatchError::new(writer);
+        self.authorize_with_buf(writer, method, uri, request)
+            .into_result()
+    }
+
+    doc_auto_cfg! {
+        /// Same as `write_header` except that this writes into an `io::Write` value.
+        ///
+        /// If `writer` returns an error, nothing is written into it afterwards.
+        ///
+        /// ## Example
+        ///
+        #[cfg_attr(all(feature = "std", feature = "hmac-sha1"), doc = " ```")]
+        #[cfg_attr(not(all(feature = "std", feature = "hmac-sha1")), doc = " ```ignore")]
+        /// # extern crate oauth1_request as oauth;
+        /// #
+        /// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+        /// let builder = oauth::Builder::<_, _>::new(client, oauth::HMAC_SHA1);
+        ///
+        /// let mut buf = [0_u8; 16];
+        /// let result = builder.write_header_io(&mut &mut buf[..], "GET", "https://example.com/", &());
+        /// assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::WriteZero);
+        /// assert_eq!(&buf, b"OAuth oauth_cons");
+        /// ```
+        #[cfg(feature = "std")]
+        pub fn write_header_io<W, U, R>(
+            &self,
+            writer: &mut W,
+            method: &str,
+            uri: U,
+            request: &R,
+        ) -> std::io::Result<()>
+        where
+            W: std::io::Write + ?Sized,
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            let writer = util::IoWrite::new(writer);
+            self.authorize_with_buf(writer, method, uri, request)
+                .into_result()
+                .map(drop)
+        }
     }
 
     doc_auto_cfg! {
@@ -517,7 +1543,7 @@ impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T
                 self.signature_method.clone(),
             );
 
-            request.serialize(serializer)
+            serialize_request!(self, request, serializer)
         }
 
         /// Authorizes a request and consumes `self`, returning an HTTP `Authorization` header value.
@@ -542,7 +1568,7 @@ impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T
                 self.signature_method,
             );
 
-            request.serialize(serializer)
+            serialize_request!(self, request, serializer)
         }
 
         /// Authorizes a request and consumes `self`, writing the OAuth protocol parameters to
@@ -568,7 +1594,7 @@ impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T
                 self.signature_method,
             );
 
-            request.serialize(serializer)
+            serialize_request!(self, request, serializer)
         }
     }
 
@@ -594,7 +1620,7 @@ impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T
             self.signature_method,
         );
 
-        request.serialize(serializer)
+        serialize_request!(self, request, serializer)
     }
 
     /// Same as `into_authorization` except that this writes the resulting `Authorization` header
@@ -622,7 +1648,7 @@ impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T
             self.signature_method,
         );
 
-        request.serialize(serializer)
+        serialize_request!(self, request, serializer)
     }
 
     /// Same as `into_form` except that this writes the resulting `x-www-form-urlencoded` string
@@ -643,7 +1669,75 @@ impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T
             self.signature_method,
         );
 
-        request.serialize(serializer)
+        serialize_request!(self, request, serializer)
+    }
+}
+
+impl<'a, SM, C, T> Debug for Builder<'a, SM, C, T>
+where
+    SM: SignatureMethod + Clone,
+    C: AsRef<str>,
+    T: AsRef<str>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Hidden;
+        impl Debug for Hidden {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("<hidden>")
+            }
+        }
+
+        // Shows the first few characters of a token identifier, or nothing of a short one.
+        struct Prefix<'a>(&'a str);
+        impl<'a> Debug for Prefix<'a> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                const SHOWN: usize = 4;
+                if self.0.chars().count() <= 2 * SHOWN {
+                    return f.write_str("<hidden>");
+                }
+                let end = self
+                    .0
+                    .char_indices()
+                    .nth(SHOWN)
+                    .map_or(self.0.len(), |(i, _)| i);
+                write!(f, "\"{}...\"", self.0[..end].escape_debug())
+            }
+        }
+
+        #[derive(Debug)]
+        struct Credentials<I> {
+            #[allow(dead_code)]
+            identifier: I,
+            #[allow(dead_code)]
+            secret: Hidden,
+        }
+
+        let mut debug = f.debug_struct("Builder");
+        debug
+            .field("signature_method", &self.signature_method.name())
+            .field(
+                "client",
+                &Credentials {
+                    identifier: self.client.identifier(),
+                    secret: Hidden,
+                },
+            )
+            .field(
+                "token",
+                &self.token.as_ref().map(|token| Credentials {
+                    identifier: Prefix(token.identifier()),
+                    secret: Hidden,
+                }),
+            )
+            .field("options", &self.options.redacted());
+        #[cfg(feature = "alloc")]
+        debug
+            .field(
+                "parameter_name_transform",
+                &self.parameter_name_transform.is_some(),
+            )
+            .field("max_parameters", &self.max_parameters);
+        debug.finish()
     }
 }
 
@@ -653,7 +1747,7 @@ macro_rules! authorize_shorthand {
         ///
         /// This returns an HTTP `Authorization` header value.
         ///
-        /// `uri` must not contain a query part, which would result in a wrong signature.
+        /// The query parameters of `uri`, if any, are included in the signature.
         #[cfg(feature = "alloc")]
         pub fn $name<U, R, C, T, SM>(
             uri: U,
@@ -690,7 +1784,7 @@ doc_auto_cfg! {
     ///
     /// This returns an HTTP `Authorization` header value.
     ///
-    /// `uri` must not contain a query part, which would result in a wrong signature.
+    /// The query parameters of `uri`, if any, are included in the signature.
     #[cfg(feature = "alloc")]
     pub fn authorize<U, R, C, T, SM>(
         method: &str,
@@ -723,6 +1817,36 @@ doc_auto_cfg! {
         inner(method, uri, request, token.as_ref(), signature_method)
     }
 
+    /// Authorizes a request to `url`, appending the OAuth protocol parameters to the query part of
+    /// `url` along with the other request parameters.
+    ///
+    /// Unlike `Builder::to_query`, this uses the [`url`] crate's API to modify `url`, so existing
+    /// query and fragment parts of `url` are preserved. The existing query parameters are included
+    /// in the signature while the fragment is excluded from it.
+    ///
+    /// [`url`]: https://docs.rs/url/2
+    #[cfg(feature = "url")]
+    pub fn append_to_url<SM, C, T, R>(
+        url: &mut url2::Url,
+        builder: &Builder<'_, SM, C, T>,
+        method: &str,
+        request: &R,
+    ) where
+        SM: SignatureMethod + Clone,
+        C: AsRef<str>,
+        T: AsRef<str>,
+        R: Request + ?Sized,
+    {
+        // The fragment is not a part of the base string URI.
+        let uri = &url[..url2::Position::AfterQuery];
+        let form = builder.to_form(method, uri, request);
+        url.query_pairs_mut()
+            .extend_pairs(url2::form_urlencoded::parse(form.as_bytes()));
+    }
+
+}
+
+doc_auto_cfg! {
     /// Serializes a `Request` to an `x-www-form-urlencoded` string.
     #[cfg(feature = "alloc")]
     pub fn to_form<R>(request: &R) -> String
@@ -736,11 +1860,196 @@ doc_auto_cfg! {
     ///
     /// This function naively concatenates a query string to `uri` and if `uri` already has
     /// a query part, it will have a duplicate query part like `?foo=bar?baz=qux`.
+    ///
+    /// The fragment part of `uri`, if any, is removed.
     #[cfg(feature = "alloc")]
-    pub fn to_query<R>(uri: String, request: &R) -> String
+    pub fn to_query<R>(mut uri: String, request: &R) -> String
     where
         R: Request + ?Sized,
     {
+        if let Some(i) = uri.find('#') {
+            uri.truncate(i);
+        }
         request.serialize(serializer::Urlencoder::query(uri))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use super::*;
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn build_with_pairs_trim_values() {
+        use alloc::borrow::ToOwned;
+
+        let client = Credentials::new("ck", "cs");
+        let mut builder = Builder::<_, &str>::new(client, PLAINTEXT);
+        builder
+            .nonce("nonce")
+            .timestamp(NonZeroU64::new(1))
+            .trim_values(true);
+
+        let uri = "https://example.com/api/statuses/update";
+        let request = request! { "lang" => " en", "status" => " Hello, world! " };
+        let (authorization, pairs) = builder.build_with_pairs("POST", uri, &request);
+        assert_eq!(authorization, builder.post(uri, &request));
+        assert_eq!(
+            pairs,
+            [
+                ("lang".to_owned(), "en".to_owned()),
+                ("status".to_owned(), "Hello, world!".to_owned()),
+            ],
+        );
+
+        let trimmed = request! { "lang" => "en", "status" => "Hello, world!" };
+        builder.trim_values(false);
+        assert_eq!(authorization, builder.post(uri, &trimmed));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn build_with_extra() {
+        let client = Credentials::new("ck", "cs");
+        let mut builder = Builder::<_, &str>::new(client, PLAINTEXT);
+        builder.nonce("nonce").timestamp(NonZeroU64::new(1));
+
+        let uri = "https://example.com/api/search";
+        let expected = builder.get(uri, &request! { "count" => 20, "page" => 2, "q" => "rust" });
+        let extra = ParameterList::new([("page", 2), ("count", 20)]);
+        assert_eq!(
+            builder.build_with_extra("GET", uri, &request! { "q" => "rust" }, &extra),
+            expected,
+        );
+        assert_eq!(
+            builder.build_with_extra("GET", uri, &extra, &request! { "q" => "rust" }),
+            expected,
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn build_with() {
+        let client = Credentials::new("ck", "cs");
+        let mut builder = Builder::<_, &str>::new(client, PLAINTEXT);
+        builder.nonce("nonce").timestamp(NonZeroU64::new(1));
+        let uri = "https://example.com/oauth/request_token";
+
+        assert_eq!(
+            builder.build_with(PLAINTEXT, builder.get_options(), "POST", uri, &()),
+            builder.post(uri, &()),
+        );
+
+        let mut options = builder.get_options().clone();
+        options.callback("https://client.example.net/cb");
+        let authorization = builder.build_with(PLAINTEXT, &options, "POST", uri, &());
+        assert!(authorization.contains("oauth_callback="));
+        assert!(!builder.post(uri, &()).contains("oauth_callback="));
+
+        #[cfg(feature = "hmac-sha1")]
+        {
+            let authorization =
+                builder.build_with(HMAC_SHA1, builder.get_options(), "GET", uri, &());
+            assert!(authorization.contains("oauth_signature_method=\"HMAC-SHA1\""));
+        }
+    }
+
+    #[cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+    fn hmac_sha1_builder() -> Builder<'static, HmacSha1, &'static str> {
+        let client = Credentials::new("ck", "cs");
+        let mut builder = Builder::new(client, HMAC_SHA1);
+        builder.nonce("nonce").timestamp(NonZeroU64::new(1));
+        builder
+    }
+
+    #[cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+    #[test]
+    fn trim_values() {
+        let mut builder = hmac_sha1_builder();
+        let uri = "https://example.com/api/update";
+        let padded = request! { "status" => "  Hello\n" };
+        let expected = builder.post_form(uri, &request! { "status" => "Hello" });
+        assert_ne!(builder.post_form(uri, &padded), expected);
+
+        builder.trim_values(true);
+        assert_eq!(builder.post_form(uri, &padded), expected);
+    }
+
+    #[cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+    #[test]
+    fn rfc_strict() {
+        let mut builder = hmac_sha1_builder();
+        let expected = builder.get("https://example.com/api/foo", &());
+        assert_ne!(
+            builder.get("https://example.com:443/api/foo", &()),
+            expected
+        );
+
+        builder.rfc_strict(true).keep_query_in_uri(true);
+        assert_eq!(
+            builder.get("https://example.com:443/api/foo", &()),
+            expected
+        );
+        assert_eq!(
+            builder.get("https://example.com/api/foo?q=1", &()),
+            builder.get("https://example.com/api/foo", &request! { "q" => 1 }),
+        );
+    }
+
+    #[cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+    #[test]
+    fn parameter_name_transform() {
+        let mut builder = hmac_sha1_builder();
+        let uri = "https://example.com/api/foo";
+        let expected = builder.post_form(uri, &request! { "LANG" => "en", "Q" => "bar" });
+
+        builder.parameter_name_transform(|key| Cow::Owned(key.to_uppercase()));
+        let form = builder.post_form(uri, &request! { "lang" => "en", "q" => "bar" });
+        assert!(form.starts_with("LANG=en&Q=bar&oauth_consumer_key="));
+        assert_eq!(form, expected);
+    }
+
+    #[cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+    #[test]
+    fn authorize_methods() {
+        let builder = hmac_sha1_builder();
+        let uri = "https://example.com/api/foo";
+        let headers = builder.authorize_methods(&["GET", "HEAD"], uri, &());
+        assert_eq!(headers[0], ("GET".into(), builder.get(uri, &())));
+        assert_eq!(headers[1], ("HEAD".into(), builder.head(uri, &())));
+        assert_ne!(headers[0].1, headers[1].1);
+    }
+
+    #[cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+    #[test]
+    fn build_echo() {
+        let mut builder = hmac_sha1_builder();
+        builder.token(Credentials::new("tk", "ts"));
+        let provider = "https://api.twitter.com/1.1/account/verify_credentials.json";
+        let (name, value) = builder.build_echo(provider);
+        assert_eq!(name, "X-Verify-Credentials-Authorization");
+        assert_eq!(value, builder.get(provider, &()));
+    }
+
+    #[cfg(all(feature = "url", feature = "hmac-sha1"))]
+    #[test]
+    fn append_to_url() {
+        use alloc::borrow::ToOwned;
+
+        let builder = hmac_sha1_builder();
+        let mut url = url2::Url::parse("https://example.com/api/search?q=foo#results").unwrap();
+        let request = request! { "lang" => "en" };
+        super::append_to_url(&mut url, &builder, "GET", &request);
+
+        assert_eq!(url.fragment(), Some("results"));
+        let uri = "https://example.com/api/search?q=foo".to_owned();
+        assert_eq!(
+            url.as_str(),
+            builder.to_query("GET", uri, &request) + "#results",
+        );
+        assert!(url
+            .as_str()
+            .starts_with("https://example.com/api/search?q=foo&lang=en&oauth_consumer_key=ck&"));
+    }
+}
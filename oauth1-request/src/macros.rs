@@ -0,0 +1,93 @@
+//! Macros for defining the methods of `Builder` and the types wrapping it.
+
+// Serializes `$request` with `$serializer`, applying the parameter name transform of `$builder`.
+macro_rules! serialize_request {
+    ($builder:expr, $request:expr, $serializer:expr) => {{
+        #[cfg(feature = "alloc")]
+        let ret = match $builder.parameter_name_transform {
+            Some(transform) => $crate::request::merge::TransformKeys::new($request, transform)
+                .serialize($serializer),
+            None => $request.serialize($serializer),
+        };
+        #[cfg(not(feature = "alloc"))]
+        let ret = $request.serialize($serializer);
+        ret
+    }};
+}
+
+macro_rules! builder_authorize_shorthand {
+    ($($name:ident($method:expr);)*) => {doc_auto_cfg! {$(
+        #[doc = concat!("Authorizes a `", $method, "` request to `uri`,")]
+        /// returning an HTTP `Authorization` header value.
+        ///
+        /// The query parameters of `uri`, if any, are included in the signature.
+        #[cfg(feature = "alloc")]
+        pub fn $name<U, R>(&self, uri: U, request: &R) -> String
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            self.authorize($method, uri, request)
+        }
+    )*}};
+}
+
+macro_rules! builder_header_value_shorthand {
+    ($($name:ident($method:expr);)*) => {doc_auto_cfg! {$(
+        #[doc = concat!("Authorizes a `", $method, "` request to `uri`,")]
+        /// returning an HTTP `Authorization` header value as an
+        /// [`http::HeaderValue`][http1::HeaderValue].
+        ///
+        /// See [`authorize_header_value`](Self::authorize_header_value) for the details.
+        #[cfg(feature = "http")]
+        pub fn $name<U, R>(&self, uri: U, request: &R) -> http1::HeaderValue
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            self.authorize_header_value($method, uri, request)
+        }
+    )*}};
+}
+
+macro_rules! builder_to_form_shorthand {
+    ($($name:ident($method:expr);)*) => {doc_auto_cfg! {$(
+        #[doc = concat!("Authorizes a `", $method, "` request to `uri`,")]
+        /// writing the OAuth protocol parameters to an `x-www-form-urlencoded` string
+        /// along with the other request parameters.
+        ///
+        /// The query parameters of `uri`, if any, are included in the signature.
+        #[cfg(feature = "alloc")]
+        pub fn $name<U, R>(&self, uri: U, request: &R) -> String
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            self.to_form($method, uri, request)
+        }
+    )*}};
+}
+
+macro_rules! builder_to_query_shorthand {
+    ($($name:ident($method:expr);)*) => {$(
+        doc_coerce_expr! {
+            #[doc = concat!("Authorizes a `", $method, "` request to `uri`, appending")]
+            /// the OAuth protocol parameters to `uri` along with the other request parameters.
+            ///
+            /// The query parameters of `uri`, if any, are included in the signature.
+            /// Without the `alloc` feature, `uri` must not contain a query part, which would result
+            /// in a wrong signature.
+            pub fn $name<W, R>(&self, uri: W, request: &R) -> W
+            where
+                W: Display + Write,
+                R: Request + ?Sized,
+                SM: Clone,
+            {
+                self.to_query($method, uri, request)
+            }
+        }
+    )*};
+}
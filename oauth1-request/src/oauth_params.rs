@@ -0,0 +1,372 @@
+//! The OAuth protocol parameters of a signed request.
+
+use alloc::string::{String, ToString};
+use core::fmt::{Display, Write};
+
+use percent_encoding::percent_decode_str;
+
+use crate::request::Request;
+use crate::serializer::auth::Options;
+use crate::signature_method::{Sign, SignatureMethod};
+use crate::util::percent_encode;
+use crate::Builder;
+
+/// The OAuth protocol parameters of a signed request, including the computed signature,
+/// returned by [`Builder::sign_to_parts`](crate::Builder::sign_to_parts).
+///
+/// The values are not percent-encoded.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OAuthParams {
+    header: String,
+    realm: Option<String>,
+    consumer_key: String,
+    token: Option<String>,
+    nonce: Option<String>,
+    timestamp: Option<u64>,
+    signature_method: String,
+    signature: String,
+    callback: Option<String>,
+    verifier: Option<String>,
+    version: Option<String>,
+    body_hash: Option<String>,
+}
+
+/// A signature method wrapper that records the OAuth protocol parameters fed to the `Sign` of
+/// `SM`, including the resulting signature, into an `OAuthParams`.
+pub(crate) struct Collect<'a, SM> {
+    inner: SM,
+    params: &'a mut OAuthParams,
+}
+
+pub(crate) struct CollectSign<'a, S> {
+    inner: S,
+    params: &'a mut OAuthParams,
+}
+
+impl OAuthParams {
+    /// Completes the `OAuthParams` collected with `Collect` with the values that are not fed to
+    /// the `Sign`.
+    pub(crate) fn finish(&mut self, header: String, options: &Options<'_>) {
+        self.header = header;
+        self.realm = options.header_realm();
+        // The `Sign` is fed with `oauth_verifier` even if it is not written to the header.
+        if options.sends_verifier_in_body() {
+            self.verifier = None;
+        }
+    }
+
+    /// Returns the `realm` value, as written to the `Authorization` header.
+    pub fn realm(&self) -> Option<&str> {
+        self.realm.as_deref()
+    }
+
+    /// Returns the `oauth_consumer_key` value.
+    pub fn consumer_key(&self) -> &str {
+        &self.consumer_key
+    }
+
+    /// Returns the `oauth_token` value.
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// Returns the `oauth_nonce` value.
+    pub fn nonce(&self) -> Option<&str> {
+        self.nonce.as_deref()
+    }
+
+    /// Returns the `oauth_timestamp` value.
+    pub fn timestamp(&self) -> Option<u64> {
+        self.timestamp
+    }
+
+    /// Returns the `oauth_signature_method` value.
+    pub fn signature_method(&self) -> &str {
+        &self.signature_method
+    }
+
+    /// Returns the `oauth_signature` value.
+    pub fn signature(&self) -> &str {
+        &self.signature
+    }
+
+    /// Returns the `oauth_callback` value.
+    pub fn callback(&self) -> Option<&str> {
+        self.callback.as_deref()
+    }
+
+    /// Returns the `oauth_verifier` value.
+    ///
+    /// This is `None` if the `verifier_in_body` option is set, in which case the value is not
+    /// a part of the protocol parameters.
+    pub fn verifier(&self) -> Option<&str> {
+        self.verifier.as_deref()
+    }
+
+    /// Returns the `oauth_version` value.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Returns the `oauth_body_hash` value.
+    pub fn body_hash(&self) -> Option<&str> {
+        self.body_hash.as_deref()
+    }
+
+    /// Returns the parameters as an `x-www-form-urlencoded` string without a leading `?` or `&`,
+    /// in the order they are written to the `Authorization` header.
+    ///
+    /// The `realm` parameter is not included.
+    pub fn to_query(&self) -> String {
+        let timestamp = self.timestamp.map(|t| t.to_string());
+        let params = [
+            ("oauth_body_hash", self.body_hash.as_deref()),
+            ("oauth_callback", self.callback.as_deref()),
+            ("oauth_consumer_key", Some(&*self.consumer_key)),
+            ("oauth_nonce", self.nonce.as_deref()),
+            ("oauth_signature_method", Some(&*self.signature_method)),
+            ("oauth_timestamp", timestamp.as_deref()),
+            ("oauth_token", self.token.as_deref()),
+            ("oauth_verifier", self.verifier.as_deref()),
+            ("oauth_version", self.version.as_deref()),
+        ];
+
+        let mut ret = String::new();
+        for &(k, v) in params.iter() {
+            if let Some(v) = v {
+                write!(ret, "{}={}&", k, percent_encode(v)).unwrap();
+            }
+        }
+        write!(ret, "oauth_signature={}", percent_encode(&self.signature)).unwrap();
+
+        ret
+    }
+
+    /// Returns the parameters as an HTTP `Authorization` header value.
+    pub fn to_header(&self) -> &str {
+        &self.header
+    }
+}
+
+impl<'a, SM> Collect<'a, SM> {
+    pub fn new(inner: SM, params: &'a mut OAuthParams) -> Self {
+        Collect { inner, params }
+    }
+}
+
+impl<'a, SM: SignatureMethod> SignatureMethod for Collect<'a, SM> {
+    type Sign = CollectSign<'a, SM::Sign>;
+
+    fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> Self::Sign {
+        CollectSign {
+            inner: self.inner.sign_with(client_secret, token_secret),
+            params: self.params,
+        }
+    }
+}
+
+// Decodes a value percent-encoded `times` times. The values fed to a `Sign` are percent-encoded
+// twice (once for the parameter and once for the signature base string), except for
+// the signature, which is percent-encoded once.
+fn decode(value: &str, times: usize) -> String {
+    let mut ret = value.to_string();
+    for _ in 0..times {
+        ret = percent_decode_str(&ret).decode_utf8_lossy().into_owned();
+    }
+    ret
+}
+
+macro_rules! collect_parameter {
+    ($($name:ident,)*) => {$(
+        fn $name<V: Display>(&mut self, value: V) {
+            let value = value.to_string();
+            self.params.$name = decode(&value, 2).into();
+            self.inner.$name(value);
+        }
+    )*};
+}
+
+impl<'a, S: Sign> Sign for CollectSign<'a, S> {
+    type Signature = S::Signature;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.inner.get_signature_method_name()
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.inner.request_method(method);
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        self.inner.uri(uri);
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        // `oauth_body_hash` is the only protocol parameter without a dedicated method.
+        if key == "oauth_body_hash" {
+            let value = value.to_string();
+            self.params.body_hash = Some(decode(&value, 2));
+            self.inner.parameter(key, value);
+        } else {
+            self.inner.parameter(key, value);
+        }
+    }
+
+    fn delimiter(&mut self) {
+        self.inner.delimiter();
+    }
+
+    fn end(self) -> S::Signature {
+        let signature = self.inner.end();
+        self.params.signature = decode(&signature.to_string(), 1);
+        signature
+    }
+
+    collect_parameter! { callback, consumer_key, nonce, }
+
+    fn use_nonce(&self) -> bool {
+        self.inner.use_nonce()
+    }
+
+    fn signature_method(&mut self) {
+        self.params.signature_method = self.inner.get_signature_method_name().to_string();
+        self.inner.signature_method();
+    }
+
+    fn timestamp(&mut self, value: u64) {
+        self.params.timestamp = Some(value);
+        self.inner.timestamp(value);
+    }
+
+    fn use_timestamp(&self) -> bool {
+        self.inner.use_timestamp()
+    }
+
+    collect_parameter! { token, verifier, }
+
+    fn version(&mut self) {
+        self.params.version = Some("1.0".to_string());
+        self.inner.version();
+    }
+
+    fn debug_base_string(&self) -> Option<&str> {
+        self.inner.debug_base_string()
+    }
+}
+
+impl<'a, SM: SignatureMethod, C: AsRef<str>, T: AsRef<str>> Builder<'a, SM, C, T> {
+    doc_auto_cfg! {
+        /// Signs a request and returns the OAuth protocol parameters, including the computed
+        /// signature, as separate values.
+        ///
+        /// This is useful for sending the parameters somewhere other than the `Authorization`
+        /// header, like the query part of the request URI ([`OAuthParams::to_query`]) or a multipart
+        /// form.
+        #[cfg(feature = "alloc")]
+        pub fn sign_to_parts<U, R>(&self, method: &str, uri: U, request: &R) -> OAuthParams
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            let mut params = OAuthParams::default();
+            let collect = Collect::new(self.signature_method.clone(), &mut params);
+            let header = self.build_with(collect, &self.options, method, uri, request);
+            params.finish(header, &self.options);
+            params
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::NonZeroU64;
+
+    use oauth_credentials::Credentials;
+
+    use crate::request;
+    use crate::signature_method::PLAINTEXT;
+
+    use super::*;
+
+    #[test]
+    fn sign_to_parts() {
+        let client = Credentials::new("c k", "cs");
+        let token = Credentials::new("t&k", "ts");
+        let mut builder = Builder::new(client, PLAINTEXT);
+        builder
+            .token(token)
+            .realm("a b")
+            .encode_realm(true)
+            .verifier("v%")
+            .nonce("n=1")
+            .timestamp(NonZeroU64::new(1))
+            .version(true);
+
+        let uri = "https://example.com/api/items";
+        let params = builder.sign_to_parts("POST", uri, &request! { "q" => "rust" });
+        assert_eq!(
+            params.to_header(),
+            builder.post(uri, &request! { "q" => "rust" })
+        );
+        assert_eq!(params.realm(), Some("a%20b"));
+        assert_eq!(params.consumer_key(), "c k");
+        assert_eq!(params.token(), Some("t&k"));
+        assert_eq!(params.callback(), None);
+        assert_eq!(params.verifier(), Some("v%"));
+        assert_eq!(params.nonce(), Some("n=1"));
+        assert_eq!(params.timestamp(), Some(1));
+        assert_eq!(params.signature_method(), "PLAINTEXT");
+        assert_eq!(params.version(), Some("1.0"));
+        assert_eq!(params.signature(), "cs&ts");
+        assert_eq!(params.body_hash(), None);
+
+        builder.verifier_in_body(true);
+        let params = builder.sign_to_parts("POST", uri, &());
+        assert_eq!(params.verifier(), None);
+        assert!(!params.to_query().contains("oauth_verifier"));
+
+        let mut options = builder.get_options().clone();
+        options
+            .verifier(None)
+            .callback("https://example.net/cb?x=1")
+            .body_hash("a+b=");
+        let builder = Builder::<_, &str>::with_options(client, PLAINTEXT, options);
+        let params = builder.sign_to_parts("POST", uri, &());
+        assert_eq!(params.callback(), Some("https://example.net/cb?x=1"));
+        assert_eq!(params.body_hash(), Some("a+b="));
+        assert!(params
+            .to_query()
+            .starts_with("oauth_body_hash=a%2Bb%3D&oauth_callback=https%3A%2F%2F"));
+    }
+
+    #[cfg(feature = "hmac-sha1")]
+    #[test]
+    fn sign_to_parts_signature() {
+        use alloc::format;
+
+        use crate::signature_method::HMAC_SHA1;
+
+        let client = Credentials::new("ck", "cs");
+        let mut builder = Builder::<_, &str>::new(client, HMAC_SHA1);
+        builder.nonce("nonce").timestamp(NonZeroU64::new(1));
+
+        let uri = "https://example.com/api/items";
+        let params = builder.sign_to_parts("GET", uri, &());
+        assert_eq!(params.to_header(), builder.get(uri, &()));
+        assert_eq!(params.signature().len(), 28);
+
+        let signature = percent_encode(params.signature()).to_string();
+        assert!(params
+            .to_header()
+            .ends_with(&format!("oauth_signature=\"{}\"", signature)));
+        assert_eq!(
+            params.to_query(),
+            format!(
+                "oauth_consumer_key=ck&oauth_nonce=nonce&oauth_signature_method=HMAC-SHA1&\
+                 oauth_timestamp=1&oauth_signature={}",
+                signature,
+            ),
+        );
+    }
+}
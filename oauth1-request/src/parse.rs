@@ -0,0 +1,300 @@
+//! Parsing of OAuth `Authorization` header values ([RFC 5849 section 3.5.1.][rfc]).
+//!
+//! [rfc]: https://tools.ietf.org/html/rfc5849#section-3.5.1
+//!
+//! This is the inverse of what [`Authorizer::authorization`] produces, useful for inspecting or
+//! verifying a request that already carries an `Authorization` header.
+//!
+//! [`Authorizer::authorization`]: crate::serializer::auth::Authorizer::authorization
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use core::fmt::{self, Display, Formatter};
+use core::num::NonZeroU64;
+
+use percent_encoding::percent_decode_str;
+
+use crate::serializer::auth::Options;
+
+/// The protocol parameters of an `Authorization` header value.
+///
+/// The values are percent-decoded, except for `realm`, which is kept as is.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParsedAuthorization {
+    /// The `realm` parameter.
+    pub realm: Option<String>,
+    /// The `oauth_consumer_key` parameter.
+    pub consumer_key: String,
+    /// The `oauth_token` parameter.
+    pub token: Option<String>,
+    /// The `oauth_nonce` parameter.
+    pub nonce: Option<String>,
+    /// The `oauth_timestamp` parameter.
+    pub timestamp: Option<u64>,
+    /// The `oauth_signature_method` parameter.
+    pub signature_method: String,
+    /// The `oauth_signature` parameter.
+    pub signature: String,
+    /// The `oauth_callback` parameter.
+    pub callback: Option<String>,
+    /// The `oauth_verifier` parameter.
+    pub verifier: Option<String>,
+    /// The `oauth_version` parameter.
+    pub version: Option<String>,
+    /// The other parameters (e.g. `oauth_body_hash`), keyed by their names.
+    pub other: BTreeMap<String, String>,
+}
+
+/// An error while parsing an `Authorization` header value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The authentication scheme is not `OAuth`.
+    NotOAuth,
+    /// The header value is not a comma-separated list of `key="value"` pairs.
+    Malformed,
+    /// A parameter is not percent-encoded correctly.
+    InvalidEncoding,
+    /// The `oauth_timestamp` value is not a positive integer.
+    InvalidTimestamp,
+    /// A required parameter is missing.
+    MissingParameter(&'static str),
+    /// A parameter appears more than once.
+    DuplicateParameter(String),
+}
+
+/// Parses an `Authorization` header value with the `OAuth` authentication scheme.
+///
+/// The parameters may appear in any order and may be separated by whitespace in addition to
+/// the commas. The `oauth_consumer_key`, `oauth_signature_method` and `oauth_signature`
+/// parameters are required.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::parse::parse_authorization_header;
+///
+/// let parsed = parse_authorization_header(
+///     "OAuth realm=\"Example\", \
+///      oauth_signature=\"wOJIO9A2W5mFwDgiDvZbTSMK%2FPY%3D\", \
+///      oauth_consumer_key=\"9djdj82h48djs9d2\", \
+///      oauth_token=\"kkk9d7dh3k39sjv7\", \
+///      oauth_signature_method=\"HMAC-SHA1\", \
+///      oauth_timestamp=\"137131201\", \
+///      oauth_nonce=\"7d8f3e4a\", \
+///      oauth_body_hash=\"2jmj7l5rSw0yVb%2FvlWAYkK%2FYBwk%3D\"",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(parsed.realm.as_deref(), Some("Example"));
+/// assert_eq!(parsed.consumer_key, "9djdj82h48djs9d2");
+/// assert_eq!(parsed.token.as_deref(), Some("kkk9d7dh3k39sjv7"));
+/// assert_eq!(parsed.signature, "wOJIO9A2W5mFwDgiDvZbTSMK/PY=");
+/// assert_eq!(parsed.timestamp, Some(137131201));
+/// assert_eq!(parsed.other["oauth_body_hash"], "2jmj7l5rSw0yVb/vlWAYkK/YBwk=");
+/// ```
+pub fn parse_authorization_header(header: &str) -> Result<ParsedAuthorization, ParseError> {
+    let header = header.trim();
+    let params = match header.get(..6) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("OAuth ") => &header[6..],
+        _ if header.eq_ignore_ascii_case("OAuth") => "",
+        _ => return Err(ParseError::NotOAuth),
+    };
+
+    let mut ret = ParsedAuthorization::default();
+    let mut consumer_key = None;
+    let mut signature_method = None;
+    let mut signature = None;
+
+    let mut rest = params.trim_start();
+    while !rest.is_empty() {
+        let eq = rest.find('=').ok_or(ParseError::Malformed)?;
+        let key = rest[..eq].trim_end();
+        let value = rest[eq + 1..].trim_start();
+        let value = value.strip_prefix('"').ok_or(ParseError::Malformed)?;
+        let end = value.find('"').ok_or(ParseError::Malformed)?;
+        rest = value[end + 1..].trim_start();
+        let value = &value[..end];
+
+        if !rest.is_empty() {
+            rest = rest.strip_prefix(',').ok_or(ParseError::Malformed)?;
+            rest = rest.trim_start();
+        }
+        if key.is_empty() {
+            return Err(ParseError::Malformed);
+        }
+
+        if key == "realm" {
+            set(&mut ret.realm, key, value.to_string())?;
+            continue;
+        }
+
+        let value = decode(value)?;
+        match key {
+            "oauth_consumer_key" => set(&mut consumer_key, key, value)?,
+            "oauth_token" => set(&mut ret.token, key, value)?,
+            "oauth_nonce" => set(&mut ret.nonce, key, value)?,
+            "oauth_timestamp" => {
+                let timestamp = value
+                    .parse::<NonZeroU64>()
+                    .map_err(|_| ParseError::InvalidTimestamp)?;
+                set(&mut ret.timestamp, key, timestamp.get())?;
+            }
+            "oauth_signature_method" => set(&mut signature_method, key, value)?,
+            "oauth_signature" => set(&mut signature, key, value)?,
+            "oauth_callback" => set(&mut ret.callback, key, value)?,
+            "oauth_verifier" => set(&mut ret.verifier, key, value)?,
+            "oauth_version" => set(&mut ret.version, key, value)?,
+            _ => {
+                let key = decode(key)?;
+                if ret.other.contains_key(&key) {
+                    return Err(ParseError::DuplicateParameter(key));
+                }
+                ret.other.insert(key, value);
+            }
+        }
+    }
+
+    ret.consumer_key = consumer_key.ok_or(ParseError::MissingParameter("oauth_consumer_key"))?;
+    ret.signature_method =
+        signature_method.ok_or(ParseError::MissingParameter("oauth_signature_method"))?;
+    ret.signature = signature.ok_or(ParseError::MissingParameter("oauth_signature"))?;
+
+    Ok(ret)
+}
+
+impl ParsedAuthorization {
+    /// Returns an `Options` with the `realm`, `oauth_callback`, `oauth_verifier`, `oauth_nonce`,
+    /// `oauth_timestamp`, `oauth_version` and `oauth_body_hash` values of the header set,
+    /// which can be used to sign the same request again.
+    pub fn options(&self) -> Options<'_> {
+        let mut ret = Options::new();
+        ret.realm(self.realm.as_deref())
+            .callback(self.callback.as_deref())
+            .verifier(self.verifier.as_deref())
+            .nonce(self.nonce.as_deref())
+            .timestamp(self.timestamp.and_then(NonZeroU64::new))
+            .version(self.version.is_some())
+            .body_hash(self.other.get("oauth_body_hash").map(String::as_str));
+        ret
+    }
+}
+
+fn set<T>(slot: &mut Option<T>, key: &str, value: T) -> Result<(), ParseError> {
+    if slot.is_some() {
+        return Err(ParseError::DuplicateParameter(key.to_string()));
+    }
+    *slot = Some(value);
+    Ok(())
+}
+
+fn decode(s: &str) -> Result<String, ParseError> {
+    let bytes = s.as_bytes();
+    for (i, _) in s.match_indices('%') {
+        let valid = bytes.len() > i + 2
+            && bytes[i + 1].is_ascii_hexdigit()
+            && bytes[i + 2].is_ascii_hexdigit();
+        if !valid {
+            return Err(ParseError::InvalidEncoding);
+        }
+    }
+    percent_decode_str(s)
+        .decode_utf8()
+        .map(|s| s.into_owned())
+        .map_err(|_| ParseError::InvalidEncoding)
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            ParseError::NotOAuth => f.write_str("authentication scheme is not `OAuth`"),
+            ParseError::Malformed => f.write_str("malformed `Authorization` header value"),
+            ParseError::InvalidEncoding => f.write_str("invalid percent-encoding in a parameter"),
+            ParseError::InvalidTimestamp => f.write_str("invalid `oauth_timestamp` value"),
+            ParseError::MissingParameter(key) => write!(f, "missing `{}` parameter", key),
+            ParseError::DuplicateParameter(ref key) => write!(f, "duplicate `{}` parameter", key),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[cfg(feature = "hmac-sha1")]
+    #[test]
+    fn roundtrip() {
+        use crate::Credentials;
+
+        let client = Credentials::new("consumer key", "consumer secret");
+        let token = Credentials::new("token", "token secret");
+        let mut builder = crate::Builder::new(client, crate::HMAC_SHA1);
+        builder
+            .token(token)
+            .realm("Photos, Inc.")
+            .callback("https://client.example.net/cb?x=1")
+            .nonce("nonce")
+            .timestamp(NonZeroU64::new(1234567890))
+            .version(true);
+        let header = builder.post("https://example.com/", &());
+
+        let parsed = parse_authorization_header(&header).unwrap();
+        assert_eq!(parsed.realm.as_deref(), Some("Photos, Inc."));
+        assert_eq!(parsed.consumer_key, "consumer key");
+        assert_eq!(parsed.token.as_deref(), Some("token"));
+        assert_eq!(parsed.signature_method, "HMAC-SHA1");
+        assert_eq!(
+            parsed.callback.as_deref(),
+            Some("https://client.example.net/cb?x=1"),
+        );
+        assert_eq!(parsed.version.as_deref(), Some("1.0"));
+        assert!(parsed.other.is_empty());
+
+        // Signing the request again with the parsed options produces the same header.
+        let mut builder = crate::Builder::new(client, crate::HMAC_SHA1);
+        builder.token(token);
+        let options = parsed.options();
+        let resigned = builder.build_with(crate::HMAC_SHA1, &options, "POST", "https://example.com/", &());
+        assert_eq!(resigned, header);
+    }
+
+    #[test]
+    fn errors() {
+        for &(header, ref expected) in &[
+            ("Basic dXNlcjpwYXNz", ParseError::NotOAuth),
+            ("OAuth oauth_consumer_key=key", ParseError::Malformed),
+            ("OAuth oauth_consumer_key=\"key", ParseError::Malformed),
+            (
+                "OAuth oauth_consumer_key=\"a\" oauth_nonce=\"b\"",
+                ParseError::Malformed,
+            ),
+            (
+                "OAuth oauth_consumer_key=\"%ZZ\"",
+                ParseError::InvalidEncoding,
+            ),
+            ("OAuth oauth_timestamp=\"0\"", ParseError::InvalidTimestamp),
+            (
+                "OAuth oauth_signature_method=\"PLAINTEXT\",oauth_signature=\"a\"",
+                ParseError::MissingParameter("oauth_consumer_key"),
+            ),
+            (
+                "OAuth oauth_nonce=\"a\",oauth_nonce=\"b\"",
+                ParseError::DuplicateParameter("oauth_nonce".to_string()),
+            ),
+        ] {
+            assert_eq!(
+                parse_authorization_header(header).as_ref(),
+                Err(expected),
+                "{}",
+                header,
+            );
+        }
+    }
+}
@@ -1,9 +1,32 @@
 //! Requests to be authorized with OAuth.
 
-pub mod parameter_list;
+doc_auto_cfg! {
+    #[cfg(feature = "alloc")]
+    pub mod encoded_query;
+    #[cfg(feature = "alloc")]
+    pub mod merge;
+    pub mod parameter_list;
+    #[cfg(feature = "alloc")]
+    pub mod prepared;
+    #[cfg(feature = "serde")]
+    pub mod serde_scalar;
+    #[cfg(feature = "alloc")]
+    pub mod spec_params;
+}
 
-pub use self::parameter_list::ParameterList;
+doc_auto_cfg! {
+    #[cfg(feature = "alloc")]
+    pub use self::encoded_query::EncodedQuery;
+    #[cfg(feature = "alloc")]
+    pub use self::merge::Merge;
+    pub use self::parameter_list::ParameterList;
+    #[cfg(feature = "alloc")]
+    pub use self::prepared::PreparedRequest;
+    #[cfg(feature = "alloc")]
+    pub use self::spec_params::SpecParams;
+}
 
+use core::borrow::Borrow;
 use core::fmt::Display;
 
 use crate::serializer::{Serializer, SerializerExt};
@@ -25,6 +48,46 @@ pub trait Request {
         S: Serializer;
 }
 
+/// Creates an ad-hoc [`Request`] from a list of key-value pairs.
+///
+/// This is like a struct literal for a `Request` without naming a type. The keys must be
+/// `&str` and the values must implement `Display`. The pairs may be written in any order and are
+/// sorted on construction without allocating.
+///
+/// The values are borrowed by the resulting `Request`, so temporary values (like the return value
+/// of `to_string()`) must be bound to a variable beforehand, unless the `Request` is used within
+/// the same expression.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// let screen_name = "tesaguri";
+/// let count = 2;
+///
+/// let request = oauth::request! {
+///     "screen_name" => screen_name,
+///     "count" => count,
+///     "include_rts" => false,
+/// };
+///
+/// let form = oauth::to_form(&request);
+/// assert_eq!(form, "count=2&include_rts=false&screen_name=tesaguri");
+/// ```
+#[macro_export]
+macro_rules! request {
+    () => {
+        ()
+    };
+    ($($key:expr => $value:expr),+ $(,)?) => {
+        $crate::request::ParameterList::new([
+            $(($key, &$value as &dyn ::core::fmt::Display)),+
+        ])
+    };
+}
+
 /// A wrapper type that implements [`Request`] with key-value pairs returned by the wrapped
 /// iterator.
 ///
@@ -112,6 +175,173 @@ impl<R: Request> Request for Option<R> {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<'a, R> Request for alloc::borrow::Cow<'a, R>
+where
+    R: Request + alloc::borrow::ToOwned + ?Sized,
+{
+    fn serialize<S>(&self, serializer: S) -> S::Output
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+/// Authorizes a request with the key-value pairs in the slice.
+///
+/// The pairs are sorted on each serialization through a `Vec` of indices, leaving the slice
+/// itself untouched.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use std::borrow::Cow;
+///
+/// let pairs = [("foo", 123), ("bar", 23), ("foo", 3)];
+///
+/// let form = oauth::to_form(&pairs[..]);
+/// assert_eq!(form, "bar=23&foo=123&foo=3");
+///
+/// let request: Cow<'_, [_]> = Cow::Borrowed(&pairs);
+/// assert_eq!(oauth::to_form(&request), form);
+/// ```
+#[cfg(feature = "alloc")]
+impl<K, V> Request for [(K, V)]
+where
+    K: Borrow<str>,
+    V: Display,
+{
+    fn serialize<S>(&self, serializer: S) -> S::Output
+    where
+        S: Serializer,
+    {
+        let mut indices: alloc::vec::Vec<usize> = (0..self.len()).collect();
+        indices.sort_unstable_by(|&i, &j| {
+            let (ref ki, ref vi) = self[i];
+            let (ref kj, ref vj) = self[j];
+            (ki.borrow(), fmt_cmp::Cmp(vi)).cmp(&(kj.borrow(), fmt_cmp::Cmp(vj)))
+        });
+
+        let pairs = indices.iter().map(|&i| {
+            let (ref k, ref v) = self[i];
+            (k.borrow(), v)
+        });
+        AssertSorted::new(pairs).serialize(serializer)
+    }
+}
+
+/// Authorizes a request with the key-value pairs in the `Vec`.
+///
+/// The pairs are sorted on each serialization, leaving the `Vec` itself untouched.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// let request = vec![("foo", 123), ("bar", 23), ("foo", 3)];
+///
+/// let form = oauth::to_form(&request);
+/// assert_eq!(form, "bar=23&foo=123&foo=3");
+/// assert_eq!(request, [("foo", 123), ("bar", 23), ("foo", 3)]);
+/// ```
+#[cfg(feature = "alloc")]
+impl<K, V> Request for alloc::vec::Vec<(K, V)>
+where
+    K: Borrow<str>,
+    V: Display,
+{
+    fn serialize<S>(&self, serializer: S) -> S::Output
+    where
+        S: Serializer,
+    {
+        self[..].serialize(serializer)
+    }
+}
+
+/// Authorizes a request with the key-value pairs in the `BTreeMap`.
+///
+/// The map is already sorted by its keys, so this does not need to sort the pairs.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use std::collections::BTreeMap;
+///
+/// let mut request = BTreeMap::new();
+/// request.insert("foo", 123);
+/// request.insert("bar", 23);
+///
+/// let form = oauth::to_form(&request);
+/// assert_eq!(form, "bar=23&foo=123");
+/// ```
+#[cfg(feature = "alloc")]
+impl<K, V> Request for alloc::collections::BTreeMap<K, V>
+where
+    K: Borrow<str> + Ord,
+    V: Display,
+{
+    fn serialize<S>(&self, serializer: S) -> S::Output
+    where
+        S: Serializer,
+    {
+        AssertSorted::new(self.iter().map(|(k, v)| (k.borrow(), v))).serialize(serializer)
+    }
+}
+
+macro_rules! array_impls {
+    ($($n:expr)*) => {$(
+        /// Authorizes a request with the key-value pairs in the array.
+        ///
+        /// The pairs are sorted on each serialization through an array of indices on the stack,
+        /// leaving the array itself untouched. Unlike the `Vec` implementation, this does not
+        /// allocate, which is useful for a small, fixed number of parameters in `no_std`
+        /// environments.
+        ///
+        /// This is implemented for arrays of length up to 32.
+        impl<K, V> Request for [(K, V); $n]
+        where
+            K: Borrow<str>,
+            V: Display,
+        {
+            fn serialize<S>(&self, serializer: S) -> S::Output
+            where
+                S: Serializer,
+            {
+                let mut indices = [0; $n];
+                for (i, index) in indices.iter_mut().enumerate() {
+                    *index = i;
+                }
+                let key = |i: usize| {
+                    let (ref k, ref v) = self[i];
+                    (k.borrow(), fmt_cmp::Cmp(v))
+                };
+                indices.sort_unstable_by(|&i, &j| key(i).cmp(&key(j)));
+                debug_assert!(indices.windows(2).all(|w| key(w[0]) <= key(w[1])));
+
+                let pairs = indices.iter().map(|&i| {
+                    let (ref k, ref v) = self[i];
+                    (k.borrow(), v)
+                });
+                AssertSorted::new(pairs).serialize(serializer)
+            }
+        }
+    )*};
+}
+
+// Implemented with a macro rather than const generics to keep supporting Rust 1.46.
+array_impls! {
+    0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32
+}
+
 impl<I, K, V> AssertSorted<I>
 where
     I: Clone + Iterator<Item = (K, V)>,
@@ -158,3 +388,17 @@ where
         serializer.end()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn array() {
+        let request = [("foo", 123), ("bar", 23), ("foo", 3)];
+        assert_eq!(crate::to_form(&request), "bar=23&foo=123&foo=3");
+        assert_eq!(request, [("foo", 123), ("bar", 23), ("foo", 3)]);
+
+        let request: [(&str, u8); 0] = [];
+        assert_eq!(crate::to_form(&request), "");
+    }
+}
@@ -0,0 +1,90 @@
+//! A [`Request`] that takes its parameters from an already percent-encoded query string.
+
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use super::Request;
+use crate::serializer::Serializer;
+use crate::util::OAuthParameter;
+
+/// A [`Request`] with the parameters of an already percent-encoded query string
+/// (e.g. the raw query part of an incoming request's URI).
+///
+/// The values are fed to the serializer with `serialize_parameter_encoded` as they are, so that
+/// the exact encoding of the original query is signed without a decode/re-encode round trip.
+/// The only exception is a `+` character, which is signed as `%20` since it stands for a space
+/// in a query string. The pairs are sorted on each serialization.
+///
+/// The keys must consist only of "unreserved characters" of RFC 3986, as required by the
+/// [`Serializer`] trait, and the values should be percent-encoded as the OAuth standard requires
+/// ([RFC 5849 section 3.6.][rfc]). Otherwise, the signature would differ from the one computed
+/// by a server that normalizes the encoding.
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.6
+///
+/// ## Example
+///
+/// ```
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::request::EncodedQuery;
+///
+/// let query = EncodedQuery::new("q=caf%C3%A9+au+lait&lang=fr");
+/// let decoded = oauth::request! { "lang" => "fr", "q" => "café au lait" };
+///
+/// assert_eq!(oauth::to_form(&query), "lang=fr&q=caf%C3%A9%20au%20lait");
+/// assert_eq!(oauth::to_form(&query), oauth::to_form(&decoded));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EncodedQuery<'a> {
+    query: &'a str,
+}
+
+impl<'a> EncodedQuery<'a> {
+    /// Creates a new `EncodedQuery` from a query string without the leading `'?'`.
+    pub fn new(query: &'a str) -> Self {
+        EncodedQuery { query }
+    }
+}
+
+impl<'a> Request for EncodedQuery<'a> {
+    fn serialize<S>(&self, mut serializer: S) -> S::Output
+    where
+        S: Serializer,
+    {
+        let mut pairs: Vec<(&str, Cow<'_, str>)> = self
+            .query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let mut kv = pair.splitn(2, '=');
+                let k = kv.next().unwrap();
+                let v = kv.next().unwrap_or("");
+                let v = if v.contains('+') {
+                    Cow::Owned(v.replace('+', "%20"))
+                } else {
+                    Cow::Borrowed(v)
+                };
+                (k, v)
+            })
+            .collect();
+        pairs.sort_unstable();
+
+        let mut next_param = OAuthParameter::default();
+
+        for (k, v) in &pairs {
+            while next_param < **k {
+                next_param.serialize(&mut serializer);
+                next_param = next_param.next();
+            }
+            serializer.serialize_parameter_encoded(k, v);
+        }
+
+        while next_param != OAuthParameter::None {
+            next_param.serialize(&mut serializer);
+            next_param = next_param.next();
+        }
+
+        serializer.end()
+    }
+}
@@ -0,0 +1,240 @@
+//! A [`Request`] that merges the parameters of two `Request`s.
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+use super::Request;
+use crate::serializer::{auth, Serializer};
+use crate::util::{percent_encode, EncodeSet, OAuthParameter, PercentEncodeWith, TrimAsciiWhitespace};
+
+/// A [`Request`] that merges the parameters of two `Request`s into a single sorted parameter set.
+///
+/// This is useful for signing a request that carries parameters in both of the query part of
+/// the URI and an `x-www-form-urlencoded` body, both of which are included in the signature
+/// ([RFC 5849 section 3.4.1.3.1.][rfc]) regardless of the HTTP request method.
+///
+/// Unlike the other `Request` implementations of the crate, this buffers the parameters of
+/// the two `Request`s on each serialization to sort them.
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1.3.1
+///
+/// ## Example
+///
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::request::Merge;
+///
+/// let query = oauth::request! { "foo" => 1, "qux" => 3 };
+/// let body = oauth::request! { "bar" => 2 };
+///
+/// let form = oauth::to_form(&Merge::new(&query, &body));
+/// assert_eq!(form, "bar=2&foo=1&qux=3");
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Merge<A, B> {
+    first: A,
+    second: B,
+}
+
+/// A [`Request`] that transforms the keys of the parameters of another `Request`, re-sorting
+/// the parameters by the resulting keys.
+pub(crate) struct TransformKeys<'r, R: ?Sized> {
+    request: &'r R,
+    transform: fn(&str) -> Cow<'_, str>,
+}
+
+/// The parameters of a [`Request`], collected in sorted order so that the exact parameters that
+/// are signed can also be handed to an HTTP client.
+#[derive(Clone, Debug)]
+pub(crate) struct Collected(Vec<Param>);
+
+pub(crate) struct Collect {
+    params: Vec<Param>,
+    /// Whether to trim the values and the set to encode them in, if they are collected as
+    /// an `Authorizer` signs them.
+    signing: Option<(bool, EncodeSet)>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Param {
+    key: String,
+    value: String,
+    encoded: bool,
+}
+
+impl<A: Request, B: Request> Merge<A, B> {
+    /// Creates a new `Merge` from two `Request`s.
+    pub fn new(first: A, second: B) -> Self {
+        Merge { first, second }
+    }
+}
+
+impl<A: Request, B: Request> Request for Merge<A, B> {
+    fn serialize<S>(&self, serializer: S) -> S::Output
+    where
+        S: Serializer,
+    {
+        let mut params = self.first.serialize(Collect::new());
+        params.extend(self.second.serialize(Collect::new()));
+        serialize_sorted(params, serializer)
+    }
+}
+
+impl<'r, R: ?Sized> TransformKeys<'r, R> {
+    pub(crate) fn new(request: &'r R, transform: fn(&str) -> Cow<'_, str>) -> Self {
+        TransformKeys { request, transform }
+    }
+}
+
+impl<'r, R: Request + ?Sized> Request for TransformKeys<'r, R> {
+    fn serialize<S>(&self, serializer: S) -> S::Output
+    where
+        S: Serializer,
+    {
+        let mut params = self.request.serialize(Collect::new());
+        for param in &mut params {
+            if let Cow::Owned(key) = (self.transform)(&param.key) {
+                param.key = key;
+            }
+        }
+        serialize_sorted(params, serializer)
+    }
+}
+
+impl Collected {
+    pub(crate) fn new(mut params: Vec<Param>) -> Self {
+        sort(&mut params);
+        Collected(params)
+    }
+
+    /// Percent encodes the values that were not serialized as already encoded, so that they are
+    /// not encoded again on each serialization.
+    pub(crate) fn encode_values(mut self) -> Self {
+        for param in &mut self.0 {
+            if !param.encoded {
+                param.value = percent_encode(&param.value).to_string();
+                param.encoded = true;
+            }
+        }
+        self
+    }
+
+    /// Returns the key-value pairs of the parameters, percent decoding the values that were
+    /// serialized as already encoded.
+    pub(crate) fn into_pairs(self) -> Vec<(String, String)> {
+        self.0
+            .into_iter()
+            .map(|param| {
+                let value = if param.encoded {
+                    percent_encoding::percent_decode_str(&param.value)
+                        .decode_utf8_lossy()
+                        .into_owned()
+                } else {
+                    param.value
+                };
+                (param.key, value)
+            })
+            .collect()
+    }
+}
+
+impl Request for Collected {
+    fn serialize<S>(&self, serializer: S) -> S::Output
+    where
+        S: Serializer,
+    {
+        serialize_params(&self.0, serializer)
+    }
+}
+
+fn sort(params: &mut [Param]) {
+    params.sort_by(|a, b| (&*a.key, &*a.value).cmp(&(&*b.key, &*b.value)));
+}
+
+/// Sorts `params` and feeds them to `serializer` along with the OAuth protocol parameters.
+fn serialize_sorted<S: Serializer>(mut params: Vec<Param>, serializer: S) -> S::Output {
+    sort(&mut params);
+    serialize_params(&params, serializer)
+}
+
+/// Feeds the sorted `params` to `serializer` along with the OAuth protocol parameters.
+fn serialize_params<S: Serializer>(params: &[Param], mut serializer: S) -> S::Output {
+    let mut next_param = OAuthParameter::default();
+
+    for param in params {
+        while next_param < *param.key {
+            next_param.serialize(&mut serializer);
+            next_param = next_param.next();
+        }
+        if param.encoded {
+            serializer.serialize_parameter_encoded(&param.key, &param.value);
+        } else {
+            serializer.serialize_parameter(&param.key, &param.value);
+        }
+    }
+
+    while next_param != OAuthParameter::None {
+        next_param.serialize(&mut serializer);
+        next_param = next_param.next();
+    }
+
+    serializer.end()
+}
+
+impl Collect {
+    pub(crate) fn new() -> Self {
+        Collect {
+            params: Vec::new(),
+            signing: None,
+        }
+    }
+
+    /// Creates a `Collect` that trims and percent encodes the values the same way as
+    /// an `Authorizer` with `options` does when signing them.
+    pub(crate) fn signing(options: &auth::Options<'_>) -> Self {
+        Collect {
+            params: Vec::new(),
+            signing: Some((
+                options.effective_trim_values(),
+                options.effective_encode_set(),
+            )),
+        }
+    }
+
+    fn push(&mut self, key: &str, value: impl Display, encoded: bool) {
+        self.params.push(Param {
+            key: key.to_string(),
+            value: value.to_string(),
+            encoded,
+        });
+    }
+}
+
+impl Serializer for Collect {
+    type Output = Vec<Param>;
+
+    fn serialize_parameter<V: Display>(&mut self, key: &str, value: V) {
+        match self.signing {
+            Some((true, encode_set)) => {
+                let value = PercentEncodeWith(TrimAsciiWhitespace(value), encode_set);
+                self.push(key, value, true);
+            }
+            Some((false, encode_set)) => self.push(key, PercentEncodeWith(value, encode_set), true),
+            None => self.push(key, value, false),
+        }
+    }
+
+    fn serialize_parameter_encoded<V: Display>(&mut self, key: &str, value: V) {
+        self.push(key, value, true);
+    }
+
+    crate::serializer::skip_serialize_oauth_parameters!();
+
+    fn end(self) -> Vec<Param> {
+        self.params
+    }
+}
@@ -28,6 +28,22 @@ use crate::serializer::Serializer;
 /// let form = oauth::to_form(&request);
 /// assert_eq!(form, "bar=23&foo=123&foo=3");
 /// ```
+///
+/// A `ParameterList` can also be collected from an iterator of pairs, which is useful for
+/// parameters built at runtime. The pairs are sorted by key and then by value in either case.
+/// If you have the parameters as an already percent-encoded string like `a=1&b=2`, use
+/// `EncodedQuery` instead, which requires the `alloc` feature.
+///
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// let fields = vec![("status", "Hello"), ("media_ids", "2"), ("media_ids", "1")];
+/// let request: oauth::ParameterList<&str, &str> = fields.into_iter().collect();
+///
+/// let form = oauth::to_form(&request);
+/// assert_eq!(form, "media_ids=1&media_ids=2&status=Hello");
+/// ```
 pub struct ParameterList<
     K,
     V,
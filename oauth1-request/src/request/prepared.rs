@@ -0,0 +1,64 @@
+//! A [`Request`] whose parameters are serialized once and reused for subsequent signings.
+
+use super::merge::{Collect, Collected};
+use super::Request;
+use crate::serializer::Serializer;
+
+/// A [`Request`] holding the sorted and percent-encoded parameters of another `Request`.
+///
+/// Creating a `PreparedRequest` serializes the parameters once. Serializing the
+/// `PreparedRequest` afterwards only interleaves the OAuth protocol parameters with the stored
+/// parameters, so authorizing it again (e.g. when retrying a failed request with a fresh nonce
+/// and timestamp) does not need to format, encode and sort the parameters anew.
+///
+/// The values are percent-encoded with the default encode set ([`EncodeSet::RFC5849`]), so
+/// the [`encode_set`](crate::serializer::auth::Options::encode_set) option of the serializer has
+/// no effect on them.
+///
+/// [`EncodeSet::RFC5849`]: crate::serializer::EncodeSet::RFC5849
+///
+/// ## Example
+///
+#[cfg_attr(all(feature = "std", feature = "default-nonce", feature = "hmac-sha1"), doc = " ```")]
+#[cfg_attr(not(all(feature = "std", feature = "default-nonce", feature = "hmac-sha1")), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::request::PreparedRequest;
+///
+/// let request = oauth::request! { "status" => "Hello, world!", "lang" => "en" };
+/// let prepared = PreparedRequest::new(&request);
+/// assert_eq!(oauth::to_form(&prepared), oauth::to_form(&request));
+///
+/// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+/// let builder = oauth::Builder::<_, _>::new(client, oauth::HMAC_SHA1);
+/// let uri = "https://example.com/api/update";
+/// let first = builder.post_form(uri, &prepared);
+/// // Retry with a fresh nonce and timestamp.
+/// let second = builder.post_form(uri, &prepared);
+/// assert!(first.starts_with("lang=en&oauth_consumer_key=consumer_key&"));
+/// assert!(second.contains("&status=Hello%2C%20world%21&oauth_signature="));
+/// assert_ne!(first, second);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PreparedRequest {
+    params: Collected,
+}
+
+impl PreparedRequest {
+    /// Creates a new `PreparedRequest` by serializing the parameters of `request`.
+    pub fn new<R: Request + ?Sized>(request: &R) -> Self {
+        let params = Collected::new(request.serialize(Collect::new()));
+        PreparedRequest {
+            params: params.encode_values(),
+        }
+    }
+}
+
+impl Request for PreparedRequest {
+    fn serialize<S>(&self, serializer: S) -> S::Output
+    where
+        S: Serializer,
+    {
+        self.params.serialize(serializer)
+    }
+}
@@ -0,0 +1,270 @@
+//! Formatting of scalar values through their `serde::Serialize` implementations.
+//!
+//! This is what `#[oauth1(serialize_with_serde)]` attribute of the derive macro uses under the
+//! hood, and the module can also be used with `#[oauth1(with = path)]` attribute:
+//!
+#![cfg_attr(feature = "derive", doc = " ```")]
+#![cfg_attr(not(feature = "derive"), doc = " ```ignore")]
+//! # extern crate oauth1_request as oauth;
+//! # extern crate serde_derive;
+//! #
+//! use serde_derive::Serialize;
+//!
+//! #[derive(Serialize)]
+//! # #[serde(crate = "serde1")]
+//! #[serde(rename_all = "lowercase")]
+//! enum Order {
+//!     Ascending,
+//!     Descending,
+//! }
+//!
+//! #[derive(oauth::Request)]
+//! struct Search {
+//!     #[oauth1(serialize_with_serde)]
+//!     order: Order,
+//!     #[oauth1(with = oauth::request::serde_scalar)]
+//!     order2: Order,
+//! }
+//!
+//! let request = Search {
+//!     order: Order::Ascending,
+//!     order2: Order::Descending,
+//! };
+//! assert_eq!(oauth::to_form(&request), "order=ascending&order2=descending");
+//! ```
+
+use core::fmt::{self, Display, Formatter, Write};
+
+use serde1::ser::{Impossible, Serialize, Serializer};
+
+/// Displays a value through its `Serialize` implementation.
+///
+/// See [`display`] for the supported values.
+pub struct SerdeScalar<'a, T: ?Sized>(&'a T);
+
+/// The error type of `ScalarSerializer`.
+#[derive(Debug)]
+enum Error {
+    Fmt,
+    /// The value is not a scalar value, with the description of its data model type.
+    Compound(&'static str),
+}
+
+/// A `Serializer` that writes scalar values into a `Formatter`.
+struct ScalarSerializer<'a, 'b> {
+    f: &'a mut Formatter<'b>,
+}
+
+/// Returns a `Display` adapter that formats `value` through its `Serialize` implementation.
+///
+/// Numbers, `bool`s, `char`s and strings are written with their `Display` representation.
+/// Unit variants are written as their (possibly `rename`d) names, newtype structs as their
+/// content, and `None` and units as an empty string.
+///
+/// ## Panics
+///
+/// Formatting the returned value panics if `value` serializes as a compound value (like
+/// a sequence, a map or a struct) or bytes, since OAuth parameter values are flat strings,
+/// or if the `Serialize` implementation fails.
+pub fn display<T: Serialize + ?Sized>(value: &T) -> SerdeScalar<'_, T> {
+    SerdeScalar(value)
+}
+
+impl<'a, T: Serialize + ?Sized> Display for SerdeScalar<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.0.serialize(ScalarSerializer { f }) {
+            Ok(()) => Ok(()),
+            Err(Error::Fmt) => Err(fmt::Error),
+            Err(Error::Compound(kind)) => panic!(
+                "parameter values serialized with serde must be scalar values, but got {}",
+                kind,
+            ),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Error::Fmt => f.write_str("an error occurred when formatting a value"),
+            Error::Compound(kind) => write!(f, "expected a scalar value, got {}", kind),
+        }
+    }
+}
+
+impl serde1::ser::StdError for Error {}
+
+impl serde1::ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        panic!("failed to serialize a parameter value with serde: {}", msg);
+    }
+}
+
+impl From<fmt::Error> for Error {
+    fn from(_: fmt::Error) -> Self {
+        Error::Fmt
+    }
+}
+
+macro_rules! serialize_display {
+    ($($method:ident($T:ty);)*) => {$(
+        fn $method(self, v: $T) -> Result<(), Error> {
+            write!(self.f, "{}", v).map_err(Into::into)
+        }
+    )*};
+}
+
+impl<'a, 'b> Serializer for ScalarSerializer<'a, 'b> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Impossible<(), Error>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    serialize_display! {
+        serialize_bool(bool);
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_i128(i128);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_u128(u128);
+        serialize_f32(f32);
+        serialize_f64(f64);
+        serialize_str(&str);
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        self.f.write_char(v).map_err(Into::into)
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<(), Error> {
+        Err(Error::Compound("bytes"))
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.f.write_str(variant).map_err(Into::into)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), Error> {
+        Err(Error::Compound("a newtype variant"))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(Error::Compound("a sequence"))
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(Error::Compound("a tuple"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(Error::Compound("a tuple struct"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(Error::Compound("a tuple variant"))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(Error::Compound("a map"))
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Error> {
+        Err(Error::Compound("a struct"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(Error::Compound("a struct variant"))
+    }
+
+    fn collect_str<T: Display + ?Sized>(self, value: &T) -> Result<(), Error> {
+        write!(self.f, "{}", value).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn scalar() {
+        assert_eq!(display(&42_u8).to_string(), "42");
+        assert_eq!(display(&-1.5_f64).to_string(), "-1.5");
+        assert_eq!(display(&true).to_string(), "true");
+        assert_eq!(display(&'c').to_string(), "c");
+        assert_eq!(display("café").to_string(), "café");
+        assert_eq!(display(&Some(1)).to_string(), "1");
+        assert_eq!(display(&None::<u32>).to_string(), "");
+        assert_eq!(display(&()).to_string(), "");
+    }
+
+    #[test]
+    #[should_panic(expected = "must be scalar values, but got a tuple")]
+    fn compound() {
+        display(&[1, 2]).to_string();
+    }
+}
@@ -0,0 +1,136 @@
+//! A [`Request`] built from a runtime list of parameters with their locations, as described by
+//! API specifications like OpenAPI.
+
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt::Display;
+use core::iter::FromIterator;
+
+use super::{AssertSorted, Request};
+use crate::serializer::Serializer;
+
+/// The location of a [`Parameter`] in an HTTP request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Location {
+    /// A query parameter of the request URI.
+    Query,
+    /// A parameter of an `application/x-www-form-urlencoded` request body.
+    Body,
+    /// An HTTP header.
+    Header,
+    /// A part of the path of the request URI.
+    Path,
+}
+
+/// A parameter of an HTTP request with its location.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Parameter<N, V> {
+    /// The name of the parameter.
+    pub name: N,
+    /// The value of the parameter.
+    pub value: V,
+    /// The location of the parameter.
+    pub location: Location,
+}
+
+/// A [`Request`] with a list of [`Parameter`]s in various locations.
+///
+/// Only the query parameters and the `x-www-form-urlencoded` body parameters are part of
+/// the signature ([RFC 5849 section 3.4.1.3.1.][rfc]), so the other parameters are skipped on
+/// serialization. This is useful for clients generated from API specifications at runtime,
+/// which cannot use the derive macro.
+///
+/// Note that [`Location::Body`] parameters must not be used for a request with a body other
+/// than `x-www-form-urlencoded` (e.g. JSON), whose parameters are not signed.
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1.3.1
+///
+/// ## Example
+///
+/// ```
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::request::spec_params::{Location, Parameter, SpecParams};
+///
+/// let request: SpecParams<_, _> = vec![
+///     Parameter { name: "status", value: "Hello", location: Location::Body },
+///     Parameter { name: "id", value: "42", location: Location::Path },
+///     Parameter { name: "X-Request-Id", value: "1", location: Location::Header },
+///     Parameter { name: "lang", value: "en", location: Location::Query },
+/// ]
+/// .into();
+///
+/// assert_eq!(oauth::to_form(&request), "lang=en&status=Hello");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SpecParams<N, V> {
+    params: Vec<Parameter<N, V>>,
+}
+
+impl Location {
+    /// Returns whether the parameters in the location are part of the signature.
+    pub fn is_signed(self) -> bool {
+        match self {
+            Location::Query | Location::Body => true,
+            Location::Header | Location::Path => false,
+        }
+    }
+}
+
+impl<N, V> SpecParams<N, V> {
+    /// Creates a new `SpecParams` from a list of parameters in any order.
+    pub fn new(params: Vec<Parameter<N, V>>) -> Self {
+        SpecParams { params }
+    }
+
+    /// Returns the parameters of the `SpecParams`.
+    pub fn params(&self) -> &[Parameter<N, V>] {
+        &self.params
+    }
+
+    /// Returns an iterator over the parameters in the given location.
+    pub fn in_location(&self, location: Location) -> impl Iterator<Item = &Parameter<N, V>> {
+        self.params.iter().filter(move |p| p.location == location)
+    }
+
+    /// Consumes the `SpecParams`, returning the list of parameters.
+    pub fn into_inner(self) -> Vec<Parameter<N, V>> {
+        self.params
+    }
+}
+
+impl<N, V> Request for SpecParams<N, V>
+where
+    N: Borrow<str>,
+    V: Display,
+{
+    fn serialize<S>(&self, serializer: S) -> S::Output
+    where
+        S: Serializer,
+    {
+        let mut signed: Vec<&Parameter<N, V>> = self
+            .params
+            .iter()
+            .filter(|p| p.location.is_signed())
+            .collect();
+        signed.sort_unstable_by(|a, b| {
+            (a.name.borrow(), fmt_cmp::Cmp(&a.value))
+                .cmp(&(b.name.borrow(), fmt_cmp::Cmp(&b.value)))
+        });
+
+        let pairs = signed.iter().map(|p| (p.name.borrow(), &p.value));
+        AssertSorted::new(pairs).serialize(serializer)
+    }
+}
+
+impl<N, V> From<Vec<Parameter<N, V>>> for SpecParams<N, V> {
+    fn from(params: Vec<Parameter<N, V>>) -> Self {
+        SpecParams::new(params)
+    }
+}
+
+impl<N, V> FromIterator<Parameter<N, V>> for SpecParams<N, V> {
+    fn from_iter<I: IntoIterator<Item = Parameter<N, V>>>(iter: I) -> Self {
+        SpecParams::new(iter.into_iter().collect())
+    }
+}
@@ -2,6 +2,8 @@
 
 doc_auto_cfg! {
     pub mod auth;
+    #[cfg(feature = "serde_json")]
+    pub mod json;
     #[cfg(feature = "test")]
     pub mod recorder;
     pub mod urlencode;
@@ -9,13 +11,19 @@ doc_auto_cfg! {
 
 doc_auto_cfg! {
     pub use auth::Authorizer;
+    #[cfg(feature = "serde_json")]
+    pub use json::JsonSerializer;
     #[cfg(feature = "test")]
     pub use recorder::Recorder;
     pub use urlencode::Urlencoder;
+    #[cfg(feature = "std")]
+    pub use urlencode::IoUrlencoder;
 }
 
 use core::fmt::Display;
 
+pub use crate::util::{percent_encode_into, BufferTooSmall, EncodeSet};
+
 /// Helper macro for implementors of `Serializer` which generates blank implementation of
 /// `serialize_oauth_*` methods.
 ///
@@ -104,10 +112,17 @@ pub trait Serializer {
     ///
     /// The serializer percent encodes the value, but not the key.
     ///
+    /// The value is treated as raw text even if it contains percent-encoded parts. So the output
+    /// of another serializer, like a signed `Authorization` header value embedded in a request,
+    /// should be serialized with this method (rather than `serialize_parameter_encoded`) to be
+    /// encoded exactly once.
+    ///
     /// # Panics
     ///
-    /// The parameters must be serialized in byte ascending order
-    /// and implementations may panic otherwise.
+    /// The parameters must be serialized in byte ascending order and the key must consist only of
+    /// "unreserved characters" of [RFC 3986][rfc], and implementations may panic otherwise.
+    ///
+    /// [rfc]: https://tools.ietf.org/html/rfc3986#section-2.3
     fn serialize_parameter<V>(&mut self, key: &str, value: V)
     where
         V: Display;
@@ -118,8 +133,10 @@ pub trait Serializer {
     ///
     /// # Panics
     ///
-    /// The parameters must be serialized in byte ascending order
-    /// and implementations may panic otherwise.
+    /// The parameters must be serialized in byte ascending order and the key must consist only of
+    /// "unreserved characters" of [RFC 3986][rfc], and implementations may panic otherwise.
+    ///
+    /// [rfc]: https://tools.ietf.org/html/rfc3986#section-2.3
     fn serialize_parameter_encoded<V>(&mut self, key: &str, value: V)
     where
         V: Display;
@@ -218,7 +235,9 @@ mod tests {
     #[cfg(feature = "hmac-sha1")]
     const TIMESTAMP: u64 = 1318622958;
 
+    #[cfg_attr(not(feature = "hmac-sha1"), allow(dead_code))]
     struct Inspect<SM>(SM);
+    #[cfg_attr(not(feature = "hmac-sha1"), allow(dead_code))]
     struct InspectSign<S>(S);
 
     impl<SM: SignatureMethod> SignatureMethod for Inspect<SM> {
@@ -231,6 +250,7 @@ mod tests {
         }
     }
 
+    #[allow(dead_code)]
     #[derive(Clone, Debug)]
     struct AssertImpl<'a>(
         #[cfg(feature = "hmac-sha1")] Authorizer<'a, HmacSha1, String>,
@@ -381,6 +401,896 @@ mod tests {
         }
     }
 
+    #[cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+    #[test]
+    fn fold_query() {
+        use core::num::NonZeroU64;
+        use std::format;
+
+        use crate::request::ParameterList;
+        use crate::Request;
+
+        fn authorize<R: Request>(uri: &str, request: R, options: &auth::Options<'_>) -> String {
+            let client = Credentials::new(CK, CS);
+            let token = Credentials::new(AK, AS);
+            let ser = Authorizer::authorization(
+                "GET",
+                uri,
+                client,
+                Some(token),
+                options,
+                crate::HMAC_SHA1,
+            );
+            request.serialize(ser)
+        }
+
+        let mut options = auth::Options::new();
+        options
+            .nonce(NONCE)
+            .timestamp(NonZeroU64::new(TIMESTAMP))
+            .version(true);
+
+        // Same as the last case of the `serialize` test.
+        let expected = "oauth_signature=\"Xp35hf3T21yhpEuxez7p6bV62Bw%3D\"";
+        let query = "https://example.com/get.json?foo=%E3%81%B5%E3%83%BC&bar=%E9%85%92%E5%A0%B4";
+
+        assert!(authorize(query, (), &options).ends_with(expected));
+        let request = ParameterList::new([("bar", "酒場")]);
+        let uri = "https://example.com/get.json?foo=%E3%81%B5%E3%83%BC";
+        assert!(authorize(uri, &request, &options).ends_with(expected));
+
+        options.keep_query_in_uri(true);
+        assert!(!authorize(query, (), &options).ends_with(expected));
+
+        options.keep_query_in_uri(false).strip_oauth_query(true);
+        let signed = format!("{}&oauth_nonce=foo&oauth_signature=bar", query);
+        assert!(authorize(&signed, (), &options).ends_with(expected));
+
+        let client = Credentials::new(CK, CS);
+        let ser = Authorizer::query(
+            "GET",
+            query.to_string(),
+            client,
+            None,
+            &options,
+            crate::HMAC_SHA1,
+        );
+        let uri = ().serialize(ser);
+        assert!(uri.starts_with(&format!("{}&oauth_consumer_key=", query)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn fold_query_encoded() {
+        use core::num::NonZeroU64;
+
+        use crate::request::ParameterList;
+        use crate::signature_method::BaseString;
+        use crate::Request;
+
+        let client = Credentials::new(CK, CS);
+        let mut options = auth::Options::new();
+        options.nonce("nonce").timestamp(NonZeroU64::new(1));
+
+        let mut base_string = String::new();
+        let sm = BaseString::new(&mut base_string, "PLAINTEXT");
+        // The keys and values are sorted by their encoded forms, e.g. `ids[]` (`ids%5B%5D`)
+        // precedes `ids_` and `!` (`%21`) precedes `a`.
+        let uri = "https://example.com/?x=a&ids_=1&ids%5B%5D=2&a%20b=3";
+        ParameterList::new([("x", "!")]).serialize(Authorizer::authorization(
+            "GET", uri, client, None, &options, sm,
+        ));
+        assert_eq!(
+            base_string,
+            "GET&https%3A%2F%2Fexample.com%2F&\
+             a%2520b%3D3%26\
+             ids%255B%255D%3D2%26\
+             ids_%3D1%26\
+             oauth_consumer_key%3Dxvz1evFS4wEEPTGEFPHBog%26\
+             oauth_nonce%3Dnonce%26\
+             oauth_signature_method%3DPLAINTEXT%26\
+             oauth_timestamp%3D1%26\
+             x%3D%2521%26\
+             x%3Da",
+        );
+    }
+
+    #[cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+    #[test]
+    fn urlencode() {
+        use core::num::NonZeroU64;
+        use std::format;
+
+        use crate::request::ParameterList;
+        use crate::Request;
+
+        let client = Credentials::new(CK, CS);
+        let token = Credentials::new(AK, AS);
+        let mut options = auth::Options::new();
+        options
+            .nonce(NONCE)
+            .timestamp(NonZeroU64::new(TIMESTAMP))
+            .version(true);
+
+        // Same as the second case of the `serialize` test.
+        let uri = "https://api.twitter.com/1.1/statuses/update.json";
+        let request = ParameterList::new([
+            ("include_entities", "true"),
+            (
+                "status",
+                "Hello Ladies + Gentlemen, a signed OAuth request!",
+            ),
+        ]);
+        let params = format!(
+            "include_entities=true&\
+            oauth_consumer_key={}&\
+            oauth_nonce={}&\
+            oauth_signature_method=HMAC-SHA1&\
+            oauth_timestamp={}&\
+            oauth_token={}&\
+            oauth_version=1.0&\
+            status=Hello%20Ladies%20%2B%20Gentlemen%2C%20a%20signed%20OAuth%20request%21&\
+            oauth_signature=hCtSmYh%2BiHYCEqBWrE7C7hYmtUk%3D",
+            CK, NONCE, TIMESTAMP, AK,
+        );
+
+        let ser = Authorizer::form("POST", uri, client, Some(token), &options, crate::HMAC_SHA1);
+        assert_eq!(request.serialize(ser), params);
+
+        let ser = Authorizer::query(
+            "POST",
+            uri.to_string(),
+            client,
+            Some(token),
+            &options,
+            crate::HMAC_SHA1,
+        );
+        assert_eq!(request.serialize(ser), format!("{}?{}", uri, params));
+    }
+
+    #[cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+    #[test]
+    fn verify() {
+        use std::string::ToString;
+
+        let client = Credentials::new(CK, CS);
+        let token = Credentials::new(AK, AS);
+        let timestamp = TIMESTAMP.to_string();
+
+        // Same as the first two cases of the `serialize` test.
+        for &(method, uri, params, signature) in &[
+            (
+                "GET",
+                "https://stream.twitter.com/1.1/statuses/sample.json",
+                &[("stall_warnings", "true")][..],
+                "OGQqcy4l5xWBFX7t0DrkP5/D0rM=",
+            ),
+            (
+                "POST",
+                "https://api.twitter.com/1.1/statuses/update.json",
+                &[
+                    ("include_entities", "true"),
+                    (
+                        "status",
+                        "Hello Ladies + Gentlemen, a signed OAuth request!",
+                    ),
+                ][..],
+                "hCtSmYh+iHYCEqBWrE7C7hYmtUk=",
+            ),
+        ] {
+            let mut params = params.to_vec();
+            params.extend_from_slice(&[
+                ("oauth_nonce", NONCE),
+                ("oauth_signature_method", "HMAC-SHA1"),
+                ("oauth_timestamp", &timestamp),
+                ("oauth_version", "1.0"),
+                ("oauth_signature", "ignored"),
+            ]);
+            let verify = |token, signature| {
+                crate::verify(
+                    method,
+                    uri,
+                    &params,
+                    client,
+                    token,
+                    signature,
+                    crate::HMAC_SHA1,
+                )
+            };
+
+            assert!(verify(Some(token), signature));
+            assert!(!verify(None, signature));
+            assert!(!verify(Some(Credentials::new(AK, CS)), signature));
+            assert!(!verify(Some(token), &signature[1..]));
+            assert!(!crate::verify(
+                "PUT",
+                uri,
+                &params,
+                client,
+                Some(token),
+                signature,
+                crate::HMAC_SHA1,
+            ));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn urlencode_reserved_characters() {
+        use std::format;
+
+        for &c in &["=", "&", "?", "#", "+", "%", ";", ":"] {
+            let value = format!("a{}b", c);
+            let encoded = format!("a%{:02X}b", c.as_bytes()[0]);
+
+            let mut ser = Urlencoder::form();
+            ser.serialize_parameter("k", &value);
+            ser.serialize_parameter("l", "v");
+            assert_eq!(ser.end(), format!("k={}&l=v", encoded));
+
+            let mut ser = Urlencoder::query(String::from("https://example.com/"));
+            ser.encode_set(EncodeSet::RFC5849.remove(c.as_bytes()[0]));
+            ser.serialize_parameter("k", &value);
+            let expected = if c == ";" || c == ":" {
+                format!("https://example.com/?k={}", value)
+            } else {
+                format!("https://example.com/?k={}", encoded)
+            };
+            assert_eq!(ser.end(), expected);
+
+            let mut ser = Urlencoder::form();
+            ser.separators(';', ':');
+            ser.encode_set(EncodeSet::ENCODE_URI_COMPONENT.remove(b';').remove(b':'));
+            ser.serialize_parameter("k", &value);
+            ser.serialize_parameter("l", "v");
+            assert_eq!(ser.end(), format!("k:{};l:v", encoded));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn encode_set() {
+        use core::num::NonZeroU64;
+        use std::format;
+
+        use crate::request::ParameterList;
+        use crate::signature_method::BaseString;
+        use crate::Request;
+
+        let client = Credentials::new(CK, CS);
+        let mut options = auth::Options::new();
+        options
+            .nonce("nonce")
+            .timestamp(NonZeroU64::new(1))
+            .encode_set(EncodeSet::ENCODE_URI_COMPONENT);
+        let request = ParameterList::new([("q", "(a*b)! c")]);
+        let uri = "https://example.com/search?sort=!date";
+
+        let mut base_string = String::new();
+        let sm = BaseString::new(&mut base_string, "PLAINTEXT");
+        let ser = Authorizer::form("GET", uri, client, None, &options, sm);
+        let form = request.serialize(ser);
+        assert!(form.starts_with("oauth_consumer_key="));
+        assert!(form.contains("&q=(a*b)!%20c&"));
+        assert_eq!(
+            base_string,
+            format!(
+                "GET&https%3A%2F%2Fexample.com%2Fsearch&\
+                oauth_consumer_key%3D{}%26\
+                oauth_nonce%3Dnonce%26\
+                oauth_signature_method%3DPLAINTEXT%26\
+                oauth_timestamp%3D1%26\
+                q%3D(a*b)!%2520c%26\
+                sort%3D!date",
+                CK,
+            ),
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn realm() {
+        use core::num::NonZeroU64;
+
+        use crate::signature_method::PLAINTEXT;
+        use crate::Request;
+
+        fn authorize(options: &auth::Options<'_>) -> String {
+            let client = Credentials::new(CK, CS);
+            let uri = "https://example.com/";
+            ().serialize(Authorizer::authorization(
+                "GET", uri, client, None, options, PLAINTEXT,
+            ))
+        }
+
+        let mut options = auth::Options::new();
+        options.nonce("nonce").timestamp(NonZeroU64::new(1));
+        let expected = authorize(&options);
+
+        options.realm("Example Realm");
+        let authorization = authorize(&options);
+        assert!(authorization.starts_with("OAuth realm=\"Example Realm\",oauth_consumer_key="));
+        // The realm is not included in the signature.
+        assert_eq!(
+            authorization.replace("realm=\"Example Realm\",", ""),
+            expected
+        );
+
+        options.encode_realm(true);
+        let authorization = authorize(&options);
+        assert!(authorization.starts_with("OAuth realm=\"Example%20Realm\",oauth_consumer_key="));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn reset() {
+        use core::num::NonZeroU64;
+
+        use crate::signature_method::PLAINTEXT;
+
+        fn serialize<W: core::fmt::Write>(
+            mut ser: Authorizer<'_, impl SignatureMethod, W>,
+            value: &str,
+        ) -> W {
+            ser.serialize_parameter("foo", value);
+            ser.serialize_oauth_parameters();
+            ser.end()
+        }
+
+        let client = Credentials::new(CK, CS);
+        let token = Some(Credentials::new(AK, AS));
+        let mut options = auth::Options::new();
+        options.nonce("nonce").timestamp(NonZeroU64::new(1));
+        let uri = "https://example.com/get.json?bar=baz";
+
+        let expected = [
+            serialize(
+                Authorizer::authorization("GET", uri, client, token, &options, PLAINTEXT),
+                "qux",
+            ),
+            serialize(
+                Authorizer::form("POST", uri, client, token, &options, PLAINTEXT),
+                "qux",
+            ),
+            serialize(
+                Authorizer::query("GET", uri.to_string(), client, token, &options, PLAINTEXT),
+                "qux",
+            ),
+        ];
+
+        let other = "https://example.net/";
+        let mut authorization =
+            Authorizer::authorization("PUT", other, client, None, &options, PLAINTEXT);
+        let mut form = Authorizer::form("PUT", other, client, None, &options, PLAINTEXT);
+        let mut query =
+            Authorizer::query("PUT", other.to_string(), client, None, &options, PLAINTEXT);
+        authorization.serialize_parameter("abc", "discarded");
+        form.serialize_parameter("abc", "discarded");
+        query.serialize_parameter("abc", "discarded");
+
+        authorization.reset("GET", uri, client, token, &options, PLAINTEXT);
+        form.reset("POST", uri, client, token, &options, PLAINTEXT);
+        query.reset("GET", uri, client, token, &options, PLAINTEXT);
+        let actual = [
+            serialize(authorization, "qux"),
+            serialize(form, "qux"),
+            serialize(query, "qux"),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn reset_after_panic() {
+        use core::fmt::{self, Display, Formatter};
+        use core::num::NonZeroU64;
+        use std::panic::{self, AssertUnwindSafe};
+
+        use crate::signature_method::PLAINTEXT;
+
+        struct Panic;
+        impl Display for Panic {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.write_str("partial")?;
+                panic!("panicking `Display` implementation");
+            }
+        }
+
+        let client = Credentials::new(CK, CS);
+        let mut options = auth::Options::new();
+        options.nonce("nonce").timestamp(NonZeroU64::new(1));
+        let uri = "https://example.com/get.json";
+
+        let mut expected = Authorizer::form("POST", uri, client, None, &options, PLAINTEXT);
+        expected.serialize_parameter("foo", "bar");
+        expected.serialize_oauth_parameters();
+        let expected = expected.end();
+
+        let mut form = Authorizer::form("POST", uri, client, None, &options, PLAINTEXT);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            form.serialize_parameter("abc", Panic);
+        }));
+        assert!(result.is_err());
+
+        form.reset("POST", uri, client, None, &options, PLAINTEXT);
+        form.serialize_parameter("foo", "bar");
+        form.serialize_oauth_parameters();
+        assert_eq!(form.end(), expected);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn body_hash() {
+        use core::num::NonZeroU64;
+
+        use crate::signature_method::BaseString;
+        use crate::Request;
+
+        let client = Credentials::new(CK, CS);
+        let mut options = auth::Options::new();
+        options
+            .body_hash("HMzhEUx3526K9M/Dwl6a1/At6Ns=")
+            .nonce("nonce")
+            .timestamp(NonZeroU64::new(1));
+
+        let mut base_string = String::new();
+        let sm = BaseString::new(&mut base_string, "PLAINTEXT");
+        let authorization = ().serialize(Authorizer::authorization(
+            "PATCH",
+            "https://example.com/api/items/1?a=b",
+            client,
+            None,
+            &options,
+            sm,
+        ));
+        assert!(authorization.starts_with(
+            "OAuth oauth_body_hash=\"HMzhEUx3526K9M%2FDwl6a1%2FAt6Ns%3D\",oauth_consumer_key="
+        ));
+        assert!(base_string.starts_with(
+            "PATCH&https%3A%2F%2Fexample.com%2Fapi%2Fitems%2F1&a%3Db%26\
+             oauth_body_hash%3DHMzhEUx3526K9M%252FDwl6a1%252FAt6Ns%253D%26oauth_consumer_key%3D"
+        ));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn verifier_in_body() {
+        use core::num::NonZeroU64;
+
+        use crate::signature_method::BaseString;
+        use crate::Request;
+
+        let client = Credentials::new(CK, CS);
+        let token = Some(Credentials::new(AK, AS));
+        let uri = "https://example.com/oauth/access_token";
+        let mut options = auth::Options::new();
+        options
+            .verifier("verifier")
+            .nonce("nonce")
+            .timestamp(NonZeroU64::new(1));
+
+        let mut expected = String::new();
+        let sm = BaseString::new(&mut expected, "PLAINTEXT");
+        let form = ().serialize(Authorizer::form("POST", uri, client, token, &options, sm));
+        assert!(form.contains("&oauth_verifier=verifier"));
+        assert!(expected.ends_with("%26oauth_verifier%3Dverifier"));
+
+        options.verifier_in_body(true);
+        let mut base_string = String::new();
+        let sm = BaseString::new(&mut base_string, "PLAINTEXT");
+        let authorization = ().serialize(Authorizer::authorization(
+            "POST", uri, client, token, &options, sm,
+        ));
+        assert!(!authorization.contains("oauth_verifier"));
+        assert!(authorization.contains(r#"oauth_token=""#));
+        assert_eq!(base_string, expected);
+
+        // The form serializer writes the verifier regardless of the option.
+        let sm = BaseString::new(&mut base_string, "PLAINTEXT");
+        let form_in_body = ().serialize(Authorizer::form("POST", uri, client, token, &options, sm));
+        assert_eq!(form_in_body, form);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn uppercase_scheme_and_host() {
+        use core::num::NonZeroU64;
+
+        use crate::signature_method::BaseString;
+        use crate::Request;
+
+        let client = Credentials::new(CK, CS);
+        let mut options = auth::Options::new();
+        options.nonce("nonce").timestamp(NonZeroU64::new(1));
+
+        let base_string = |uri: &str| {
+            let mut base_string = String::new();
+            let sm = BaseString::new(&mut base_string, "PLAINTEXT");
+            ().serialize(Authorizer::authorization(
+                "GET", uri, client, None, &options, sm,
+            ));
+            base_string
+        };
+
+        let expected = base_string("http://example.com:8080/Path/To?Q=A");
+        assert!(expected.starts_with("GET&http%3A%2F%2Fexample.com%3A8080%2FPath%2FTo&Q%3DA%26"));
+        assert_eq!(base_string("HTTP://Example.COM:8080/Path/To?Q=A"), expected);
+        assert_ne!(base_string("http://example.com:8080/path/to?Q=A"), expected);
+        assert_ne!(base_string("http://example.com:8080/Path/To?q=a"), expected);
+    }
+
+    // Values such as Base64-encoded media payloads can be megabytes long, so encoding and signing
+    // them must take linear time, i.e. the serializer must only format them a fixed number of
+    // times and must not write anything but the encoded value for them.
+    #[cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+    #[test]
+    fn large_value() {
+        use core::cell::Cell;
+        use core::num::NonZeroU64;
+
+        use crate::signature_method::BaseString;
+
+        struct CountFmt<'a>(&'a str, &'a Cell<usize>);
+        impl<'a> Display for CountFmt<'a> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                self.1.set(self.1.get() + 1);
+                f.write_str(self.0)
+            }
+        }
+
+        const REPEAT: usize = 1 << 20;
+
+        let client = Credentials::new(CK, CS);
+        let token = Some(Credentials::new(AK, AS));
+        let uri = "https://example.com/media/upload";
+        let mut options = auth::Options::new();
+        options.nonce("nonce").timestamp(NonZeroU64::new(1));
+        let value = "a b/".repeat(REPEAT);
+        let count = Cell::new(0);
+
+        let mut ser = Authorizer::form("POST", uri, client, token, &options, crate::HMAC_SHA1);
+        ser.serialize_parameter("media", CountFmt(&value, &count));
+        let form = ser.end();
+        assert_eq!(count.get(), 2);
+        let encoded = "a%20b%2F".repeat(REPEAT);
+        assert_eq!(&form[..6], "media=");
+        assert_eq!(&form[6..6 + encoded.len()], encoded);
+        assert!(form[6 + encoded.len()..].starts_with("&oauth_signature="));
+
+        count.set(0);
+        let mut base_string = String::new();
+        let sm = BaseString::new(&mut base_string, "HMAC-SHA1");
+        let mut ser = Authorizer::form("POST", uri, client, token, &options, sm);
+        ser.serialize_parameter("media", CountFmt(&value, &count));
+        ser.end();
+        assert_eq!(count.get(), 2);
+        let prefix = "POST&https%3A%2F%2Fexample.com%2Fmedia%2Fupload&media%3D";
+        let encoded = "a%2520b%252F".repeat(REPEAT);
+        assert_eq!(&base_string[..prefix.len()], prefix);
+        assert_eq!(&base_string[prefix.len()..], encoded);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn fragment() {
+        use core::num::NonZeroU64;
+
+        use crate::signature_method::BaseString;
+        use crate::Request;
+
+        fn base_string(uri: &str) -> String {
+            let client = Credentials::new(CK, CS);
+            let mut options = auth::Options::new();
+            options.nonce("nonce").timestamp(NonZeroU64::new(1));
+            let mut ret = String::new();
+            let sm = BaseString::new(&mut ret, "PLAINTEXT");
+            let request = crate::request! { "a" => 1 };
+            request.serialize(Authorizer::authorization(
+                "GET", uri, client, None, &options, sm,
+            ));
+            ret
+        }
+
+        let expected = base_string("https://example.com/path?q=1");
+        assert!(expected.starts_with("GET&https%3A%2F%2Fexample.com%2Fpath&a%3D1%26"));
+        assert_eq!(base_string("https://example.com/path?q=1#frag"), expected);
+        assert_eq!(
+            base_string("https://example.com/path?q=1#frag?x=2"),
+            expected
+        );
+        assert_eq!(
+            base_string("https://example.com/path#frag?q=1"),
+            base_string("https://example.com/path"),
+        );
+
+        let query = crate::to_query(
+            "https://example.com/path#frag".to_string(),
+            &crate::request! { "a" => 1 },
+        );
+        assert_eq!(query, "https://example.com/path?a=1");
+    }
+
+    #[cfg(all(feature = "alloc", debug_assertions))]
+    #[test]
+    #[should_panic(expected = "`uri` must not contain a fragment")]
+    fn panic_on_fragment_in_query_uri() {
+        use crate::signature_method::PLAINTEXT;
+
+        let client = Credentials::new(CK, CS);
+        let options = auth::Options::new();
+        let uri = "https://example.com/path#frag".to_string();
+        Authorizer::query("GET", uri, client, None, &options, PLAINTEXT);
+    }
+
+    #[cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+    #[test]
+    fn nested_authorization() {
+        use core::num::NonZeroU64;
+        use std::format;
+
+        use crate::signature_method::BaseString;
+        use crate::util::percent_encode;
+        use crate::Request;
+
+        let client = Credentials::new(CK, CS);
+        let token = Some(Credentials::new(AK, AS));
+        let mut options = auth::Options::new();
+        options.nonce("nonce").timestamp(NonZeroU64::new(1));
+
+        let uri = "https://api.example.com/account/verify_credentials.json";
+        let inner = ().serialize(Authorizer::authorization(
+            "GET",
+            uri,
+            client,
+            token,
+            &options,
+            crate::HMAC_SHA1,
+        ));
+        // The header value contains a percent-encoded signature (e.g. `%3D`).
+        assert!(inner.contains('%'));
+        let encoded = percent_encode(&inner).to_string();
+
+        let uri = "https://example.com/upload";
+        let mut ser = Authorizer::form("POST", uri, client, None, &options, crate::HMAC_SHA1);
+        ser.serialize_parameter("x_verify_credentials_authorization", &*inner);
+        let form = ser.end();
+        let value = form
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("x_verify_credentials_authorization="))
+            .unwrap();
+        assert_eq!(value, encoded);
+        assert_eq!(
+            percent_encoding::percent_decode_str(value)
+                .decode_utf8()
+                .unwrap(),
+            inner,
+        );
+
+        let mut base_string = String::new();
+        let sm = BaseString::new(&mut base_string, "HMAC-SHA1");
+        let mut ser = Authorizer::form("POST", uri, client, None, &options, sm);
+        ser.serialize_parameter("x_verify_credentials_authorization", &*inner);
+        ser.end();
+        let expected = format!(
+            "&x_verify_credentials_authorization%3D{}",
+            percent_encode(&encoded),
+        );
+        assert!(base_string.ends_with(&expected), "{}", base_string);
+    }
+
+    #[cfg(feature = "idna")]
+    #[test]
+    fn idna() {
+        use core::num::NonZeroU64;
+
+        use crate::signature_method::BaseString;
+        use crate::Request;
+
+        fn base_string(uri: &str) -> String {
+            let client = Credentials::new(CK, CS);
+            let mut options = auth::Options::new();
+            options.nonce("nonce").timestamp(NonZeroU64::new(1));
+            let mut ret = String::new();
+            let sm = BaseString::new(&mut ret, "PLAINTEXT");
+            ().serialize(Authorizer::authorization(
+                "GET", uri, client, None, &options, sm,
+            ));
+            ret
+        }
+
+        let expected = base_string("http://xn--r8jz45g.jp/?q=%E4%BE%8B");
+        assert!(expected.starts_with("GET&http%3A%2F%2Fxn--r8jz45g.jp%2F&"));
+        assert_eq!(base_string("http://例え.jp/?q=%E4%BE%8B"), expected);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "parameter key contains a non-ASCII or reserved character\
+                               \n key: `\"filter[name]\"`")]
+    fn panic_on_reserved_key() {
+        let mut ser = Urlencoder::form_with_buf(String::new());
+        ser.serialize_parameter("filter[name]", "foo");
+    }
+
+    #[cfg(not(feature = "default-nonce"))]
+    #[test]
+    #[should_panic(expected = "without the `default-nonce` feature")]
+    fn panic_on_missing_nonce() {
+        use crate::Credentials;
+
+        let options = auth::Options::new();
+        let mut ser = Authorizer::authorization_with_buf(
+            String::new(),
+            "GET",
+            "https://example.com/get.json",
+            Credentials::new("consumer_key", "consumer_secret"),
+            None,
+            &options,
+            Plaintext::<String>::with_buf(),
+        );
+        ser.serialize_oauth_nonce();
+    }
+
+    #[cfg(all(feature = "alloc", debug_assertions))]
+    #[test]
+    #[should_panic(expected = "`uri` must be an absolute URI with a scheme and authority")]
+    fn panic_on_relative_uri() {
+        let client = Credentials::new(CK, CS);
+        let options = auth::Options::new();
+        Authorizer::authorization(
+            "GET",
+            "/1.1/statuses/home_timeline.json",
+            client,
+            None,
+            &options,
+            Plaintext::<String>::with_buf(),
+        );
+    }
+
+    #[cfg(all(feature = "alloc", debug_assertions))]
+    #[test]
+    #[should_panic(expected = "`uri` must not contain an `oauth_*` query parameter")]
+    fn panic_on_oauth_query() {
+        let client = Credentials::new(CK, CS);
+        let options = auth::Options::new();
+        Authorizer::authorization(
+            "GET",
+            "https://example.com/get.json?oauth_token=foo",
+            client,
+            None,
+            &options,
+            Plaintext::<String>::with_buf(),
+        );
+    }
+
+    #[cfg(all(feature = "default-nonce", feature = "std"))]
+    #[test]
+    fn nonce_rng() {
+        use std::sync::Mutex;
+
+        use rand::{CryptoRng, RngCore};
+
+        struct OnesRng;
+        impl RngCore for OnesRng {
+            fn next_u32(&mut self) -> u32 {
+                u32::from_ne_bytes([1; 4])
+            }
+            fn next_u64(&mut self) -> u64 {
+                u64::from_ne_bytes([1; 8])
+            }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                dest.iter_mut().for_each(|b| *b = 1);
+            }
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+        impl CryptoRng for OnesRng {}
+
+        let rng = Mutex::new(OnesRng);
+        let mut options = auth::Options::new();
+        options.nonce_generator(&rng as &dyn auth::NonceGenerator);
+        let client = Credentials::new(CK, CS);
+        let mut ser = Authorizer::authorization_with_buf(
+            String::new(),
+            "GET",
+            "https://example.com/get.json",
+            client,
+            None,
+            &options,
+            Plaintext::<String>::with_buf(),
+        );
+        ser.serialize_oauth_nonce();
+        assert!(ser.end().contains("oauth_nonce=\"AQEBAQEBAQEB\","));
+    }
+
+    #[cfg(feature = "default-nonce")]
+    #[test]
+    fn nonce_config() {
+        use std::format;
+
+        use auth::{NonceAlphabet, NonceConfig};
+
+        const ALPHANUMERIC: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+        for &(len, alphabet, chars) in &[
+            (32, NonceAlphabet::Hex, "0123456789abcdef"),
+            (1, NonceAlphabet::AlphaNumeric, ALPHANUMERIC),
+            (
+                64,
+                NonceAlphabet::Base64Url,
+                &*format!("{}-_", ALPHANUMERIC),
+            ),
+        ] {
+            let mut options = auth::Options::new();
+            options.nonce_config(NonceConfig::new(len, alphabet));
+            let mut ser = Authorizer::authorization_with_buf(
+                String::new(),
+                "GET",
+                "https://example.com/get.json",
+                Credentials::new(CK, CS),
+                None,
+                &options,
+                Plaintext::<String>::with_buf(),
+            );
+            ser.serialize_oauth_nonce();
+            let header = ser.end();
+            let nonce = header
+                .split("oauth_nonce=\"")
+                .nth(1)
+                .and_then(|s| s.split('"').next())
+                .unwrap();
+            assert_eq!(nonce.len(), len);
+            assert!(
+                nonce.chars().all(|c| chars.contains(c)),
+                "nonce = {:?}",
+                nonce
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "nonce length must be between 1 and 64")]
+    fn panic_on_long_nonce_config() {
+        auth::NonceConfig::new(65, auth::NonceAlphabet::Hex);
+    }
+
+    #[cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+    #[test]
+    fn empty_token() {
+        use core::num::NonZeroU64;
+
+        use crate::Request;
+
+        let mut options = auth::Options::new();
+        options.nonce(NONCE).timestamp(NonZeroU64::new(TIMESTAMP));
+        let authorize = |token| {
+            let client = Credentials::new(CK, CS);
+            let ser = Authorizer::authorization(
+                "POST",
+                "https://example.com/request_token",
+                client,
+                token,
+                &options,
+                crate::HMAC_SHA1,
+            );
+            ().serialize(ser)
+        };
+
+        let absent = authorize(None);
+        let empty = authorize(Some(Credentials::new("", "")));
+
+        assert!(!absent.contains("oauth_token="));
+        assert!(empty.contains(",oauth_token=\"\","));
+        // The `oauth_token` parameter is part of the signature base string.
+        let signature = |authorization: &str| {
+            let i = authorization.find("oauth_signature=").unwrap();
+            authorization[i..].to_string()
+        };
+        assert_ne!(signature(&absent), signature(&empty));
+    }
+
     #[cfg(all(feature = "alloc", debug_assertions))]
     #[test]
     #[should_panic(
@@ -1,9 +1,14 @@
 //! An OAuth 1.0 `Authorization` header serializer.
 
-use core::fmt::{self, Display, Write};
+#[cfg(feature = "alloc")]
+use alloc::string::ToString;
+use core::fmt::{self, Debug, Display, Write};
+#[cfg(any(feature = "alloc", feature = "tracing"))]
+use core::mem;
 use core::num::NonZeroU64;
 use core::str;
 
+#[cfg(feature = "default-nonce")]
 use rand::prelude::*;
 
 use crate::signature_method::{Sign, SignatureMethod};
@@ -18,6 +23,22 @@ cfg_type_param_hack! {
     /// The resulting parameter values are either written to an HTTP `Authorization` header value or
     /// URI query/`x-www-form-urlencoded` string (along with the other request parameters)
     /// depending on the constructor you use.
+    ///
+    /// The request URI passed to the constructors must be an absolute URI like
+    /// `https://example.com/path`, and the constructors panic in debug builds otherwise.
+    ///
+    /// With the `tracing` feature, the serializer emits two `TRACE` level spans carrying
+    /// the signature method name and the number of parameters: `base_string`, which lasts from
+    /// the construction of the serializer until all the parameters are serialized, and `sign`,
+    /// which covers the finalization of the signature.
+    ///
+    /// If a `Display` implementation of a parameter value panics, the output and the signing
+    /// state of the `Authorizer` are left incomplete. Do not use it further unless you `reset` it,
+    /// which requires the `alloc` feature.
+    ///
+    /// With the `log` feature, the serializer emits a `DEBUG` level log entry on completion with
+    /// the HTTP request method, the request URI without the query part, the number of parameters
+    /// and the signature method name. The parameter values and the signature are never logged.
     #[derive(Clone, Debug)]
     pub struct Authorizer<
         'a,
@@ -31,29 +52,240 @@ cfg_type_param_hack! {
         data: Data<W>,
         sign: SM::Sign,
         append_delim_to_sign: bool,
+        #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+        query: Query,
+        // Whether `data` is a URI with a query part being appended, as opposed to a form string.
+        is_query: bool,
         #[cfg(all(feature = "alloc", debug_assertions))]
         prev_key: alloc::string::String,
+        #[cfg(feature = "tracing")]
+        span: tracing01::Span,
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        parameters: usize,
+        // The HTTP request method and the request URI to log, if `DEBUG` logs are enabled.
+        #[cfg(feature = "log")]
+        request_line: Option<alloc::string::String>,
+    }
+}
+
+/// A strategy of generating `oauth_nonce` values, like a counter or a UUID generator.
+///
+/// A generator is shared by reference across signing processes, which may run on different
+/// threads, so it takes `&self` and is required to be `Sync`. Use an atomic or a `Mutex` to hold
+/// any mutable state.
+///
+/// With the `default-nonce` and `std` features, `Mutex<R>` implements this trait for any
+/// cryptographically secure RNG `R`, generating random nonces in the format set with
+/// `nonce_config`.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use std::fmt::{self, Write};
+/// use std::sync::atomic::{AtomicU64, Ordering};
+///
+/// use oauth::serializer::auth::{NonceConfig, NonceGenerator};
+///
+/// struct Counter(AtomicU64);
+///
+/// impl NonceGenerator for Counter {
+///     fn generate(&self, _: Option<NonceConfig>, w: &mut dyn Write) -> fmt::Result {
+///         write!(w, "{}", self.0.fetch_add(1, Ordering::Relaxed))
+///     }
+/// }
+/// ```
+pub trait NonceGenerator: Sync {
+    /// Writes a new nonce value to `w`.
+    ///
+    /// `config` is the value set with `nonce_config`, if any, which the generator may ignore.
+    /// The value is percent encoded by the serializer, and must not be longer than
+    /// [`NonceConfig::MAX_LEN`] bytes.
+    fn generate(&self, config: Option<NonceConfig>, w: &mut dyn Write) -> fmt::Result;
+}
+
+/// The length and character set of `oauth_nonce` values generated by the serializer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NonceConfig {
+    len: usize,
+    alphabet: NonceAlphabet,
+}
+
+/// A character set of generated `oauth_nonce` values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NonceAlphabet {
+    /// Lowercase hexadecimal digits (`0-9a-f`).
+    Hex,
+    /// ASCII alphanumeric characters (`0-9A-Za-z`).
+    AlphaNumeric,
+    /// The URL-safe Base64 alphabet (`A-Za-z0-9-_`).
+    Base64Url,
+}
+
+doc_auto_cfg! {
+    /// A `Serializer` that produces the normalized request parameters string
+    /// ([RFC 5849 section 3.4.1.3.2.][rfc]) of a request, i.e. the parameters component of
+    /// the signature base string before the whole component is percent encoded.
+    ///
+    /// The output can be cached and combined with different HTTP request methods and URIs to
+    /// build signature base strings without serializing the request again.
+    ///
+    /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1.3.2
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+    #[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// use std::num::NonZeroU64;
+    ///
+    /// use oauth::serializer::auth::{self, ParameterStringSerializer};
+    /// use oauth::Request;
+    ///
+    /// // Based on the example of RFC 5849 section 3.4.1.3.2.
+    /// let client = oauth::Credentials::new("9djdj82h48djs9d2", "");
+    /// let token = oauth::Credentials::new("kkk9d7dh3k39sjv7", "");
+    /// let mut options = auth::Options::new();
+    /// options.nonce("7d8f3e4a").timestamp(NonZeroU64::new(137131201));
+    /// let request = oauth::ParameterList::new([
+    ///     ("a2", "r b"),
+    ///     ("a3", "2 q"),
+    ///     ("a3", "a"),
+    ///     ("b5", "=%3D"),
+    ///     ("c2", ""),
+    /// ]);
+    ///
+    /// let serializer = ParameterStringSerializer::with_oauth_parameters(
+    ///     client,
+    ///     Some(token),
+    ///     &options,
+    ///     oauth::HMAC_SHA1,
+    /// );
+    /// assert_eq!(
+    ///     request.serialize(serializer),
+    ///     "a2=r%20b&a3=2%20q&a3=a&b5=%3D%253D&c2=&\
+    ///      oauth_consumer_key=9djdj82h48djs9d2&oauth_nonce=7d8f3e4a&\
+    ///      oauth_signature_method=HMAC-SHA1&oauth_timestamp=137131201&\
+    ///      oauth_token=kkk9d7dh3k39sjv7",
+    /// );
+    ///
+    /// // Without the OAuth protocol parameters:
+    /// let serializer = ParameterStringSerializer::new();
+    /// assert_eq!(
+    ///     request.serialize(serializer),
+    ///     "a2=r%20b&a3=2%20q&a3=a&b5=%3D%253D&c2=",
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[derive(Clone, Debug)]
+    pub struct ParameterStringSerializer<'a> {
+        inner: ParameterStringInner<'a>,
     }
 }
 
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+enum ParameterStringInner<'a> {
+    Request(Urlencoder),
+    OAuth(Authorizer<'a, SignatureMethodInfo>),
+}
+
+/// A pseudo signature method that only provides the information of another signature method
+/// that affects the OAuth protocol parameters, leaving the signature empty.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug)]
+struct SignatureMethodInfo {
+    name: &'static str,
+    use_nonce: bool,
+    use_timestamp: bool,
+}
+
 #[derive(Clone, Debug)]
 enum Data<W> {
     Authorization(W),
     Urlencode(Urlencoder<W>),
 }
 
+// Query parameters of the request URI that are yet to be folded into the signature base string.
+#[cfg(feature = "alloc")]
+type Query = QueryParameters;
+#[cfg(not(feature = "alloc"))]
+type Query = ();
+
 options! {
     /// Optional OAuth parameters.
     #[derive(Clone, Debug, Default)]
     pub struct Options<'a> {
         /// Creates a blank `Options` with default values (`None`).
         new;
+        /// Sets `realm` parameter of the `Authorization` header.
+        ///
+        /// The `realm` parameter is not included in the signature base string
+        /// ([RFC 5849 section 3.4.1.3.1.][rfc]) and is only written to the `Authorization` header,
+        /// so this has no effect on the URI query or form string serializers.
+        ///
+        /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1.3.1
+        realm: Option<&'a str>,
+        /// Sets whether to percent encode the `realm` value.
+        ///
+        /// The OAuth standard is ambiguous on whether the `realm` value should be percent encoded
+        /// and server implementations differ. By default, the value is written as is, only
+        /// wrapped in double quotes, in which case the value must not contain a `"` or `\`
+        /// character.
+        encode_realm: bool,
+        /// Sets `oauth_body_hash` parameter of the [OAuth Request Body Hash][body-hash]
+        /// extension.
+        ///
+        /// The value is the Base64-encoded hash of the request body, and is percent encoded by
+        /// the serializer.
+        ///
+        /// [body-hash]: https://datatracker.ietf.org/doc/html/draft-eaton-oauth-bodyhash-00
+        body_hash: Option<&'a str>,
         /// Sets `oauth_callback` parameter.
         callback: Option<&'a str>,
         /// Sets `oauth_verifier` parameter.
         verifier: Option<&'a str>,
+        /// Sets whether to leave `oauth_verifier` parameter out of the `Authorization` header.
+        ///
+        /// Some providers expect the `oauth_verifier` value of the access token request in
+        /// the `x-www-form-urlencoded` request body instead of the `Authorization` header.
+        /// Setting this to `true` omits the parameter from the header while still including it
+        /// in the signature, and you should send `oauth_verifier=<verifier>` in the request body
+        /// by yourself.
+        ///
+        /// This has no effect on the URI query or form string serializers, which always write
+        /// the parameter along with the other parameters.
+        verifier_in_body: bool,
+        /// Sets whether to panic if both `oauth_callback` and `oauth_verifier` are set.
+        ///
+        /// No single step of the authorization flow uses both parameters, so setting both is
+        /// almost certainly a leftover from a previous step. By default, this is only checked
+        /// with a debug assertion. Setting this to `true` makes the serializer panic in release
+        /// builds as well.
+        strict_flow: bool,
         /// Sets `oauth_nonce` parameter.
+        ///
+        /// If this is not set, the serializer generates a nonce with `nonce_generator` if set,
+        /// or a random nonce otherwise, which requires the `default-nonce` feature. Without
+        /// the feature, the serializer panics if the signature method uses a nonce and neither
+        /// of them is set.
         nonce: Option<&'a str>,
+        /// Sets the generator to generate `oauth_nonce` values with.
+        ///
+        /// This has no effect if `nonce` is set.
+        ///
+        /// By default, the nonces are generated with `rand::rngs::OsRng` (i.e. the `getrandom`
+        /// crate), which requires the `default-nonce` feature.
+        nonce_generator: Option<&'a dyn NonceGenerator>,
+        /// Sets the length and character set of generated `oauth_nonce` values.
+        ///
+        /// This has no effect if `nonce` is set.
+        ///
+        /// By default, the nonces are 12 (or less) characters long and consist of the URL-safe
+        /// Base64 alphabet.
+        nonce_config: Option<NonceConfig>,
         /// Sets `oauth_timestamp` parameter.
         ///
         /// The OAuth standard ([RFC 5849 section 3.3.][rfc]) says that the timestamp value
@@ -63,20 +295,78 @@ options! {
         timestamp: Option<NonZeroU64>,
         /// Sets whether to include `oauth_version="1.0"` parameter in the `Authorization` header.
         version: bool,
+        /// Sets whether to keep the query part of the request URI in the base string URI.
+        ///
+        /// By default, the query parameters of the request URI are folded out of the base string
+        /// URI and into the request parameters as the OAuth standard
+        /// ([RFC 5849 section 3.4.1.2.][rfc]) requires. Setting this to `true` keeps the query
+        /// part in the base string URI while still adding the query parameters to the request
+        /// parameters, which a few nonconforming providers expect.
+        ///
+        /// This has no effect without the `alloc` feature, in which case the request URI must not
+        /// contain a query part.
+        ///
+        /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1.2
+        keep_query_in_uri: bool,
+        /// Sets whether to strip `oauth_*` parameters in the query part of the request URI.
+        ///
+        /// A request URI containing `oauth_*` query parameters, like a URI copied from
+        /// a previously signed request, would result in duplicate protocol parameters and thus
+        /// in an invalid signature. By default, the serializer panics on such a URI in debug
+        /// builds and signs the parameters as they are in release builds, and `Builder::try_build`
+        /// returns an error on it. Setting this to `true` excludes those parameters from
+        /// the signature instead. Note that this does not remove them from the URI itself, so you
+        /// should remove them before sending the request.
+        ///
+        /// This has no effect without the `alloc` feature.
+        strip_oauth_query: bool,
+        /// Sets whether to trim leading and trailing ASCII whitespace from the request parameter
+        /// values.
+        ///
+        /// The OAuth standard requires the values to be signed as is, but some nonconforming
+        /// servers trim the values before verifying the signature. Setting this to `true` trims
+        /// the values both in the signature and in the URI query or form string produced by
+        /// the serializer. This applies to the values passed to `serialize_parameter`, and not to
+        /// the already encoded values nor the query part of the request URI.
+        ///
+        /// Note that the parameters are still expected to be sorted by their untrimmed values.
+        trim_values: bool,
+        /// Sets the set of characters to percent encode in the request parameter values.
+        ///
+        /// Defaults to [`EncodeSet::RFC5849`], which is what the OAuth standard requires.
+        /// Changing this is only useful for talking to nonconforming servers that encode
+        /// the signature base string differently. The set applies both to the signature and to
+        /// the URI query or form string produced by the serializer, while the protocol parameters
+        /// are always encoded with the default set.
+        encode_set: EncodeSet,
+        /// Sets whether to strictly follow the signature base string construction of the OAuth
+        /// standard ([RFC 5849 section 3.4.1.][rfc]).
+        ///
+        /// Regardless of this option, the serializer always excludes the fragment of the request
+        /// URI, lowercases its scheme and host and folds its query parameters into
+        /// the request parameters. Setting this to `true` additionally:
+        ///
+        /// - removes the port from the base string URI if it is the default port of the scheme
+        ///   (`80` for `http` and `443` for `https`) or empty,
+        /// - overrides `keep_query_in_uri` and `trim_values` with `false`, and
+        /// - overrides `encode_set` with [`EncodeSet::RFC5849`].
+        ///
+        /// Note that this does not affect the options of the signature method, like
+        /// `HmacSha1::url_safe_base64`.
+        ///
+        /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1
+        rfc_strict: bool,
     }
 }
 
 doc_auto_cfg! {
     #[cfg(feature = "alloc")]
     impl<'a, SM: SignatureMethod> Authorizer<'a, SM> {
-        /// Creates an `Authorizer` that produces an HTTP `Authorization header value.
-        ///
-        /// `uri` must not contain a query part.
-        /// Otherwise, the serializer will produce a wrong signature.
-        ///
-        /// # Panics
+        /// Creates an `Authorizer` that produces an HTTP `Authorization` header value.
         ///
-        /// In debug builds, panics if `uri` contains a `'?'` character.
+        /// The query parameters of `uri`, if any, are folded into the signature. The output
+        /// buffer is allocated at once, with a capacity estimated from the lengths of the OAuth
+        /// protocol parameters, which are the only parameters written to the header.
         pub fn authorization<T: Display>(
             method: &str,
             uri: T,
@@ -85,7 +375,8 @@ doc_auto_cfg! {
             options: &'a Options<'a>,
             signature_method: SM,
         ) -> Self {
-            let buf = alloc::string::String::with_capacity(512);
+            let capacity = authorization_capacity(client, token, options);
+            let buf = alloc::string::String::with_capacity(capacity);
             Authorizer::authorization_with_buf(
                 buf,
                 method,
@@ -99,12 +390,7 @@ doc_auto_cfg! {
 
         /// Creates an `Authorizer` that produces an `x-www-form-urlencoded` string.
         ///
-        /// `uri` must not contain a query part.
-        /// Otherwise, the serializer will produce a wrong signature.
-        ///
-        /// # Panics
-        ///
-        /// In debug builds, panics if `uri` contains a `'?'` character.
+        /// The query parameters of `uri`, if any, are folded into the signature.
         pub fn form<T: Display>(
             method: &str,
             uri: T,
@@ -119,15 +405,90 @@ doc_auto_cfg! {
     }
 }
 
+doc_auto_cfg! {
+    #[cfg(feature = "alloc")]
+    impl<'a, SM: SignatureMethod> Authorizer<'a, SM> {
+        /// Resets the `Authorizer` to sign another request, reusing its output buffer.
+        ///
+        /// This discards the parameters serialized so far, clears the output buffer and starts
+        /// over with the given arguments, producing the same kind of output (`Authorization`
+        /// header value, `x-www-form-urlencoded` string or URI query) as before. An `Authorizer`
+        /// created with `query` writes `uri` to the buffer and appends the query part to it.
+        ///
+        /// Only the allocation of the output buffer is kept. The signing state is created anew
+        /// from `signature_method`, so a reset `Authorizer` produces the same output as a freshly
+        /// constructed one.
+        ///
+        /// This also makes it safe to reuse an `Authorizer` after a panic during serialization
+        /// (e.g. from a `Display` implementation of a parameter value) has been caught with
+        /// `std::panic::catch_unwind`: the partially written output and the partially fed
+        /// signing state are discarded. If this method itself panics,
+        /// the `Authorizer` keeps the kind of its output but loses the allocation of its buffer.
+        pub fn reset<T: Display>(
+            &mut self,
+            method: &str,
+            uri: T,
+            client: Credentials<&'a str>,
+            token: Option<Credentials<&'a str>>,
+            options: &'a Options<'a>,
+            signature_method: SM,
+        ) {
+            // Keep the kind of the output in case the constructors below panic.
+            let placeholder = match self.data {
+                Data::Authorization(_) => Data::Authorization(alloc::string::String::new()),
+                Data::Urlencode(_) => {
+                    Data::Urlencode(Urlencoder::form_with_buf(alloc::string::String::new()))
+                }
+            };
+            let data = mem::replace(&mut self.data, placeholder);
+            *self = match data {
+                Data::Authorization(mut buf) => {
+                    buf.clear();
+                    Authorizer::authorization_with_buf(
+                        buf,
+                        method,
+                        uri,
+                        client,
+                        token,
+                        options,
+                        signature_method,
+                    )
+                }
+                Data::Urlencode(encoder) => {
+                    let mut buf = encoder.end();
+                    buf.clear();
+                    if self.is_query {
+                        write!(buf, "{}", uri).unwrap();
+                        Authorizer::query(method, buf, client, token, options, signature_method)
+                    } else {
+                        Authorizer::form_with_buf(
+                            buf,
+                            method,
+                            uri,
+                            client,
+                            token,
+                            options,
+                            signature_method,
+                        )
+                    }
+                }
+            };
+        }
+    }
+}
+
 impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, W> {
     /// Creates an `Authorizer` that appends a query part to `uri`.
     ///
-    /// `uri` must not contain a query part.
-    /// Otherwise, the serializer will produce a wrong signature.
+    /// The query parameters of `uri`, if any, are folded into the signature and the OAuth protocol
+    /// parameters are appended to the existing query part.
+    /// Without the `alloc` feature, `uri` must not contain a query part and the serializer
+    /// will produce a wrong signature otherwise.
     ///
     /// # Panics
     ///
-    /// In debug builds, panics if `uri` contains a `'?'` character.
+    /// Panics in debug builds if `uri` contains a fragment part (a `'#'` character).
+    /// Without the `alloc` feature, panics in debug builds if `uri` contains a `'?'` character.
     pub fn query(
         method: &str,
         uri: W,
@@ -139,9 +500,32 @@ impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, W> {
     where
         W: Display,
     {
-        let sign = make_sign(method, &uri, client, token, signature_method);
-        let data = Data::Urlencode(Urlencoder::query(uri));
-        Authorizer::new_(data, sign, client, token, options)
+        debug_assert!(
+            !contains_fragment(&uri),
+            "`uri` must not contain a fragment, which would end up before the appended query part",
+        );
+        #[cfg(feature = "log")]
+        let request_line = request_line(method, &uri);
+        let (sign, query) = make_sign(method, &uri, client, token, options, signature_method);
+        let encoder = if contains_query(&uri) {
+            Urlencoder::append_query(uri)
+        } else {
+            Urlencoder::query(uri)
+        };
+        let mut ret = Authorizer::new_(
+            Data::Urlencode(encoder),
+            sign,
+            query,
+            client,
+            token,
+            options,
+        );
+        ret.is_query = true;
+        #[cfg(feature = "log")]
+        {
+            ret.request_line = request_line;
+        }
+        ret
     }
 
     /// Same as `authorization` except that this writes the resulting `Authorization` header value
@@ -156,9 +540,28 @@ impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, W> {
         signature_method: SM,
     ) -> Self {
         buf.write_str("OAuth ").unwrap();
+        if let Some(realm) = options.realm {
+            if options.encode_realm {
+                write!(buf, r#"realm="{}","#, percent_encode(realm)).unwrap();
+            } else {
+                debug_assert!(
+                    !realm.contains(&['"', '\\'][..]),
+                    "`realm` must not contain a `\"` or `\\` character unless `encode_realm` is set",
+                );
+                write!(buf, r#"realm="{}","#, realm).unwrap();
+            }
+        }
         let data = Data::Authorization(buf);
-        let sign = make_sign(method, uri, client, token, signature_method);
-        Authorizer::new_(data, sign, client, token, options)
+        #[cfg(feature = "log")]
+        let request_line = request_line(method, &uri);
+        let (sign, query) = make_sign(method, uri, client, token, options, signature_method);
+        #[allow(unused_mut, clippy::let_and_return)]
+        let mut ret = Authorizer::new_(data, sign, query, client, token, options);
+        #[cfg(feature = "log")]
+        {
+            ret.request_line = request_line;
+        }
+        ret
     }
 
     /// Same with `form` except that this writes the resulting form string into `buf`.
@@ -172,40 +575,240 @@ impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, W> {
         signature_method: SM,
     ) -> Self {
         let data = Data::Urlencode(Urlencoder::form_with_buf(buf));
-        let sign = make_sign(method, uri, client, token, signature_method);
-        Authorizer::new_(data, sign, client, token, options)
+        #[cfg(feature = "log")]
+        let request_line = request_line(method, &uri);
+        let (sign, query) = make_sign(method, uri, client, token, options, signature_method);
+        #[allow(unused_mut, clippy::let_and_return)]
+        let mut ret = Authorizer::new_(data, sign, query, client, token, options);
+        #[cfg(feature = "log")]
+        {
+            ret.request_line = request_line;
+        }
+        ret
     }
 
     fn new_(
         data: Data<W>,
         sign: SM::Sign,
+        query: Query,
         client: Credentials<&'a str>,
         token: Option<Credentials<&'a str>>,
         options: &'a Options<'a>,
     ) -> Self {
-        cfg_if::cfg_if! {
-            if #[cfg(all(feature = "alloc", debug_assertions))] {
-                Authorizer {
-                    consumer_key: client.identifier,
-                    token: token.map(|t| t.identifier),
-                    options,
-                    data,
-                    sign,
-                    append_delim_to_sign: false,
-                    prev_key: alloc::string::String::new(),
-                }
+        let mut data = data;
+        if let Data::Urlencode(ref mut encoder) = data {
+            encoder.encode_set(options.effective_encode_set());
+        }
+        #[cfg(feature = "tracing")]
+        let span = tracing01::trace_span!(
+            "base_string",
+            signature_method = sign.get_signature_method_name(),
+            parameters = tracing01::field::Empty,
+        );
+        Authorizer {
+            consumer_key: client.identifier,
+            token: token.map(|t| t.identifier),
+            options,
+            data,
+            sign,
+            append_delim_to_sign: false,
+            query,
+            is_query: false,
+            #[cfg(all(feature = "alloc", debug_assertions))]
+            prev_key: alloc::string::String::new(),
+            #[cfg(feature = "tracing")]
+            span,
+            #[cfg(any(feature = "log", feature = "tracing"))]
+            parameters: 0,
+            #[cfg(feature = "log")]
+            request_line: None,
+        }
+    }
+}
+
+#[cfg(all(feature = "default-nonce", feature = "std"))]
+impl<R: RngCore + CryptoRng + Send> NonceGenerator for std::sync::Mutex<R> {
+    fn generate(&self, config: Option<NonceConfig>, w: &mut dyn Write) -> fmt::Result {
+        let mut buf = [0; MAX_NONCE_LEN];
+        // A panic while holding the lock cannot leave the RNG in an invalid state.
+        let mut rng = self.lock().unwrap_or_else(|e| e.into_inner());
+        w.write_str(gen_nonce(&mut buf, config, &mut *rng))
+    }
+}
+
+impl NonceConfig {
+    /// The maximum length of generated `oauth_nonce` values.
+    pub const MAX_LEN: usize = MAX_NONCE_LEN;
+
+    /// Creates a new `NonceConfig` that generates `len` characters long nonces consisting of
+    /// `alphabet`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is zero or greater than [`NonceConfig::MAX_LEN`].
+    pub fn new(len: usize, alphabet: NonceAlphabet) -> Self {
+        assert!(
+            0 < len && len <= MAX_NONCE_LEN,
+            "nonce length must be between 1 and {}",
+            MAX_NONCE_LEN,
+        );
+        NonceConfig { len, alphabet }
+    }
+}
+
+#[cfg(feature = "default-nonce")]
+impl NonceAlphabet {
+    fn chars(self) -> &'static [u8] {
+        match self {
+            NonceAlphabet::Hex => b"0123456789abcdef",
+            NonceAlphabet::AlphaNumeric => {
+                b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz"
+            }
+            NonceAlphabet::Base64Url => {
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"
+            }
+        }
+    }
+}
+
+impl<'a> Options<'a> {
+    #[cfg(feature = "alloc")]
+    fn effective_keep_query_in_uri(&self) -> bool {
+        self.keep_query_in_uri && !self.rfc_strict
+    }
+
+    /// Returns the `realm` value as written to the `Authorization` header.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn header_realm(&self) -> Option<alloc::string::String> {
+        self.realm.map(|realm| {
+            if self.encode_realm {
+                percent_encode(realm).to_string()
             } else {
-                Authorizer {
-                    consumer_key: client.identifier,
-                    token: token.map(|t| t.identifier),
-                    options,
-                    data,
-                    sign,
-                    append_delim_to_sign: false,
-                }
+                realm.to_string()
             }
+        })
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn sends_verifier_in_body(&self) -> bool {
+        self.verifier_in_body
+    }
+
+    pub(crate) fn effective_trim_values(&self) -> bool {
+        self.trim_values && !self.rfc_strict
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn strips_oauth_query(&self) -> bool {
+        self.strip_oauth_query
+    }
+
+    pub(crate) fn effective_encode_set(&self) -> EncodeSet {
+        if self.rfc_strict {
+            EncodeSet::RFC5849
+        } else {
+            self.encode_set
         }
     }
+
+    /// Returns a `Debug` view of the options that only shows whether the `oauth_callback`,
+    /// `oauth_verifier` and `oauth_nonce` values are set.
+    pub(crate) fn redacted(&self) -> RedactedOptions<'_, 'a> {
+        RedactedOptions(self)
+    }
+}
+
+pub(crate) struct RedactedOptions<'b, 'a>(&'b Options<'a>);
+
+impl<'b, 'a> Debug for RedactedOptions<'b, 'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let o = self.0;
+        f.debug_struct("Options")
+            .field("realm", &o.realm)
+            .field("encode_realm", &o.encode_realm)
+            .field("body_hash", &o.body_hash)
+            .field("callback", &o.callback.is_some())
+            .field("verifier", &o.verifier.is_some())
+            .field("verifier_in_body", &o.verifier_in_body)
+            .field("strict_flow", &o.strict_flow)
+            .field("nonce", &o.nonce.is_some())
+            .field("nonce_generator", &o.nonce_generator.is_some())
+            .field("nonce_config", &o.nonce_config)
+            .field("timestamp", &o.timestamp)
+            .field("version", &o.version)
+            .field("keep_query_in_uri", &o.keep_query_in_uri)
+            .field("strip_oauth_query", &o.strip_oauth_query)
+            .field("trim_values", &o.trim_values)
+            .field("encode_set", &o.encode_set)
+            .field("rfc_strict", &o.rfc_strict)
+            .finish()
+    }
+}
+
+impl<'a> Debug for dyn NonceGenerator + 'a {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NonceGenerator").finish()
+    }
+}
+
+/// Formats the HTTP request method and the request URI without the query and fragment parts
+/// for logging, returning `None` if `DEBUG` logs are disabled.
+#[cfg(feature = "log")]
+fn request_line(method: &str, uri: &dyn Display) -> Option<alloc::string::String> {
+    if !log04::log_enabled!(log04::Level::Debug) {
+        return None;
+    }
+    let uri = uri.to_string();
+    let end = uri.find(&['?', '#'][..]).unwrap_or(uri.len());
+    Some(alloc::format!("{} {}", method, &uri[..end]))
+}
+
+/// Estimates the length of the `Authorization` header value so that its buffer can be allocated at
+/// once.
+///
+/// This is a counting pass over the OAuth protocol parameters, which are the only parameters written
+/// to the header. The values that are not known in advance are assumed to be as long as the longest
+/// value the crate generates, i.e. a 20 digit timestamp, a nonce of the configured length and
+/// a percent encoded `HMAC-SHA512` signature.
+#[cfg(feature = "alloc")]
+fn authorization_capacity(
+    client: Credentials<&str>,
+    token: Option<Credentials<&str>>,
+    options: &Options<'_>,
+) -> usize {
+    // The length of a Base64-encoded SHA-512 digest, with up to two padding characters encoded
+    // into `%3D` and some room for the percent-encoded `+` and `/` characters.
+    const SIGNATURE_LEN: usize = 88 + 4 + 16;
+    const TIMESTAMP_LEN: usize = 20;
+
+    let mut w = CountWrite(0);
+    w.write_str("OAuth ").unwrap();
+    if let Some(realm) = options.realm {
+        write!(w, r#"realm="{}","#, percent_encode(realm)).unwrap();
+    }
+    let parameters = [
+        ("oauth_body_hash", options.body_hash),
+        ("oauth_callback", options.callback),
+        ("oauth_consumer_key", Some(client.identifier)),
+        ("oauth_nonce", Some(options.nonce.unwrap_or(""))),
+        ("oauth_signature", Some("")),
+        ("oauth_signature_method", Some("HMAC-SHA512")),
+        ("oauth_timestamp", Some("")),
+        ("oauth_token", token.map(|t| t.identifier)),
+        ("oauth_verifier", options.verifier),
+        ("oauth_version", Some("1.0")),
+    ];
+    for &(k, v) in &parameters {
+        if let Some(v) = v {
+            write!(w, r#"{}="{}","#, k, percent_encode(v)).unwrap();
+        }
+    }
+
+    let nonce_len = match options.nonce {
+        Some(_) => 0,
+        None => options.nonce_config.map_or(NONCE_LEN, |config| config.len),
+    };
+    w.0 + SIGNATURE_LEN + nonce_len + TIMESTAMP_LEN
 }
 
 fn make_sign<SM: SignatureMethod, T: Display>(
@@ -213,40 +816,223 @@ fn make_sign<SM: SignatureMethod, T: Display>(
     uri: T,
     client: Credentials<&str>,
     token: Option<Credentials<&str>>,
+    options: &Options<'_>,
     signature_method: SM,
-) -> SM::Sign {
-    // This is a no_alloc-equivalent of `assert!(!uri.to_string().contains('?'))`.
-    // We can determine if the URI contains a query part by just checking if it containsa `'?'`
-    // character, because the scheme and authority part of a valid URI does not contain
-    // that character.
-    #[cfg(debug_assertions)]
+) -> (SM::Sign, Query) {
+    debug_assert!(
+        is_absolute_or_empty(&uri),
+        "`uri` must be an absolute URI with a scheme and authority (e.g. `https://example.com/`)",
+    );
+
+    const BOTH_CALLBACK_AND_VERIFIER: &str =
+        "`oauth_callback` and `oauth_verifier` must not be set at the same time";
+    let both_callback_and_verifier = options.callback.is_some() && options.verifier.is_some();
+    if options.strict_flow {
+        assert!(
+            !both_callback_and_verifier,
+            "{}",
+            BOTH_CALLBACK_AND_VERIFIER
+        );
+    } else {
+        debug_assert!(
+            !both_callback_and_verifier,
+            "{}",
+            BOTH_CALLBACK_AND_VERIFIER
+        );
+    }
+
+    let mut sign = signature_method.sign_with(client.secret, token.map(|t| t.secret));
+    sign.request_method(method);
+
+    // The fragment is not a part of the request URI.
+    let uri = WithoutFragment(uri);
+    // Make the base string URI independent of whether the host is given in Unicode or Punycode.
+    #[cfg(feature = "idna")]
+    let uri = host_to_ascii(uri.to_string());
+    let uri = WithoutDefaultPort(LowercaseSchemeAndHost(uri), options.rfc_strict);
+
+    #[cfg(feature = "alloc")]
+    let query = if contains_query(&uri) {
+        let uri = uri.to_string();
+        let i = uri.find('?').unwrap();
+        if options.effective_keep_query_in_uri() {
+            sign.uri(PercentEncode(&uri));
+        } else {
+            sign.uri(PercentEncode(&uri[..i]));
+        }
+        let mut query = QueryParameters::parse(&uri[i + 1..], options.effective_encode_set());
+        if options.strip_oauth_query {
+            query.strip_oauth_parameters();
+        } else {
+            debug_assert!(
+                !query.contains_oauth_parameters(),
+                "`uri` must not contain an `oauth_*` query parameter"
+            );
+        }
+        query
+    } else {
+        sign.uri(PercentEncode(uri));
+        QueryParameters::default()
+    };
+    #[cfg(not(feature = "alloc"))]
     {
-        struct AssertNotContainQuestion;
-        impl Write for AssertNotContainQuestion {
-            #[track_caller]
-            fn write_str(&mut self, uri: &str) -> fmt::Result {
-                assert!(!uri.contains('?'), "`uri` must not contain a query part");
-                Ok(())
-            }
+        let _ = options;
+        debug_assert!(!contains_query(&uri), "`uri` must not contain a query part");
+        sign.uri(PercentEncode(uri));
+    }
+    #[cfg(not(feature = "alloc"))]
+    let query = ();
+
+    (sign, query)
+}
+
+/// Checks if `uri` contains a query part.
+///
+/// This is a no_alloc-equivalent of `WithoutFragment(uri).to_string().contains('?')`.
+// We can determine if the URI contains a query part by just checking if it contains a `'?'`
+// character before a `'#'` character, because the scheme and authority part of a valid URI does
+// not contain those characters.
+fn contains_query<T: Display>(uri: &T) -> bool {
+    struct ContainsQuestion(bool);
+    impl Write for ContainsQuestion {
+        fn write_str(&mut self, uri: &str) -> fmt::Result {
+            self.0 |= uri.contains('?');
+            Ok(())
         }
-        write!(AssertNotContainQuestion, "{}", uri).unwrap();
     }
+    let mut w = ContainsQuestion(false);
+    write!(w, "{}", WithoutFragment(uri)).unwrap();
+    w.0
+}
 
-    let mut ret = signature_method.sign_with(client.secret, token.map(|t| t.secret));
-    ret.request_method(method);
-    ret.uri(PercentEncode(uri));
+/// Checks if `uri` contains a fragment part.
+fn contains_fragment<T: Display>(uri: &T) -> bool {
+    struct ContainsHash(bool);
+    impl Write for ContainsHash {
+        fn write_str(&mut self, uri: &str) -> fmt::Result {
+            self.0 |= uri.contains('#');
+            Ok(())
+        }
+    }
+    let mut w = ContainsHash(false);
+    write!(w, "{}", uri).unwrap();
+    w.0
+}
 
-    ret
+/// Checks if `uri` is an absolute URI, i.e. begins with a scheme followed by `://`, or is empty.
+///
+/// The base string URI ([RFC 5849 section 3.4.1.2.][rfc]) is made of the scheme, authority and
+/// path of the request URI, so signing a request with a path-only URI yields a signature that
+/// the server would reject.
+///
+/// An empty URI is let through since it is used as a placeholder in some (test) code.
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1.2
+fn is_absolute_or_empty<T: Display>(uri: &T) -> bool {
+    enum State {
+        Start,
+        Scheme,
+        Colon,
+        Slash,
+        Absolute,
+        Relative,
+    }
+    struct CheckAbsolute(State);
+    impl Write for CheckAbsolute {
+        fn write_str(&mut self, uri: &str) -> fmt::Result {
+            for b in uri.bytes() {
+                self.0 = match (&self.0, b) {
+                    (State::Absolute, _) | (State::Relative, _) => return Ok(()),
+                    (State::Start, b) if b.is_ascii_alphabetic() => State::Scheme,
+                    (State::Scheme, b)
+                        if b.is_ascii_alphanumeric() || b == b'+' || b == b'-' || b == b'.' =>
+                    {
+                        State::Scheme
+                    }
+                    (State::Scheme, b':') => State::Colon,
+                    (State::Colon, b'/') => State::Slash,
+                    (State::Slash, b'/') => State::Absolute,
+                    _ => State::Relative,
+                };
+            }
+            Ok(())
+        }
+    }
+    let mut w = CheckAbsolute(State::Start);
+    write!(w, "{}", uri).unwrap();
+    matches!(w.0, State::Start | State::Absolute)
 }
 
 impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, W> {
     fn append_to_header_encoded<V: Display>(&mut self, k: &str, v: V) {
+        self.fold_query_before_key(k);
         self.check_dictionary_order(k);
         match self.data {
             Data::Authorization(ref mut header) => write!(header, r#"{}="{}","#, k, v).unwrap(),
             Data::Urlencode(ref mut encoder) => encoder.serialize_parameter_encoded(k, v),
         }
+        self.count_parameter();
+        self.sign_delimiter();
+    }
+
+    fn serialize_parameter_<V: Display>(&mut self, key: &str, value: V) {
+        debug_assert_key(key);
+        self.fold_query_before(key, &value);
+        if let Data::Urlencode(ref mut encoder) = self.data {
+            encoder.serialize_parameter(key, &value);
+        }
+        let encode_set = self.options.effective_encode_set();
+        self.sign_parameter(key, DoublePercentEncodeWith(value, encode_set));
+    }
+
+    fn sign_parameter<V: Display>(&mut self, k: &str, v: V) {
+        self.check_dictionary_order(k);
+        self.count_parameter();
         self.sign_delimiter();
+        self.sign.parameter(k, v);
+    }
+
+    fn fold_query_before_key(&mut self, _k: &str) {
+        #[cfg(feature = "alloc")]
+        {
+            while let Some((k, v)) = self.query.next_before_key(_k) {
+                self.sign_query_parameter(&k, &v);
+            }
+        }
+    }
+
+    fn fold_query_before<V: Display>(&mut self, _k: &str, _v: &V) {
+        #[cfg(feature = "alloc")]
+        {
+            let encode_set = self.options.effective_encode_set();
+            while let Some((k, v)) = self.query.next_before(_k, _v, encode_set) {
+                self.sign_query_parameter(&k, &v);
+            }
+        }
+    }
+
+    fn fold_query_rest(&mut self) {
+        #[cfg(feature = "alloc")]
+        {
+            while let Some((k, v)) = self.query.next() {
+                self.sign_query_parameter(&k, &v);
+            }
+        }
+    }
+
+    /// Signs a query parameter whose key and value are already percent encoded.
+    #[cfg(feature = "alloc")]
+    fn sign_query_parameter(&mut self, k: &str, v: &str) {
+        self.check_dictionary_order(k);
+        self.count_parameter();
+        self.sign_delimiter();
+        let v = PercentEncodeWith(v, EncodeSet::NONE);
+        if k.contains('%') {
+            self.sign
+                .parameter(&PercentEncodeWith(k, EncodeSet::NONE).to_string(), v);
+        } else {
+            self.sign.parameter(k, v);
+        }
     }
 
     fn sign_delimiter(&mut self) {
@@ -257,6 +1043,13 @@ impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, W> {
         }
     }
 
+    fn count_parameter(&mut self) {
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        {
+            self.parameters += 1;
+        }
+    }
+
     fn check_dictionary_order(&mut self, _k: &str) {
         #[cfg(all(feature = "alloc", debug_assertions))]
         {
@@ -291,49 +1084,109 @@ macro_rules! append_to_header {
     }};
 }
 
+impl<'a, SM: SignatureMethod, W: Write> Authorizer<'a, SM, W> {
+    #[cfg(feature = "default-nonce")]
+    fn serialize_generated_nonce(&mut self) {
+        let mut nonce_buf = [0; MAX_NONCE_LEN];
+        let config = self.options.nonce_config;
+        let nonce = gen_nonce(&mut nonce_buf, config, &mut get_rng());
+        append_to_header!(self, encoded nonce, nonce);
+    }
+
+    #[cfg(not(feature = "default-nonce"))]
+    fn serialize_generated_nonce(&mut self) {
+        panic!(
+            "Attempted to generate a nonce without the `default-nonce` feature. You must either \
+            use a signature method that does not use nonce (i.e. `SignatureMethod::Sign::use_nonce` \
+            returns `false`) or explicitly set the nonce via `Builder::nonce` or \
+            `serializer::auth::Options::nonce`",
+        );
+    }
+}
+
+// Enters the `base_string` span until the end of the enclosing block.
+macro_rules! enter_span {
+    ($self:expr) => {
+        #[cfg(feature = "tracing")]
+        let _span = $self.span.clone().entered();
+    };
+}
+
 impl<'a, SM: SignatureMethod, W: Write> Serializer for Authorizer<'a, SM, W> {
     type Output = W;
 
     fn serialize_parameter<V: Display>(&mut self, key: &str, value: V) {
-        self.check_dictionary_order(key);
-        self.sign_delimiter();
-        self.sign.parameter(key, DoublePercentEncode(value));
+        enter_span!(self);
+        if self.options.effective_trim_values() {
+            self.serialize_parameter_(key, TrimAsciiWhitespace(value));
+        } else {
+            self.serialize_parameter_(key, value);
+        }
     }
 
     fn serialize_parameter_encoded<V: Display>(&mut self, key: &str, value: V) {
-        self.check_dictionary_order(key);
-        self.sign_delimiter();
-        self.sign.parameter(key, PercentEncode(value));
+        enter_span!(self);
+        debug_assert_key(key);
+        self.fold_query_before(key, &value);
+        if let Data::Urlencode(ref mut encoder) = self.data {
+            encoder.serialize_parameter_encoded(key, &value);
+        }
+        self.sign_parameter(key, PercentEncode(value));
     }
 
     fn serialize_oauth_callback(&mut self) {
+        enter_span!(self);
+        // `oauth_body_hash` is not a part of the `Serializer` interface, so it is written right
+        // before `oauth_callback`, which it immediately precedes in dictionary order among
+        // the `oauth_*` parameters.
+        if let Some(h) = self.options.body_hash {
+            self.append_to_header_encoded("oauth_body_hash", percent_encode(h));
+            self.sign
+                .parameter("oauth_body_hash", DoublePercentEncode(h));
+        }
         if let Some(c) = self.options.callback {
             append_to_header!(self, callback, c);
         }
     }
 
     fn serialize_oauth_consumer_key(&mut self) {
+        enter_span!(self);
         append_to_header!(self, consumer_key, self.consumer_key);
     }
 
     fn serialize_oauth_nonce(&mut self) {
-        if self.sign.use_nonce() {
-            if let Some(n) = self.options.nonce {
-                append_to_header!(self, nonce, n);
-            } else {
-                let mut nonce_buf = Default::default();
-                append_to_header!(self, encoded nonce, gen_nonce(&mut nonce_buf, &mut get_rng()));
-            }
+        enter_span!(self);
+        if !self.sign.use_nonce() {
+            return;
         }
+        if let Some(n) = self.options.nonce {
+            append_to_header!(self, nonce, n);
+            return;
+        }
+        if let Some(g) = self.options.nonce_generator {
+            let mut buf = NonceBuf {
+                buf: [0; MAX_NONCE_LEN],
+                len: 0,
+            };
+            g.generate(self.options.nonce_config, &mut buf).expect(
+                "`NonceGenerator` failed to generate a nonce (is it longer than \
+                 `NonceConfig::MAX_LEN`?)",
+            );
+            append_to_header!(self, nonce, buf.as_str());
+            return;
+        }
+        self.serialize_generated_nonce();
     }
 
     fn serialize_oauth_signature_method(&mut self) {
+        enter_span!(self);
         let v = self.sign.get_signature_method_name();
         self.append_to_header_encoded("oauth_signature_method", v);
         self.sign.signature_method();
     }
 
     fn serialize_oauth_timestamp(&mut self) {
+        enter_span!(self);
         if self.sign.use_timestamp() {
             let t = if let Some(t) = self.options.timestamp {
                 t.get()
@@ -345,25 +1198,66 @@ impl<'a, SM: SignatureMethod, W: Write> Serializer for Authorizer<'a, SM, W> {
     }
 
     fn serialize_oauth_token(&mut self) {
+        enter_span!(self);
         if let Some(t) = self.token {
             append_to_header!(self, token, t);
         }
     }
 
     fn serialize_oauth_verifier(&mut self) {
+        enter_span!(self);
         if let Some(v) = self.options.verifier {
+            if self.options.verifier_in_body {
+                if let Data::Authorization(_) = self.data {
+                    self.fold_query_before_key("oauth_verifier");
+                    self.check_dictionary_order("oauth_verifier");
+                    self.count_parameter();
+                    self.sign_delimiter();
+                    self.sign.verifier(DoublePercentEncode(v));
+                    return;
+                }
+            }
             append_to_header!(self, verifier, v);
         }
     }
 
     fn serialize_oauth_version(&mut self) {
+        enter_span!(self);
         if self.options.version {
             self.append_to_header_encoded("oauth_version", "1.0");
             self.sign.version();
         }
     }
 
-    fn end(self) -> W {
+    fn end(mut self) -> W {
+        {
+            enter_span!(self);
+            self.fold_query_rest();
+        }
+
+        #[cfg(feature = "tracing")]
+        let _span = {
+            self.span.record("parameters", self.parameters);
+            // Closes the `base_string` span, which covers the serialization of the parameters.
+            drop(mem::replace(&mut self.span, tracing01::Span::none()));
+            tracing01::trace_span!(
+                "sign",
+                signature_method = self.sign.get_signature_method_name(),
+                parameters = self.parameters,
+            )
+            .entered()
+        };
+
+        #[cfg(feature = "log")]
+        if let Some(ref request_line) = self.request_line {
+            log04::debug!(
+                "signed {} with {} parameters using {}",
+                request_line,
+                self.parameters,
+                self.sign.get_signature_method_name(),
+            );
+        }
+
         let Self { data, sign, .. } = self;
 
         match data {
@@ -373,6 +1267,8 @@ impl<'a, SM: SignatureMethod, W: Write> Serializer for Authorizer<'a, SM, W> {
                 header
             }
             Data::Urlencode(mut encoder) => {
+                // The signature depends on all the other parameters, so it always comes last
+                // regardless of the dictionary order.
                 encoder.serialize_parameter_encoded("oauth_signature", sign.end());
                 encoder.end()
             }
@@ -380,6 +1276,159 @@ impl<'a, SM: SignatureMethod, W: Write> Serializer for Authorizer<'a, SM, W> {
     }
 }
 
+doc_auto_cfg! {
+    #[cfg(feature = "alloc")]
+    impl<'a> ParameterStringSerializer<'a> {
+        /// Creates a `ParameterStringSerializer` that produces the parameters string of
+        /// the request parameters only, without the OAuth protocol parameters.
+        ///
+        /// The output is the same as that of [`Urlencoder::form`].
+        pub fn new() -> Self {
+            ParameterStringSerializer {
+                inner: ParameterStringInner::Request(Urlencoder::form()),
+            }
+        }
+
+        /// Creates a `ParameterStringSerializer` that produces the parameters string including
+        /// the OAuth protocol parameters (except `oauth_signature`), as used in the signature
+        /// base string of a request signed with `signature_method`.
+        ///
+        /// The `oauth_nonce` and `oauth_timestamp` values are generated unless they are set
+        /// in `options`, so you should set them if you are going to cache the output.
+        pub fn with_oauth_parameters<SM: SignatureMethod>(
+            client: Credentials<&'a str>,
+            token: Option<Credentials<&'a str>>,
+            options: &'a Options<'a>,
+            signature_method: SM,
+        ) -> Self {
+            let sign = signature_method.sign_with(client.secret, token.map(|t| t.secret));
+            let info = SignatureMethodInfo {
+                name: sign.get_signature_method_name(),
+                use_nonce: sign.use_nonce(),
+                use_timestamp: sign.use_timestamp(),
+            };
+            // The URI is not a part of the output.
+            #[allow(unused_mut)]
+            let mut authorizer = Authorizer::form("", "", client, token, options, info);
+            // This is not a request to be logged.
+            #[cfg(feature = "log")]
+            {
+                authorizer.request_line = None;
+            }
+            ParameterStringSerializer {
+                inner: ParameterStringInner::OAuth(authorizer),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Default for ParameterStringSerializer<'a> {
+    fn default() -> Self {
+        ParameterStringSerializer::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! delegate_parameter_string {
+    ($($method:ident;)*) => {$(
+        fn $method(&mut self) {
+            match self.inner {
+                ParameterStringInner::Request(ref mut ser) => ser.$method(),
+                ParameterStringInner::OAuth(ref mut ser) => ser.$method(),
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> Serializer for ParameterStringSerializer<'a> {
+    type Output = alloc::string::String;
+
+    fn serialize_parameter<V: Display>(&mut self, key: &str, value: V) {
+        match self.inner {
+            ParameterStringInner::Request(ref mut ser) => ser.serialize_parameter(key, value),
+            ParameterStringInner::OAuth(ref mut ser) => ser.serialize_parameter(key, value),
+        }
+    }
+
+    fn serialize_parameter_encoded<V: Display>(&mut self, key: &str, value: V) {
+        match self.inner {
+            ParameterStringInner::Request(ref mut ser) => {
+                ser.serialize_parameter_encoded(key, value)
+            }
+            ParameterStringInner::OAuth(ref mut ser) => ser.serialize_parameter_encoded(key, value),
+        }
+    }
+
+    delegate_parameter_string! {
+        serialize_oauth_callback;
+        serialize_oauth_consumer_key;
+        serialize_oauth_nonce;
+        serialize_oauth_signature_method;
+        serialize_oauth_timestamp;
+        serialize_oauth_token;
+        serialize_oauth_verifier;
+        serialize_oauth_version;
+    }
+
+    fn end(self) -> alloc::string::String {
+        match self.inner {
+            ParameterStringInner::Request(ser) => ser.end(),
+            ParameterStringInner::OAuth(ser) => {
+                // The form string ends with an `oauth_signature` parameter with an empty value.
+                const SIGNATURE: &str = "&oauth_signature=";
+                let mut form = ser.end();
+                debug_assert!(form.ends_with(SIGNATURE));
+                form.truncate(form.len() - SIGNATURE.len());
+                form
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl SignatureMethod for SignatureMethodInfo {
+    type Sign = Self;
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Self {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Sign for SignatureMethodInfo {
+    type Signature = &'static str;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn request_method(&mut self, _method: &str) {}
+
+    fn uri<T: Display>(&mut self, _uri: T) {}
+
+    fn parameter<V: Display>(&mut self, _key: &str, _value: V) {}
+
+    fn delimiter(&mut self) {}
+
+    fn end(self) -> &'static str {
+        ""
+    }
+
+    fn use_nonce(&self) -> bool {
+        self.use_nonce
+    }
+
+    fn use_timestamp(&self) -> bool {
+        self.use_timestamp
+    }
+}
+
 fn get_current_timestamp() -> u64 {
     cfg_if::cfg_if! {
         // `std::time::SystemTime::now` is not supported and panics on `wasm32-unknown-unknown` target
@@ -402,14 +1451,9 @@ fn get_current_timestamp() -> u64 {
     }
 }
 
+#[cfg(feature = "default-nonce")]
 fn get_rng() -> impl RngCore + CryptoRng {
-    cfg_if::cfg_if! {
-        if #[cfg(feature = "std")] {
-            thread_rng()
-        } else {
-            rand::rngs::OsRng
-        }
-    }
+    rand::rngs::OsRng
 }
 
 // This is worth 72 bits of entropy. The nonce is required to be unique across all requests with
@@ -421,9 +1465,51 @@ fn get_rng() -> impl RngCore + CryptoRng {
 //     P = 1 - (2^72 - 1)/(2^72) * (2^72 - 2)/(2^72) * ... * (2^72 - 999999)/(2^72)
 // (birthday problem), and the expected number of seconds it takes until getting a collision with
 // the same timestamp is 1/P.
+#[cfg(any(feature = "alloc", feature = "default-nonce"))]
 const NONCE_LEN: usize = 12;
 
-fn gen_nonce<'a, R: RngCore + CryptoRng>(buf: &'a mut [u8; NONCE_LEN], rng: &mut R) -> &'a str {
+const MAX_NONCE_LEN: usize = 64;
+
+/// A buffer for a nonce written by a `NonceGenerator`.
+struct NonceBuf {
+    buf: [u8; MAX_NONCE_LEN],
+    len: usize,
+}
+
+impl NonceBuf {
+    fn as_str(&self) -> &str {
+        // Only whole `str`s are written to the buffer.
+        str::from_utf8(&self.buf[..self.len]).unwrap()
+    }
+}
+
+impl Write for NonceBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "default-nonce")]
+fn gen_nonce<'a, R: RngCore + CryptoRng + ?Sized>(
+    buf: &'a mut [u8; MAX_NONCE_LEN],
+    config: Option<NonceConfig>,
+    rng: &mut R,
+) -> &'a str {
+    if let Some(config) = config {
+        let alphabet = config.alphabet.chars();
+        let buf = &mut buf[..config.len];
+        for b in buf.iter_mut() {
+            *b = alphabet[rng.gen_range(0..alphabet.len())];
+        }
+        return str::from_utf8(buf).unwrap();
+    }
+
     let mut rand = [0_u8; NONCE_LEN * 3 / 4];
     rng.fill_bytes(&mut rand);
 
@@ -431,7 +1517,7 @@ fn gen_nonce<'a, R: RngCore + CryptoRng>(buf: &'a mut [u8; NONCE_LEN], rng: &mut
     let i = rand.iter().position(|&b| b != 0).unwrap_or(rand.len());
     let rand = &rand[i..];
 
-    let len = base64::encode_config_slice(&rand, base64::URL_SAFE_NO_PAD, buf);
+    let len = base64::encode_config_slice(rand, base64::URL_SAFE_NO_PAD, buf);
     let buf = &buf[..len];
 
     str::from_utf8(buf).unwrap()
@@ -0,0 +1,80 @@
+//! A serializer that produces a JSON object of the parameters of a request.
+
+use alloc::string::{String, ToString};
+use alloc::vec;
+use core::fmt::Display;
+
+use serde_json1::map::Entry;
+use serde_json1::{Map, Value};
+
+use super::Serializer;
+
+/// A `Serializer` that produces a JSON object of the parameters of a request, which is useful
+/// for inspecting the parameters to be signed, e.g. in logs or tests.
+///
+/// The values are the `Display` representations of the parameter values. The values serialized
+/// with `serialize_parameter_encoded` are written as is (i.e. percent-encoded). If a key appears
+/// more than once, its values are collected into an array in the order of serialization.
+///
+/// The OAuth protocol parameters are not included.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::serializer::JsonSerializer;
+/// use oauth::Request;
+///
+/// let request = oauth::request! { "q" => "café", "count" => 20, "lang" => "fr" };
+/// let json = request.serialize(JsonSerializer::new());
+/// assert_eq!(json.to_string(), r#"{"count":"20","lang":"fr","q":"café"}"#);
+///
+/// let request = vec![("id", 2), ("id", 1), ("page", 3)];
+/// let json = request.serialize(JsonSerializer::new());
+/// assert_eq!(json.to_string(), r#"{"id":["1","2"],"page":"3"}"#);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct JsonSerializer {
+    map: Map<String, Value>,
+}
+
+impl JsonSerializer {
+    /// Creates a new `JsonSerializer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(&mut self, key: &str, value: String) {
+        match self.map.entry(key) {
+            Entry::Vacant(entry) => {
+                entry.insert(Value::String(value));
+            }
+            Entry::Occupied(mut entry) => match *entry.get_mut() {
+                Value::Array(ref mut values) => values.push(Value::String(value)),
+                ref mut prev => {
+                    let first = prev.take();
+                    *prev = Value::Array(vec![first, Value::String(value)]);
+                }
+            },
+        }
+    }
+}
+
+impl Serializer for JsonSerializer {
+    type Output = Value;
+
+    fn serialize_parameter<V: Display>(&mut self, key: &str, value: V) {
+        self.insert(key, value.to_string());
+    }
+
+    fn serialize_parameter_encoded<V: Display>(&mut self, key: &str, value: V) {
+        self.insert(key, value.to_string());
+    }
+
+    super::skip_serialize_oauth_parameters!();
+
+    fn end(self) -> Value {
+        Value::Object(self.map)
+    }
+}
@@ -2,7 +2,9 @@
 
 use core::fmt::Write;
 
-use crate::util::PercentEncode;
+#[cfg(feature = "std")]
+use crate::util::IoWrite;
+use crate::util::{debug_assert_key, EncodeSet, PercentEncodeWith};
 
 use super::Serializer;
 
@@ -16,6 +18,46 @@ cfg_type_param_hack! {
     > {
         data: W,
         next_append: Append,
+        encode_set: EncodeSet,
+        pair_separator: char,
+        key_value_separator: char,
+    }
+}
+
+doc_auto_cfg! {
+    /// A `Serializer` that writes an `x-www-form-urlencoded` string into an `io::Write` value.
+    ///
+    /// This is useful for streaming a large request body into a socket or a file without
+    /// building the whole string in memory. Consider wrapping an unbuffered writer with
+    /// `std::io::BufWriter` since the string is written in small pieces.
+    ///
+    /// If the writer returns an error, nothing is written into it afterwards and the error is
+    /// returned from [`end`](Serializer::end).
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(feature = "std", doc = " ```")]
+    #[cfg_attr(not(feature = "std"), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// use oauth::serializer::{IoUrlencoder, Serializer};
+    ///
+    /// let mut ser = IoUrlencoder::form(Vec::new());
+    /// ser.serialize_parameter("a", 1);
+    /// ser.serialize_parameter("b", "x y");
+    /// assert_eq!(ser.end().unwrap(), b"a=1&b=x%20y");
+    ///
+    /// let mut buf = [0_u8; 4];
+    /// let mut ser = IoUrlencoder::form(&mut buf[..]);
+    /// ser.serialize_parameter("a", 1);
+    /// ser.serialize_parameter("b", "x y");
+    /// assert_eq!(ser.end().unwrap_err().kind(), std::io::ErrorKind::WriteZero);
+    /// assert_eq!(&buf, b"a=1&");
+    /// ```
+    #[cfg(feature = "std")]
+    #[derive(Debug)]
+    pub struct IoUrlencoder<W> {
+        inner: Urlencoder<IoWrite<W>>,
     }
 }
 
@@ -34,6 +76,9 @@ doc_auto_cfg! {
             Urlencoder {
                 data: alloc::string::String::new(),
                 next_append: Append::None,
+                encode_set: EncodeSet::RFC5849,
+                pair_separator: '&',
+                key_value_separator: '=',
             }
         }
     }
@@ -48,6 +93,9 @@ where
         Urlencoder {
             data: buf,
             next_append: Append::None,
+            encode_set: EncodeSet::RFC5849,
+            pair_separator: '&',
+            key_value_separator: '=',
         }
     }
 
@@ -56,7 +104,76 @@ where
         Urlencoder {
             data: uri,
             next_append: Append::Question,
+            encode_set: EncodeSet::RFC5849,
+            pair_separator: '&',
+            key_value_separator: '=',
+        }
+    }
+
+    /// Creates a `Urlencoder` that appends query pairs to the existing query part of the given URI.
+    pub fn append_query(uri: W) -> Self {
+        Urlencoder {
+            data: uri,
+            next_append: Append::Ampersand,
+            encode_set: EncodeSet::RFC5849,
+            pair_separator: '&',
+            key_value_separator: '=',
+        }
+    }
+
+    /// Sets the set of characters to percent encode in the parameter values.
+    ///
+    /// Defaults to [`EncodeSet::RFC5849`]. Regardless of the set, the characters `=`, `&`, `?`,
+    /// `#` and `+` and the separators set with [`separators`](Self::separators) are always
+    /// percent encoded so that the values cannot be confused with the delimiters.
+    pub fn encode_set(&mut self, encode_set: EncodeSet) -> &mut Self {
+        self.encode_set = encode_set;
+        self
+    }
+
+    /// Sets the characters written between parameter pairs and between the key and the value of
+    /// a pair.
+    ///
+    /// Defaults to `&` and `=` respectively. This only affects the output of the `Urlencoder`.
+    /// In particular, the signature base string always uses the standard separators.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = " ```")]
+    #[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// use oauth::serializer::{Serializer, Urlencoder};
+    ///
+    /// let mut ser = Urlencoder::form();
+    /// ser.separators(';', ':');
+    /// ser.serialize_parameter("a", 1);
+    /// ser.serialize_parameter("b", "x y");
+    /// assert_eq!(ser.end(), "a:1;b:x%20y");
+    /// ```
+    pub fn separators(&mut self, pair: char, key_value: char) -> &mut Self {
+        self.pair_separator = pair;
+        self.key_value_separator = key_value;
+        self
+    }
+
+    /// Returns the set of characters to percent encode in the values, which always includes
+    /// the delimiters.
+    fn value_encode_set(&self) -> EncodeSet {
+        let mut ret = self
+            .encode_set
+            .add(b'=')
+            .add(b'&')
+            .add(b'?')
+            .add(b'#')
+            .add(b'+');
+        for &c in &[self.pair_separator, self.key_value_separator] {
+            // Non-ASCII characters are always encoded.
+            if c.is_ascii() {
+                ret = ret.add(c as u8);
+            }
         }
+        ret
     }
 
     fn append_delim(&mut self) {
@@ -66,7 +183,7 @@ where
                 self.data.write_char('?').unwrap();
                 self.next_append = Append::Ampersand;
             }
-            Append::Ampersand => self.data.write_char('&').unwrap(),
+            Append::Ampersand => self.data.write_char(self.pair_separator).unwrap(),
         }
     }
 }
@@ -78,16 +195,19 @@ impl<W: Write> Serializer for Urlencoder<W> {
     where
         V: core::fmt::Display,
     {
+        debug_assert_key(key);
         self.append_delim();
-        write!(self.data, "{}={}", key, PercentEncode(&value)).unwrap();
+        let value = PercentEncodeWith(&value, self.value_encode_set());
+        write!(self.data, "{}{}{}", key, self.key_value_separator, value).unwrap();
     }
 
     fn serialize_parameter_encoded<V>(&mut self, key: &str, value: V)
     where
         V: core::fmt::Display,
     {
+        debug_assert_key(key);
         self.append_delim();
-        write!(self.data, "{}={}", key, value).unwrap();
+        write!(self.data, "{}{}{}", key, self.key_value_separator, value).unwrap();
     }
 
     super::skip_serialize_oauth_parameters!();
@@ -96,3 +216,55 @@ impl<W: Write> Serializer for Urlencoder<W> {
         self.data
     }
 }
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> IoUrlencoder<W> {
+    /// Creates an `IoUrlencoder` that writes an `x-www-form-urlencoded` string into `writer`.
+    pub fn form(writer: W) -> Self {
+        IoUrlencoder {
+            inner: Urlencoder::form_with_buf(IoWrite::new(writer)),
+        }
+    }
+
+    /// Sets the set of characters to percent encode in the parameter values.
+    ///
+    /// See [`Urlencoder::encode_set`] for the details.
+    pub fn encode_set(&mut self, encode_set: EncodeSet) -> &mut Self {
+        self.inner.encode_set(encode_set);
+        self
+    }
+
+    /// Sets the characters written between parameter pairs and between the key and the value of
+    /// a pair.
+    ///
+    /// See [`Urlencoder::separators`] for the details.
+    pub fn separators(&mut self, pair: char, key_value: char) -> &mut Self {
+        self.inner.separators(pair, key_value);
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Serializer for IoUrlencoder<W> {
+    type Output = std::io::Result<W>;
+
+    fn serialize_parameter<V>(&mut self, key: &str, value: V)
+    where
+        V: core::fmt::Display,
+    {
+        self.inner.serialize_parameter(key, value);
+    }
+
+    fn serialize_parameter_encoded<V>(&mut self, key: &str, value: V)
+    where
+        V: core::fmt::Display,
+    {
+        self.inner.serialize_parameter_encoded(key, value);
+    }
+
+    super::skip_serialize_oauth_parameters!();
+
+    fn end(self) -> Self::Output {
+        self.inner.end().into_result()
+    }
+}
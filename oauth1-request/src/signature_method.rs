@@ -6,29 +6,59 @@
 //! So the module provides an abstraction over signature methods so that users can implement those
 //! custom methods by themselves.
 
+pub mod base_string;
+
 doc_auto_cfg! {
+    #[cfg(feature = "alloc")]
+    pub mod debug;
+    #[cfg(feature = "test")]
+    pub mod fixed;
     #[cfg(feature = "hmac-sha1")]
     pub mod hmac_sha1;
+    #[cfg(feature = "alloc")]
+    pub mod identity;
     pub mod plaintext;
+    /// The `RSA-SHA1` signature method ([RFC 5849 section 3.4.3.][rfc]), using the latest
+    /// supported version of the `rsa` crate.
+    ///
+    /// This is currently an alias of [`rsa_sha1_06`](super::rsa_sha1_06). Use the versioned
+    /// module and feature if you need to stick to a specific version of the `rsa` crate.
+    ///
+    /// [rfc]: https://datatracker.ietf.org/doc/html/rfc5849#section-3.4.3
+    #[cfg(feature = "rsa-sha1")]
+    pub mod rsa_sha1 {
+        pub use super::rsa_sha1_06::*;
+    }
     #[cfg(feature = "rsa-sha1-06")]
     pub mod rsa_sha1_06;
-
-//    #[cfg(feature = "rsa-sha256")]
-
+    #[cfg(feature = "hmac-sha256")]
+    pub mod hmac_sha256;
+    #[cfg(feature = "hmac-sha512")]
+    pub mod hmac_sha512;
 }
 
-pub mod hmac_sha256;
-
-#[cfg(any(feature = "hmac-sha1", feature = "rsa-sha1-06"))]
+#[cfg(any(
+    feature = "hmac-sha1",
+    feature = "hmac-sha256",
+    feature = "hmac-sha512",
+    feature = "rsa-sha1-06",
+))]
 mod digest_common;
 #[cfg(feature = "either")]
 mod either;
 
+pub use self::base_string::BaseString;
 doc_auto_cfg! {
+    #[cfg(feature = "alloc")]
+    pub use self::debug::Debug;
+    #[cfg(feature = "test")]
+    pub use self::fixed::Fixed;
     #[cfg(feature = "hmac-sha1")]
     pub use self::hmac_sha1::HmacSha1;
     #[cfg(feature = "hmac-sha1")]
     pub use self::hmac_sha1::HMAC_SHA1;
+    #[cfg(feature = "alloc")]
+    pub use self::identity::Identity;
     pub use self::plaintext::Plaintext;
     #[cfg(feature = "alloc")]
     pub use self::plaintext::PLAINTEXT;
@@ -47,6 +77,31 @@ pub trait SignatureMethod {
     /// The algorithm used by this signature method to sign a signature base string.
     type Sign: Sign;
 
+    /// Returns the `oauth_signature_method` string for the signature method.
+    ///
+    /// This is the same as the `Sign::get_signature_method_name` value of the `Self::Sign`s
+    /// created by `self`, and is useful for displaying the signature method without signing
+    /// anything.
+    ///
+    /// The default implementation creates a `Self::Sign` from a clone of `self` with empty
+    /// secrets to get the name. Implementations are encouraged to override it with a cheaper one.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+    #[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+    /// use oauth1_request::signature_method::{HmacSha1, SignatureMethod, HMAC_SHA1};
+    ///
+    /// assert_eq!(HmacSha1::NAME, "HMAC-SHA1");
+    /// assert_eq!(HMAC_SHA1.name(), HmacSha1::NAME);
+    /// ```
+    fn name(&self) -> &'static str
+    where
+        Self: Clone,
+    {
+        self.clone().sign_with("", None).get_signature_method_name()
+    }
+
     /// Creates a `Self::Sign` that signs a signature base string with the given shared-secrets.
     fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> Self::Sign;
 }
@@ -125,8 +180,51 @@ stringify!($name), "\"` as the first argument."
 /// sign.parameter("z", "");
 /// let _ = sign.end();
 /// ```
+///
+/// ## Reusing a common prefix
+///
+/// The `Sign` types of the crate (except for [`BaseStringSign`](base_string::BaseStringSign),
+/// which mutably borrows its buffer) implement `Clone`. When signing many requests that share
+/// a prefix of the signature base string, you can feed a `Sign` with the prefix once and clone it
+/// for each request, so that the prefix is only hashed once:
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::signature_method::{Sign, SignatureMethod, HMAC_SHA1};
+///
+/// fn feed_prefix(sign: &mut impl Sign) {
+///     sign.request_method("GET");
+///     sign.uri("https%3A%2F%2Fexample.com%2Fapi%2Fsearch");
+///     sign.consumer_key("9djdj82h48djs9d2");
+///     sign.delimiter();
+///     sign.signature_method();
+///     sign.delimiter();
+/// }
+///
+/// let mut prefix = HMAC_SHA1.sign_with("client_secret", Some("token_secret"));
+/// feed_prefix(&mut prefix);
+///
+/// for &q in &["foo", "bar", "baz"] {
+///     let mut sign = prefix.clone();
+///     sign.parameter("q", q);
+///     let signature = sign.end().to_string();
+///
+///     // Same as signing the whole base string from scratch.
+///     let mut fresh = HMAC_SHA1.sign_with("client_secret", Some("token_secret"));
+///     feed_prefix(&mut fresh);
+///     fresh.parameter("q", q);
+///     assert_eq!(signature, fresh.end().to_string());
+/// }
+/// ```
+///
+/// Note that the parameters have to be fed in dictionary order, so only the parameters that
+/// precede the varying ones can be a part of the common prefix.
 pub trait Sign {
     /// The URL-encoded representation of `oauth_signature` string the algorithm produces.
+    ///
+    /// The signature types of the crate implement the [`Signature`] trait as well.
     type Signature: Display;
 
     /// Returns the `oauth_signature_method` string for the signature method associated with the
@@ -200,6 +298,120 @@ pub trait Sign {
     fn version(&mut self) {
         self.parameter("oauth_version", "1.0");
     }
+
+    /// Returns the signature base string fed to `self` so far, if `self` keeps it.
+    ///
+    /// This is meant for debugging a signature rejected by a server. Wrap a signature method
+    /// with `Debug`, which requires the `alloc` feature, to capture the signature base string of
+    /// any signature method.
+    ///
+    /// The default implementation returns `None`.
+    fn debug_base_string(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// A signature produced by a [`Sign`], in the form that is written to the `oauth_signature`
+/// parameter.
+///
+/// The signature types of the crate implement this trait, which allows for writing code that is
+/// generic over the signature methods, like logging or comparing signatures.
+///
+/// ## Example
+///
+#[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+#[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::signature_method::{Sign, SignatureMethod, Signature, HMAC_SHA1, PLAINTEXT};
+///
+/// fn sign<SM: SignatureMethod>(signature_method: SM) -> String
+/// where
+///     <SM::Sign as Sign>::Signature: Signature,
+/// {
+///     let mut sign = signature_method.sign_with("client_secret", Some("token_secret"));
+///     sign.request_method("GET");
+///     sign.uri("https%3A%2F%2Fexample.com%2F");
+///     sign.parameter("q", "foo");
+///
+///     let signature = sign.end();
+///     let mut ret = String::new();
+///     signature.write_encoded(&mut ret).unwrap();
+///     if let Some(s) = signature.as_encoded_str() {
+///         assert_eq!(s, ret);
+///     }
+///     ret
+/// }
+///
+/// assert_eq!(sign(PLAINTEXT), "client_secret&token_secret");
+/// // The Base64 padding of the HMAC-SHA1 signature is percent encoded.
+/// assert!(sign(HMAC_SHA1).ends_with("%3D"));
+/// ```
+pub trait Signature: Display {
+    /// Writes the encoded signature into `dst`.
+    ///
+    /// The default implementation writes the `Display` representation of `self`.
+    fn write_encoded(&self, dst: &mut dyn Write) -> fmt::Result {
+        write!(dst, "{}", self)
+    }
+
+    /// Returns the encoded signature as a string slice if `self` holds the signature in that form,
+    /// like the signature of the `PLAINTEXT` method, and `None` otherwise.
+    ///
+    /// The default implementation returns `None`.
+    fn as_encoded_str(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl Signature for &str {
+    fn write_encoded(&self, dst: &mut dyn Write) -> fmt::Result {
+        dst.write_str(self)
+    }
+
+    fn as_encoded_str(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+
+doc_auto_cfg! {
+    #[cfg(feature = "alloc")]
+    impl Signature for alloc::string::String {
+        fn write_encoded(&self, dst: &mut dyn Write) -> fmt::Result {
+            dst.write_str(self)
+        }
+
+        fn as_encoded_str(&self) -> Option<&str> {
+            Some(self)
+        }
+    }
+
+    /// Builds the key used by the HMAC-based signature methods (and the signature of `PLAINTEXT`)
+    /// from the client and token secrets ([RFC 5849 section 3.4.2.][rfc]).
+    ///
+    /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.2
+    ///
+    /// The key is the percent-encoded client secret and the percent-encoded token secret (if
+    /// any), joined with a `&` character. This is useful when implementing a custom signature
+    /// method or when delegating the signing to an external signer.
+    ///
+    /// Note that the returned string is as sensitive as the secrets themselves, so take care not
+    /// to log or otherwise expose it.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use oauth1_request::signature_method::build_signing_key;
+    ///
+    /// assert_eq!(build_signing_key("client secret", Some("token&secret")), "client%20secret&token%26secret");
+    /// assert_eq!(build_signing_key("client secret", None), "client%20secret&");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn build_signing_key(client_secret: &str, token_secret: Option<&str>) -> alloc::string::String {
+        let mut ret = alloc::string::String::new();
+        write_signing_key(&mut ret, client_secret, token_secret).unwrap();
+        ret
+    }
 }
 
 fn write_signing_key<W: Write>(
@@ -0,0 +1,162 @@
+//! A pseudo signature method that captures the signature base string
+//! ([RFC 5849 section 3.4.1.][rfc]) instead of signing it.
+//!
+//! [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1
+//!
+//! This is useful for verifying a signature on the server side: you can reconstruct the signature
+//! base string of an incoming request by feeding its parameters to a `Serializer` and then sign the
+//! base string with your copy of the shared-secrets to compare with the request's signature.
+
+use core::fmt::{self, Debug, Display, Formatter, Write};
+
+use super::{Sign, SignatureMethod};
+
+/// A `SignatureMethod` that writes the signature base string into a `W` value without signing it.
+///
+/// The shared-secrets passed to [`sign_with`](SignatureMethod::sign_with) are ignored and
+/// the resulting `oauth_signature` is an empty string.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "alloc", doc = " ```")]
+#[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use std::num::NonZeroU64;
+///
+/// use oauth::signature_method::BaseString;
+///
+/// let client = oauth::Credentials::new("9djdj82h48djs9d2", "");
+/// let token = oauth::Credentials::new("kkk9d7dh3k39sjv7", "");
+/// let request = oauth::ParameterList::new([("a2", "r b")]);
+///
+/// let mut base_string = String::new();
+/// let mut builder = oauth::Builder::new(client, BaseString::new(&mut base_string, "HMAC-SHA1"));
+/// builder
+///     .token(token)
+///     .nonce("7d8f3e4a")
+///     .timestamp(NonZeroU64::new(137131201));
+/// builder.into_authorization("POST", "http://example.com/request", &request);
+///
+/// assert_eq!(
+///     base_string,
+///     "POST&http%3A%2F%2Fexample.com%2Frequest&\
+///      a2%3Dr%2520b%26\
+///      oauth_consumer_key%3D9djdj82h48djs9d2%26\
+///      oauth_nonce%3D7d8f3e4a%26\
+///      oauth_signature_method%3DHMAC-SHA1%26\
+///      oauth_timestamp%3D137131201%26\
+///      oauth_token%3Dkkk9d7dh3k39sjv7",
+/// );
+/// ```
+pub struct BaseString<'a, W: ?Sized> {
+    buf: &'a mut W,
+    signature_method_name: &'static str,
+    use_nonce: bool,
+    use_timestamp: bool,
+}
+
+/// A `Sign` implementation that writes the signature base string into a `W` value.
+pub struct BaseStringSign<'a, W: ?Sized> {
+    buf: &'a mut W,
+    signature_method_name: &'static str,
+    use_nonce: bool,
+    use_timestamp: bool,
+}
+
+impl<'a, W: Write + ?Sized> BaseString<'a, W> {
+    /// Creates a new `BaseString` that writes the signature base string into `buf`.
+    ///
+    /// `signature_method_name` is used as the `oauth_signature_method` value in the signature base
+    /// string, which should be that of the signature method you are verifying against
+    /// (e.g. `"HMAC-SHA1"`).
+    pub fn new(buf: &'a mut W, signature_method_name: &'static str) -> Self {
+        BaseString {
+            buf,
+            signature_method_name,
+            use_nonce: true,
+            use_timestamp: true,
+        }
+    }
+
+    /// Creates a `BaseString` that mimics the signature method of `sign`, including whether it
+    /// uses the `oauth_nonce` and `oauth_timestamp` parameters.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn like<S: Sign>(buf: &'a mut W, sign: &S) -> Self {
+        BaseString {
+            buf,
+            signature_method_name: sign.get_signature_method_name(),
+            use_nonce: sign.use_nonce(),
+            use_timestamp: sign.use_timestamp(),
+        }
+    }
+}
+
+impl<'a, W: ?Sized> Debug for BaseString<'a, W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BaseString")
+            .field("signature_method_name", &self.signature_method_name)
+            .finish()
+    }
+}
+
+impl<'a, W: ?Sized> Debug for BaseStringSign<'a, W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BaseStringSign")
+            .field("signature_method_name", &self.signature_method_name)
+            .finish()
+    }
+}
+
+impl<'a, W: Write + ?Sized> SignatureMethod for BaseString<'a, W> {
+    type Sign = BaseStringSign<'a, W>;
+
+    fn name(&self) -> &'static str {
+        self.signature_method_name
+    }
+
+    fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Self::Sign {
+        BaseStringSign {
+            buf: self.buf,
+            signature_method_name: self.signature_method_name,
+            use_nonce: self.use_nonce,
+            use_timestamp: self.use_timestamp,
+        }
+    }
+}
+
+impl<'a, W: Write + ?Sized> Sign for BaseStringSign<'a, W> {
+    type Signature = &'static str;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.signature_method_name
+    }
+
+    fn request_method(&mut self, method: &str) {
+        write!(self.buf, "{}&", method).unwrap();
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        write!(self.buf, "{}&", uri).unwrap();
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        write!(self.buf, "{}%3D{}", key, value).unwrap();
+    }
+
+    fn delimiter(&mut self) {
+        self.buf.write_str("%26").unwrap();
+    }
+
+    fn use_nonce(&self) -> bool {
+        self.use_nonce
+    }
+
+    fn use_timestamp(&self) -> bool {
+        self.use_timestamp
+    }
+
+    fn end(self) -> &'static str {
+        ""
+    }
+}
@@ -0,0 +1,249 @@
+//! A signature method wrapper that captures the signature base string
+//! ([RFC 5849 section 3.4.1.][rfc]) while signing it with another signature method.
+//!
+//! [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1
+//!
+//! This is useful for debugging a signature rejected by a server, by comparing the signature base
+//! string the crate has signed with the one the server has constructed.
+
+use alloc::string::String;
+use core::fmt::{self, Display, Formatter, Write};
+
+use super::{Sign, SignatureMethod};
+
+/// A `SignatureMethod` that signs requests with `SM` and captures the signature base string.
+///
+/// The [`DebugSign`] created by this exposes the signature base string fed to it so far through
+/// [`Sign::debug_base_string`], and passes the whole signature base string to the callback set
+/// with [`callback`](Debug::callback) when the signing process is finished. The signature itself
+/// is the same as that of `SM`.
+///
+/// Note that the signature base string does not contain the secrets, but does contain
+/// the `oauth_token` value, so take care when logging it in production.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "hmac-sha1", doc = " ```")]
+#[cfg_attr(not(feature = "hmac-sha1"), doc = " ```ignore")]
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use std::cell::RefCell;
+/// use std::num::NonZeroU64;
+///
+/// use oauth::signature_method::Debug;
+///
+/// let client = oauth::Credentials::new("9djdj82h48djs9d2", "j49sk3j29djd");
+/// let request = oauth::ParameterList::new([("a2", "r b")]);
+///
+/// let base_string = RefCell::new(String::new());
+/// let signature_method = Debug::new(oauth::HMAC_SHA1).callback(|s: &str| {
+///     // You would typically write this to a log instead.
+///     *base_string.borrow_mut() = s.to_owned();
+/// });
+/// let mut builder = oauth::Builder::<_, _>::new(client, signature_method);
+/// builder.nonce("7d8f3e4a").timestamp(NonZeroU64::new(137131201));
+/// let authorization = builder.post("http://example.com/request", &request);
+///
+/// assert_eq!(
+///     *base_string.borrow(),
+///     "POST&http%3A%2F%2Fexample.com%2Frequest&\
+///      a2%3Dr%2520b%26\
+///      oauth_consumer_key%3D9djdj82h48djs9d2%26\
+///      oauth_nonce%3D7d8f3e4a%26\
+///      oauth_signature_method%3DHMAC-SHA1%26\
+///      oauth_timestamp%3D137131201",
+/// );
+///
+/// // The signature is that of the wrapped signature method.
+/// let mut builder = oauth::Builder::<_, _>::new(client, oauth::HMAC_SHA1);
+/// builder.nonce("7d8f3e4a").timestamp(NonZeroU64::new(137131201));
+/// assert_eq!(authorization, builder.post("http://example.com/request", &request));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Debug<SM, F = fn(&str)> {
+    inner: SM,
+    callback: F,
+}
+
+/// A `Sign` implementation that signs a signature base string with `S` and captures it.
+#[derive(Clone)]
+pub struct DebugSign<S, F = fn(&str)> {
+    inner: S,
+    base_string: String,
+    callback: F,
+}
+
+impl<SM> Debug<SM> {
+    /// Creates a new `Debug` that wraps `inner`.
+    pub fn new(inner: SM) -> Self {
+        Debug {
+            inner,
+            callback: |_| {},
+        }
+    }
+}
+
+impl<SM, F> Debug<SM, F> {
+    /// Sets a callback that is called with the whole signature base string when the signing
+    /// process is finished, i.e. when [`Sign::end`] is called.
+    pub fn callback<G: FnMut(&str)>(self, callback: G) -> Debug<SM, G> {
+        Debug {
+            inner: self.inner,
+            callback,
+        }
+    }
+
+    /// Consumes the `Debug`, returning the wrapped signature method.
+    pub fn into_inner(self) -> SM {
+        self.inner
+    }
+}
+
+impl<SM: fmt::Debug, F> fmt::Debug for Debug<SM, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Debug").field(&self.inner).finish()
+    }
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for DebugSign<S, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DebugSign")
+            .field("inner", &self.inner)
+            .field("base_string", &self.base_string)
+            .finish()
+    }
+}
+
+impl<SM: SignatureMethod, F: FnMut(&str)> SignatureMethod for Debug<SM, F> {
+    type Sign = DebugSign<SM::Sign, F>;
+
+    fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> Self::Sign {
+        DebugSign {
+            inner: self.inner.sign_with(client_secret, token_secret),
+            base_string: String::new(),
+            callback: self.callback,
+        }
+    }
+}
+
+impl<S, F> DebugSign<S, F> {
+    /// Writes `key%3D` and `value` to the signature base string, returning the start position of
+    /// the value so that the value can be passed on without formatting it again.
+    fn write_parameter<V: Display>(&mut self, key: &str, value: V) -> usize {
+        write!(self.base_string, "{}%3D", key).unwrap();
+        let start = self.base_string.len();
+        write!(self.base_string, "{}", value).unwrap();
+        start
+    }
+}
+
+macro_rules! forward_parameter {
+    ($($name:ident,)*) => {$(
+        fn $name<V: Display>(&mut self, value: V) {
+            let start = self.write_parameter(concat!("oauth_", stringify!($name)), value);
+            self.inner.$name(&self.base_string[start..]);
+        }
+    )*};
+}
+
+impl<S: Sign, F: FnMut(&str)> Sign for DebugSign<S, F> {
+    type Signature = S::Signature;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.inner.get_signature_method_name()
+    }
+
+    fn request_method(&mut self, method: &str) {
+        write!(self.base_string, "{}&", method).unwrap();
+        self.inner.request_method(method);
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        let start = self.base_string.len();
+        write!(self.base_string, "{}", uri).unwrap();
+        self.inner.uri(&self.base_string[start..]);
+        self.base_string.push('&');
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        let start = self.write_parameter(key, value);
+        self.inner.parameter(key, &self.base_string[start..]);
+    }
+
+    fn delimiter(&mut self) {
+        self.base_string.push_str("%26");
+        self.inner.delimiter();
+    }
+
+    fn end(mut self) -> S::Signature {
+        (self.callback)(&self.base_string);
+        self.inner.end()
+    }
+
+    forward_parameter! { callback, consumer_key, nonce, }
+
+    fn use_nonce(&self) -> bool {
+        self.inner.use_nonce()
+    }
+
+    fn signature_method(&mut self) {
+        let name = self.inner.get_signature_method_name();
+        write!(self.base_string, "oauth_signature_method%3D{}", name).unwrap();
+        self.inner.signature_method();
+    }
+
+    fn timestamp(&mut self, value: u64) {
+        write!(self.base_string, "oauth_timestamp%3D{}", value).unwrap();
+        self.inner.timestamp(value);
+    }
+
+    fn use_timestamp(&self) -> bool {
+        self.inner.use_timestamp()
+    }
+
+    forward_parameter! { token, verifier, }
+
+    fn version(&mut self) {
+        self.base_string.push_str("oauth_version%3D1.0");
+        self.inner.version();
+    }
+
+    fn debug_base_string(&self) -> Option<&str> {
+        Some(&self.base_string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::signature_method::PLAINTEXT;
+
+    #[test]
+    fn debug_base_string() {
+        let mut sign = Debug::new(PLAINTEXT).sign_with("client_secret", Some("token_secret"));
+        assert_eq!(sign.debug_base_string(), Some(""));
+
+        sign.request_method("GET");
+        sign.uri("https%3A%2F%2Fexample.com%2F");
+        sign.consumer_key("key");
+        sign.delimiter();
+        sign.signature_method();
+        sign.delimiter();
+        sign.parameter("q", "a%2520b");
+        sign.delimiter();
+        sign.version();
+        assert_eq!(
+            sign.debug_base_string(),
+            Some(
+                "GET&https%3A%2F%2Fexample.com%2F&\
+                 oauth_consumer_key%3Dkey%26\
+                 oauth_signature_method%3DPLAINTEXT%26\
+                 q%3Da%2520b%26\
+                 oauth_version%3D1.0"
+            ),
+        );
+        assert_eq!(sign.end().to_string(), "client_secret&token_secret");
+    }
+}
@@ -8,7 +8,8 @@ use crate::util::PercentEncode;
 #[derive(Clone, Debug)]
 pub struct UpdateSign<U>(pub U);
 
-pub struct Base64PercentEncodeDisplay<A>(pub A);
+/// Displays the bytes Base64-encoded with the given configuration and then percent-encoded.
+pub struct Base64PercentEncodeDisplay<A>(pub A, pub base64::Config);
 
 struct UpdateWrite<'a, M>(&'a mut M);
 
@@ -33,12 +34,20 @@ impl<U: Update> UpdateSign<U> {
     }
 }
 
+/// Returns the Base64 configuration for the signature, which is the standard alphabet with
+/// padding unless `url_safe` or `unpadded` is `true`.
+pub fn base64_config(url_safe: bool, unpadded: bool) -> base64::Config {
+    let charset = if url_safe {
+        base64::CharacterSet::UrlSafe
+    } else {
+        base64::CharacterSet::Standard
+    };
+    base64::Config::new(charset, !unpadded)
+}
+
 impl<A: AsRef<[u8]>> Display for Base64PercentEncodeDisplay<A> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let d = PercentEncode(Base64Display::with_config(
-            self.0.as_ref(),
-            base64::STANDARD,
-        ));
+        let d = PercentEncode(Base64Display::with_config(self.0.as_ref(), self.1));
         Display::fmt(&d, f)
     }
 }
@@ -70,5 +70,22 @@ impl<L: Sign, R: Sign> Sign for Either<L, R> {
         fn token[V: Display](&mut self, value: V);
         fn verifier[V: Display](&mut self, value: V);
         fn version(&mut self);
+        fn debug_base_string(&self) -> Option<&str>;
+    }
+}
+
+impl<L: Signature, R: Signature> Signature for Either<L, R> {
+    fn write_encoded(&self, dst: &mut dyn Write) -> fmt::Result {
+        match *self {
+            Either::Left(ref l) => l.write_encoded(dst),
+            Either::Right(ref r) => r.write_encoded(dst),
+        }
+    }
+
+    fn as_encoded_str(&self) -> Option<&str> {
+        match *self {
+            Either::Left(ref l) => l.as_encoded_str(),
+            Either::Right(ref r) => r.as_encoded_str(),
+        }
     }
 }
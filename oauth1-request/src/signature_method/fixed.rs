@@ -0,0 +1,82 @@
+//! A pseudo signature method that always produces the same signature.
+//!
+//! This is useful for testing code that constructs requests independently of the actual
+//! cryptography, e.g. in snapshot tests of the `Authorization` header.
+
+use core::fmt::Display;
+
+use super::{Sign, SignatureMethod};
+
+/// A `SignatureMethod` that ignores the signature base string and always produces the given
+/// `oauth_signature` value.
+///
+/// The signature is written into the output as is, so it should be URL-encoded in advance.
+/// The `oauth_signature_method` value is `"FIXED"`.
+#[derive(Clone, Copy, Debug)]
+pub struct Fixed<'a>(pub &'a str);
+
+/// A `Sign` implementation that returns a fixed signature.
+#[derive(Clone, Copy, Debug)]
+pub struct FixedSign<'a>(&'a str);
+
+const NAME: &str = "FIXED";
+
+impl<'a> SignatureMethod for Fixed<'a> {
+    type Sign = FixedSign<'a>;
+
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Self::Sign {
+        FixedSign(self.0)
+    }
+}
+
+impl<'a> Sign for FixedSign<'a> {
+    type Signature = &'a str;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        NAME
+    }
+
+    fn request_method(&mut self, _method: &str) {}
+
+    fn uri<T: Display>(&mut self, _uri: T) {}
+
+    fn parameter<V: Display>(&mut self, _key: &str, _value: V) {}
+
+    fn delimiter(&mut self) {}
+
+    fn end(self) -> &'a str {
+        self.0
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use core::num::NonZeroU64;
+
+    use oauth_credentials::Credentials;
+
+    use super::*;
+    use crate::Builder;
+
+    #[test]
+    fn fixed() {
+        let client = Credentials::new("ck", "cs");
+        let authorization = Builder::<_, &str>::new(client, Fixed("some-signature"))
+            .nonce("nonce")
+            .timestamp(NonZeroU64::new(1))
+            .get("https://example.com/api/foo", &());
+        assert_eq!(
+            authorization,
+            "OAuth \
+             oauth_consumer_key=\"ck\",\
+             oauth_nonce=\"nonce\",\
+             oauth_signature_method=\"FIXED\",\
+             oauth_timestamp=\"1\",\
+             oauth_signature=\"some-signature\"",
+        );
+    }
+}
@@ -3,6 +3,10 @@
 //! [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.2
 //!
 //! This module is only available when `hmac-sha1` feature is activated.
+//!
+//! The signature base string is fed to the HMAC incrementally as it is serialized, so signing
+//! a request with this method does not allocate a buffer for the whole signature base string
+//! (or any heap memory at all).
 
 use core::fmt::{self, Debug, Display, Formatter, Write};
 
@@ -13,18 +17,20 @@ use digest::OutputSizeUser;
 use hmac::{Hmac, Mac};
 use sha1::{Digest, Sha1};
 
-use super::digest_common::{Base64PercentEncodeDisplay, UpdateSign};
-use super::{write_signing_key, Sign, SignatureMethod};
+use super::digest_common::{base64_config, Base64PercentEncodeDisplay, UpdateSign};
+use super::{write_signing_key, Sign, Signature, SignatureMethod};
 
 /// The `HMAC-SHA1` signature method.
 #[derive(Clone, Copy, Default)]
 pub struct HmacSha1 {
-    _priv: (),
+    url_safe_base64: bool,
+    unpadded_base64: bool,
 }
 
 /// A type that signs a signature base string with the HMAC-SHA1 signature algorithm.
 #[derive(Clone, Debug)]
 pub struct HmacSha1Sign {
+    base64_config: base64::Config,
     inner: UpdateSign<Hmac<Sha1>>,
 }
 
@@ -46,9 +52,40 @@ enum SigningKey {
 }
 
 impl HmacSha1 {
+    /// The `oauth_signature_method` string for the signature method (`"HMAC-SHA1"`).
+    pub const NAME: &'static str = "HMAC-SHA1";
+
     /// Creates a new `HmacSha1`.
     pub const fn new() -> Self {
-        HmacSha1 { _priv: () }
+        HmacSha1 {
+            url_safe_base64: false,
+            unpadded_base64: false,
+        }
+    }
+
+    /// Sets whether to encode the signature with the URL-safe Base64 alphabet (`-` and `_`)
+    /// instead of the standard one (`+` and `/`).
+    ///
+    /// The OAuth standard requires the standard alphabet and this is only useful for talking to
+    /// nonconforming servers that expect the URL-safe one. The signature is percent-encoded
+    /// either way.
+    pub const fn url_safe_base64(self, url_safe_base64: bool) -> Self {
+        HmacSha1 {
+            url_safe_base64,
+            ..self
+        }
+    }
+
+    /// Sets whether to omit the trailing `=` padding characters of the Base64-encoded
+    /// signature.
+    ///
+    /// The OAuth standard requires the padding and this is only useful for talking to
+    /// nonconforming servers that strip the padding before comparing signatures.
+    pub const fn unpadded_base64(self, unpadded_base64: bool) -> Self {
+        HmacSha1 {
+            unpadded_base64,
+            ..self
+        }
     }
 }
 
@@ -63,20 +100,33 @@ impl Debug for HmacSha1 {
 impl SignatureMethod for HmacSha1 {
     type Sign = HmacSha1Sign;
 
+    fn name(&self) -> &'static str {
+        HmacSha1::NAME
+    }
+
     fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> HmacSha1Sign {
         let mut key = SigningKey::new();
         write_signing_key(&mut key, client_secret, token_secret).unwrap();
         HmacSha1Sign {
+            base64_config: base64_config(self.url_safe_base64, self.unpadded_base64),
             inner: UpdateSign(key.into_hmac()),
         }
     }
 }
 
+impl HmacSha1Sign {
+    /// Checks the resulting signature against the raw `tag` bytes in constant time.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn verify(self, tag: &[u8]) -> bool {
+        self.inner.0.verify_slice(tag).is_ok()
+    }
+}
+
 impl Sign for HmacSha1Sign {
     type Signature = HmacSha1Signature;
 
     fn get_signature_method_name(&self) -> &'static str {
-        "HMAC-SHA1"
+        HmacSha1::NAME
     }
 
     fn request_method(&mut self, method: &str) {
@@ -97,11 +147,16 @@ impl Sign for HmacSha1Sign {
 
     fn end(self) -> HmacSha1Signature {
         HmacSha1Signature {
-            inner: Base64PercentEncodeDisplay(self.inner.0.finalize().into_bytes()),
+            inner: Base64PercentEncodeDisplay(
+                self.inner.0.finalize().into_bytes(),
+                self.base64_config,
+            ),
         }
     }
 }
 
+impl Signature for HmacSha1Signature {}
+
 impl Display for HmacSha1Signature {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         self.inner.fmt(f)
@@ -182,4 +237,51 @@ mod tests {
             assert_eq!(skm.finalize().into_bytes(), m.finalize().into_bytes());
         }
     }
+
+    #[test]
+    fn url_safe_base64() {
+        use alloc::string::{String, ToString};
+
+        let sign = |method: HmacSha1, value: &str| -> String {
+            let mut sign = method.sign_with("secret", None);
+            sign.request_method("GET");
+            sign.uri("http%3A%2F%2Fexample.com%2F");
+            sign.parameter("a", value);
+            let signature = sign.end().to_string();
+            percent_encoding::percent_decode_str(&signature)
+                .decode_utf8()
+                .unwrap()
+                .into_owned()
+        };
+
+        let mut affected = false;
+        for i in 0..32 {
+            let value = i.to_string();
+            let standard = sign(HmacSha1::new(), &value);
+            let url_safe = sign(HmacSha1::new().url_safe_base64(true), &value);
+            affected |= standard.contains(&['+', '/'][..]);
+            assert!(!url_safe.contains(&['+', '/'][..]));
+            assert_eq!(url_safe.replace('-', "+").replace('_', "/"), standard);
+        }
+        assert!(affected);
+    }
+
+    #[test]
+    fn unpadded_base64() {
+        use alloc::string::{String, ToString};
+
+        let sign = |method: HmacSha1| -> String {
+            let mut sign = method.sign_with("secret", None);
+            sign.request_method("GET");
+            sign.uri("http%3A%2F%2Fexample.com%2F");
+            sign.end().to_string()
+        };
+
+        let padded = sign(HmacSha1::new());
+        let unpadded = sign(HmacSha1::new().unpadded_base64(true));
+        // A SHA-1 digest is 20 bytes long, which takes one padding character (`%3D`).
+        assert!(padded.ends_with("%3D"));
+        assert_eq!(unpadded, padded.trim_end_matches("%3D"));
+        assert_eq!(sign(HmacSha1::new().unpadded_base64(false)), padded);
+    }
 }
@@ -6,19 +6,17 @@
 
 use core::fmt::{self, Debug, Display, Formatter, Write};
 
-use digest::core_api::BlockSizeUser;
-use digest::generic_array::sequence::GenericSequence;
-use digest::generic_array::GenericArray;
-use digest::{OutputSizeUser, Update};
-use hmac_sha256::Hash;
+use digest::Update;
+use hmac_sha256_1::Hash;
 
-use super::digest_common::{Base64PercentEncodeDisplay, UpdateSign};
-use super::{write_signing_key, Sign, SignatureMethod};
+use super::digest_common::{base64_config, Base64PercentEncodeDisplay, UpdateSign};
+use super::{write_signing_key, Sign, Signature, SignatureMethod};
 
 /// The `HMAC-SHA1` signature method.
 #[derive(Clone, Copy, Default)]
 pub struct HmacSha256 {
-    _priv: (),
+    url_safe_base64: bool,
+    unpadded_base64: bool,
 }
 
 #[derive(Clone)]
@@ -39,6 +37,7 @@ impl Debug for Hasher256 {
 /// A type that signs a signature base string with the HMAC-SHA1 signature algorithm.
 #[derive(Clone)]
 pub struct HmacSha256Sign {
+    base64_config: base64::Config,
     inner: UpdateSign<Hasher256>,
 }
 
@@ -48,8 +47,6 @@ impl Debug for HmacSha256Sign {
     }
 }
 
-type Hash256 = [u8; 32];
-
 /// A signature produced by an `HmacSha1Sign`.
 pub struct HmacSha256Signature {
     inner: Base64PercentEncodeDisplay<[u8; 32]>,
@@ -62,9 +59,40 @@ pub const HMAC_SHA1: HmacSha256 = HmacSha256::new();
 struct SigningKey(Hasher256);
 
 impl HmacSha256 {
+    /// The `oauth_signature_method` string for the signature method (`"HMAC-SHA256"`).
+    pub const NAME: &'static str = "HMAC-SHA256";
+
     /// Creates a new `HmacSha1`.
     pub const fn new() -> Self {
-        HmacSha256 { _priv: () }
+        HmacSha256 {
+            url_safe_base64: false,
+            unpadded_base64: false,
+        }
+    }
+
+    /// Sets whether to encode the signature with the URL-safe Base64 alphabet (`-` and `_`)
+    /// instead of the standard one (`+` and `/`).
+    ///
+    /// The OAuth standard requires the standard alphabet and this is only useful for talking to
+    /// nonconforming servers that expect the URL-safe one. The signature is percent-encoded
+    /// either way.
+    pub const fn url_safe_base64(self, url_safe_base64: bool) -> Self {
+        HmacSha256 {
+            url_safe_base64,
+            ..self
+        }
+    }
+
+    /// Sets whether to omit the trailing `=` padding characters of the Base64-encoded
+    /// signature.
+    ///
+    /// The OAuth standard requires the padding and this is only useful for talking to
+    /// nonconforming servers that strip the padding before comparing signatures.
+    pub const fn unpadded_base64(self, unpadded_base64: bool) -> Self {
+        HmacSha256 {
+            unpadded_base64,
+            ..self
+        }
     }
 }
 
@@ -79,10 +107,15 @@ impl Debug for HmacSha256 {
 impl SignatureMethod for HmacSha256 {
     type Sign = HmacSha256Sign;
 
+    fn name(&self) -> &'static str {
+        HmacSha256::NAME
+    }
+
     fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> HmacSha256Sign {
         let mut key = SigningKey::new();
         write_signing_key(&mut key, client_secret, token_secret).unwrap();
         HmacSha256Sign {
+            base64_config: base64_config(self.url_safe_base64, self.unpadded_base64),
             inner: UpdateSign(key.into_hmac()),
         }
     }
@@ -92,7 +125,7 @@ impl Sign for HmacSha256Sign {
     type Signature = HmacSha256Signature;
 
     fn get_signature_method_name(&self) -> &'static str {
-        "HMAC-SHA256"
+        HmacSha256::NAME
     }
 
     fn request_method(&mut self, method: &str) {
@@ -113,11 +146,13 @@ impl Sign for HmacSha256Sign {
 
     fn end(self) -> HmacSha256Signature {
         HmacSha256Signature {
-            inner: Base64PercentEncodeDisplay(self.inner.0 .0.finalize()),
+            inner: Base64PercentEncodeDisplay(self.inner.0 .0.finalize(), self.base64_config),
         }
     }
 }
 
+impl Signature for HmacSha256Signature {}
+
 impl Display for HmacSha256Signature {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         self.inner.fmt(f)
@@ -147,17 +182,11 @@ impl Write for SigningKey {
 
 #[cfg(test)]
 mod tests {
-    extern crate alloc;
-
-    use alloc::vec::Vec;
-
-    use digest::generic_array::typenum::Unsigned;
-
     use super::*;
 
     #[test]
     fn signing_key() {
-        let mut sk = SigningKey::new();
+        let _sk = SigningKey::new();
         //let mut k = Vec::new();
         /*
         for _ in 0..=<Sha1 as BlockSizeUser>::BlockSize::to_usize() + 1 {
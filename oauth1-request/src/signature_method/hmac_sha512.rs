@@ -0,0 +1,315 @@
+//! The `HMAC-SHA512` signature method.
+//!
+//! This is not a part of the OAuth 1.0 standard ([RFC 5849][rfc]), but some providers accept it
+//! as a stronger alternative to `HMAC-SHA1`. The signature is computed in the same way as
+//! `HMAC-SHA1` ([RFC 5849 section 3.4.2.][rfc-hmac]), except that the hash function is SHA-512.
+//!
+//! [rfc]: https://tools.ietf.org/html/rfc5849
+//! [rfc-hmac]: https://tools.ietf.org/html/rfc5849#section-3.4.2
+//!
+//! This module is only available when `hmac-sha512` feature is activated.
+
+use core::fmt::{self, Debug, Display, Formatter, Write};
+
+use digest::Update;
+use hmac_sha512_1::Hash;
+
+use super::digest_common::{base64_config, Base64PercentEncodeDisplay, UpdateSign};
+use super::{write_signing_key, Sign, Signature, SignatureMethod};
+
+/// The block size of SHA-512 in bytes.
+const BLOCK_SIZE: usize = 128;
+
+/// The `HMAC-SHA512` signature method.
+#[derive(Clone, Copy, Default)]
+pub struct HmacSha512 {
+    url_safe_base64: bool,
+    unpadded_base64: bool,
+}
+
+/// An HMAC-SHA512 state, keeping the outer padded key until the finalization.
+#[derive(Clone)]
+struct Hasher512 {
+    inner: Hash,
+    outer_key: [u8; BLOCK_SIZE],
+}
+
+/// A type that signs a signature base string with the HMAC-SHA512 signature algorithm.
+#[derive(Clone)]
+pub struct HmacSha512Sign {
+    base64_config: base64::Config,
+    inner: UpdateSign<Hasher512>,
+}
+
+/// A signature produced by an `HmacSha512Sign`.
+pub struct HmacSha512Signature {
+    inner: Base64PercentEncodeDisplay<[u8; 64]>,
+}
+
+/// The `HMAC-SHA512` signature method with a default configuration.
+pub const HMAC_SHA512: HmacSha512 = HmacSha512::new();
+
+#[derive(Clone)]
+enum SigningKey {
+    Key { buf: [u8; BLOCK_SIZE], pos: usize },
+    Digest(Hash),
+}
+
+impl HmacSha512 {
+    /// The `oauth_signature_method` string for the signature method (`"HMAC-SHA512"`).
+    pub const NAME: &'static str = "HMAC-SHA512";
+
+    /// Creates a new `HmacSha512`.
+    pub const fn new() -> Self {
+        HmacSha512 {
+            url_safe_base64: false,
+            unpadded_base64: false,
+        }
+    }
+
+    /// Sets whether to encode the signature with the URL-safe Base64 alphabet (`-` and `_`)
+    /// instead of the standard one (`+` and `/`).
+    ///
+    /// The signature is percent-encoded either way.
+    pub const fn url_safe_base64(self, url_safe_base64: bool) -> Self {
+        HmacSha512 {
+            url_safe_base64,
+            ..self
+        }
+    }
+
+    /// Sets whether to omit the trailing `=` padding characters of the Base64-encoded
+    /// signature.
+    pub const fn unpadded_base64(self, unpadded_base64: bool) -> Self {
+        HmacSha512 {
+            unpadded_base64,
+            ..self
+        }
+    }
+}
+
+impl Debug for HmacSha512 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        #[derive(Debug)]
+        struct HmacSha512;
+        HmacSha512.fmt(f)
+    }
+}
+
+impl SignatureMethod for HmacSha512 {
+    type Sign = HmacSha512Sign;
+
+    fn name(&self) -> &'static str {
+        HmacSha512::NAME
+    }
+
+    fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> HmacSha512Sign {
+        let mut key = SigningKey::new();
+        write_signing_key(&mut key, client_secret, token_secret).unwrap();
+        HmacSha512Sign {
+            base64_config: base64_config(self.url_safe_base64, self.unpadded_base64),
+            inner: UpdateSign(key.into_hmac()),
+        }
+    }
+}
+
+impl Hasher512 {
+    fn new(key: &[u8]) -> Self {
+        debug_assert!(key.len() <= BLOCK_SIZE);
+
+        let mut inner_key = [0x36; BLOCK_SIZE];
+        let mut outer_key = [0x5c; BLOCK_SIZE];
+        for ((i, o), k) in inner_key.iter_mut().zip(&mut outer_key).zip(key) {
+            *i ^= k;
+            *o ^= k;
+        }
+
+        let mut inner = Hash::new();
+        inner.update(&inner_key[..]);
+        Hasher512 { inner, outer_key }
+    }
+
+    fn finalize(self) -> [u8; 64] {
+        let mut outer = Hash::new();
+        outer.update(&self.outer_key[..]);
+        outer.update(self.inner.finalize());
+        outer.finalize()
+    }
+}
+
+impl Update for Hasher512 {
+    fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+}
+
+impl Debug for Hasher512 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hasher512").finish()
+    }
+}
+
+impl Debug for HmacSha512Sign {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HmacSha512Sign").finish()
+    }
+}
+
+impl Sign for HmacSha512Sign {
+    type Signature = HmacSha512Signature;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        HmacSha512::NAME
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.inner.request_method(method);
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        self.inner.uri(uri);
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        self.inner.parameter(key, value);
+    }
+
+    fn delimiter(&mut self) {
+        self.inner.delimiter();
+    }
+
+    fn end(self) -> HmacSha512Signature {
+        HmacSha512Signature {
+            inner: Base64PercentEncodeDisplay(self.inner.0.finalize(), self.base64_config),
+        }
+    }
+}
+
+impl Signature for HmacSha512Signature {}
+
+impl Display for HmacSha512Signature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl SigningKey {
+    fn new() -> Self {
+        SigningKey::Key {
+            buf: [0; BLOCK_SIZE],
+            pos: 0,
+        }
+    }
+
+    fn write(&mut self, input: &[u8]) {
+        *self = match *self {
+            SigningKey::Key {
+                ref mut buf,
+                ref mut pos,
+            } => {
+                if input.len() > buf.len() - *pos {
+                    let mut digest = Hash::new();
+                    digest.update(&buf[..*pos]);
+                    digest.update(input);
+                    SigningKey::Digest(digest)
+                } else {
+                    buf[*pos..(*pos + input.len())].copy_from_slice(input);
+                    *pos += input.len();
+                    return;
+                }
+            }
+            SigningKey::Digest(ref mut digest) => {
+                digest.update(input);
+                return;
+            }
+        };
+    }
+
+    fn into_hmac(self) -> Hasher512 {
+        match self {
+            SigningKey::Key { ref buf, pos } => Hasher512::new(&buf[..pos]),
+            SigningKey::Digest(digest) => Hasher512::new(&digest.finalize()),
+        }
+    }
+}
+
+impl Write for SigningKey {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write(s.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::string::{String, ToString};
+
+    use super::*;
+
+    fn decode(signature: &str) -> String {
+        percent_encoding::percent_decode_str(signature)
+            .decode_utf8()
+            .unwrap()
+            .into_owned()
+    }
+
+    #[test]
+    fn known_vector() {
+        // The example of RFC 5849 section 3.4.1.1, signed with `HMAC-SHA512`.
+        let mut sign = HMAC_SHA512.sign_with("kd94hf93k423kf44", Some("pfkkdhi9sl3r4s00"));
+        sign.request_method("GET");
+        sign.uri("http%3A%2F%2Fphotos.example.net%2Fphotos");
+        sign.parameter("file", "vacation.jpg");
+        for &(k, v) in &[
+            ("oauth_consumer_key", "dpf43f3p2l4k3l03"),
+            ("oauth_nonce", "kllo9940pd9333jh"),
+            ("oauth_signature_method", "HMAC-SHA512"),
+            ("oauth_timestamp", "1191242096"),
+            ("oauth_token", "nnch734d00sl2jdk"),
+            ("oauth_version", "1.0"),
+            ("size", "original"),
+        ] {
+            sign.delimiter();
+            sign.parameter(k, v);
+        }
+        let signature = decode(&sign.end().to_string());
+
+        assert_eq!(
+            signature,
+            "nQYVqZl8EkEH4fThSn+25i1gc68aX+FHTHSAXrxIl2ixdAofXM/pq2x90UaOFIZQxvkzE5VRZpPbjo6i+fe6rg==",
+        );
+        // 64 bytes take 88 Base64 characters including two padding characters.
+        assert_eq!(signature.len(), 88);
+    }
+
+    #[test]
+    fn long_key() {
+        // A key longer than the block size is hashed first.
+        let client_secret = "a".repeat(200);
+        let mut sign = HMAC_SHA512.sign_with(&client_secret, None);
+        sign.request_method("GET");
+        sign.uri("x");
+        assert_eq!(
+            decode(&sign.end().to_string()),
+            "LCCL24tzynR0nGZY8H7LLz8O7Q17l66XaapwlU+wqzFCMnBxnpmrOi7jBRSWSo4GzlSM3yq3cVbYUBivDwXXRg==",
+        );
+    }
+
+    #[test]
+    fn unpadded_base64() {
+        let sign = |method: HmacSha512| -> String {
+            let mut sign = method.sign_with("secret", None);
+            sign.request_method("GET");
+            sign.uri("http%3A%2F%2Fexample.com%2F");
+            sign.end().to_string()
+        };
+
+        let padded = sign(HmacSha512::new());
+        let unpadded = sign(HmacSha512::new().unpadded_base64(true));
+        assert!(padded.ends_with("%3D%3D"));
+        assert_eq!(unpadded, padded.trim_end_matches("%3D"));
+        assert_eq!(decode(&unpadded).len(), 86);
+    }
+}
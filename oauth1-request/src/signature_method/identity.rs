@@ -0,0 +1,111 @@
+//! A pseudo signature method that uses the signature base string
+//! ([RFC 5849 section 3.4.1.][rfc]) itself as the signature.
+//!
+//! [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1
+//!
+//! This is useful for testing serializers and `Request` implementations without any cryptography,
+//! since the resulting `oauth_signature` shows exactly what would have been signed.
+
+use alloc::string::String;
+use core::fmt::{Display, Write};
+
+use super::{Sign, SignatureMethod};
+
+/// A `SignatureMethod` that produces the signature base string as the `oauth_signature` value.
+///
+/// The shared-secrets passed to [`sign_with`](SignatureMethod::sign_with) are ignored.
+/// The `oauth_signature_method` value is `"IDENTITY"`.
+///
+/// The signature is written into the output as is. Since the signature base string is
+/// percent-encoded but contains `&` characters, the output is suitable for inspection rather than
+/// for sending. Use [`BaseString`](super::BaseString) instead if you need to capture the signature
+/// base string of a request that is signed with another signature method.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use std::num::NonZeroU64;
+///
+/// use oauth::serializer::auth::{self, Authorizer};
+/// use oauth::signature_method::Identity;
+/// use oauth::Request;
+///
+/// let client = oauth::Credentials::new("9djdj82h48djs9d2", "");
+/// let mut options = auth::Options::new();
+/// options.nonce("7d8f3e4a").timestamp(NonZeroU64::new(137131201));
+/// let request = oauth::ParameterList::new([("a2", "r b")]);
+///
+/// let serializer = Authorizer::form(
+///     "POST",
+///     "http://example.com/request",
+///     client,
+///     None,
+///     &options,
+///     Identity,
+/// );
+/// let form = request.serialize(serializer);
+///
+/// assert!(form.ends_with(
+///     "&oauth_signature=\
+///      POST&http%3A%2F%2Fexample.com%2Frequest&\
+///      a2%3Dr%2520b%26\
+///      oauth_consumer_key%3D9djdj82h48djs9d2%26\
+///      oauth_nonce%3D7d8f3e4a%26\
+///      oauth_signature_method%3DIDENTITY%26\
+///      oauth_timestamp%3D137131201",
+/// ));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Identity;
+
+/// A `Sign` implementation that returns the signature base string as the signature.
+#[derive(Clone, Debug, Default)]
+pub struct IdentitySign(String);
+
+const NAME: &str = "IDENTITY";
+
+impl SignatureMethod for Identity {
+    type Sign = IdentitySign;
+
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Self::Sign {
+        IdentitySign(String::new())
+    }
+}
+
+impl Sign for IdentitySign {
+    type Signature = String;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        NAME
+    }
+
+    fn request_method(&mut self, method: &str) {
+        write!(self.0, "{}&", method).unwrap();
+    }
+
+    fn uri<T: Display>(&mut self, uri: T) {
+        write!(self.0, "{}&", uri).unwrap();
+    }
+
+    fn parameter<V: Display>(&mut self, key: &str, value: V) {
+        write!(self.0, "{}%3D{}", key, value).unwrap();
+    }
+
+    fn delimiter(&mut self) {
+        self.0.push_str("%26");
+    }
+
+    fn end(self) -> String {
+        self.0
+    }
+
+    fn debug_base_string(&self) -> Option<&str> {
+        Some(&self.0)
+    }
+}
@@ -12,6 +12,7 @@ pub struct Plaintext<
     #[cfg(feature = "alloc")] W = alloc::string::String,
     #[cfg(not(feature = "alloc"))] W,
 > {
+    omit_nonce_and_timestamp: bool,
     marker: PhantomData<fn() -> W>,
 }
 
@@ -23,6 +24,7 @@ cfg_type_param_hack! {
         #[cfg(not(feature = "alloc"))] W,
     > {
         signing_key: W,
+        omit_nonce_and_timestamp: bool,
     }
 }
 
@@ -30,15 +32,23 @@ cfg_type_param_hack! {
 #[cfg(feature = "alloc")]
 pub const PLAINTEXT: Plaintext = Plaintext::new();
 
+const NAME: &str = "PLAINTEXT";
+
 #[cfg(feature = "alloc")]
 impl Plaintext {
+    /// The `oauth_signature_method` string for the signature method (`"PLAINTEXT"`).
+    pub const NAME: &'static str = NAME;
+
     /// Creates a new `Plaintext`.
     pub const fn new() -> Self {
         // `PhantomData::<fn() -> _>`s, which (rustc thinks to) contain a function pointer,
         // cannot appear in constant functions directly as of Rust 1.57, but this somehow works.
         // cf. <https://github.com/rust-lang/rust/issues/67649>
         const MARKER: PhantomData<fn() -> alloc::string::String> = PhantomData;
-        Plaintext { marker: MARKER }
+        Plaintext {
+            omit_nonce_and_timestamp: false,
+            marker: MARKER,
+        }
     }
 }
 
@@ -54,19 +64,54 @@ where
     /// Creates a new `Plaintext` that writes the resulting signatures into `W` values.
     pub fn with_buf() -> Self {
         Plaintext {
+            omit_nonce_and_timestamp: false,
             marker: PhantomData,
         }
     }
 }
 
-impl<W> Clone for Plaintext<W> {
-    fn clone(&self) -> Self {
+impl<W> Plaintext<W> {
+    /// Sets whether to omit the `oauth_nonce` and `oauth_timestamp` parameters.
+    ///
+    /// The OAuth standard ([RFC 5849 section 3.1.][rfc]) says that these parameters MAY be
+    /// omitted when using the `PLAINTEXT` method, but the OAuth Core 1.0 Revision A specification
+    /// used to require them, so they are included by default. Setting this to `true` omits them,
+    /// which some servers require.
+    ///
+    /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.1
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(feature = "alloc", doc = " ```")]
+    #[cfg_attr(not(feature = "alloc"), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+    /// let signature_method = oauth::PLAINTEXT.omit_nonce_and_timestamp(true);
+    /// let builder = oauth::Builder::<_, _>::new(client, signature_method);
+    ///
+    /// let authorization = builder.get("https://example.com/api/foo", &());
+    /// assert_eq!(
+    ///     authorization,
+    ///     "OAuth oauth_consumer_key=\"consumer_key\",\
+    ///      oauth_signature_method=\"PLAINTEXT\",\
+    ///      oauth_signature=\"consumer_secret&\"",
+    /// );
+    /// ```
+    pub fn omit_nonce_and_timestamp(self, omit_nonce_and_timestamp: bool) -> Self {
         Plaintext {
+            omit_nonce_and_timestamp,
             marker: PhantomData,
         }
     }
 }
 
+impl<W> Clone for Plaintext<W> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
 impl<W> Copy for Plaintext<W> {}
 
 impl<W> Debug for Plaintext<W> {
@@ -92,10 +137,17 @@ where
 {
     type Sign = PlaintextSign<W>;
 
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
     fn sign_with(self, client_secret: &str, token_secret: Option<&str>) -> Self::Sign {
         let mut signing_key = W::default();
         write_signing_key(&mut signing_key, client_secret, token_secret).unwrap();
-        PlaintextSign { signing_key }
+        PlaintextSign {
+            signing_key,
+            omit_nonce_and_timestamp: self.omit_nonce_and_timestamp,
+        }
     }
 }
 
@@ -106,7 +158,7 @@ where
     type Signature = W;
 
     fn get_signature_method_name(&self) -> &'static str {
-        "PLAINTEXT"
+        NAME
     }
 
     fn request_method(&mut self, _method: &str) {}
@@ -122,8 +174,15 @@ where
     }
 
     // The OAuth standard (section 3.1.) says that `oauth_timestamp` and `oauth_nonce` parameters
-    // MAY be omitted when using the `PLAINTEXT` signature method. So, technically, we could
-    // override `use_nonce` and `use_timestamp` so as not to use the parameters. However,
+    // MAY be omitted when using the `PLAINTEXT` signature method. However,
     // OAuth Core 1.0 Revision A (https://oauth.net/core/1.0a/) specification used to require these
-    // parameters. So, we don't override the methods here for compatibility's sake.
+    // parameters. So, we only omit them if the user opted in for compatibility's sake.
+
+    fn use_nonce(&self) -> bool {
+        !self.omit_nonce_and_timestamp
+    }
+
+    fn use_timestamp(&self) -> bool {
+        !self.omit_nonce_and_timestamp
+    }
 }
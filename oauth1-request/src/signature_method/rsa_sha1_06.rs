@@ -6,18 +6,19 @@
 
 extern crate alloc;
 
-pub use rsa06::pkcs8::DecodePrivateKey;
-pub use rsa06::RsaPrivateKey;
+pub use rsa06::errors::Error;
+pub use rsa06::pkcs8::{DecodePrivateKey, DecodePublicKey};
+pub use rsa06::{RsaPrivateKey, RsaPublicKey};
 
 use alloc::vec::Vec;
 use core::fmt::{self, Display, Formatter};
 
 use digest::Digest;
-use rsa06::{Hash, PaddingScheme};
+use rsa06::{Hash, PaddingScheme, PublicKey};
 use sha1::Sha1;
 
 use super::digest_common::{Base64PercentEncodeDisplay, UpdateSign};
-use super::{Sign, SignatureMethod};
+use super::{Sign, Signature, SignatureMethod};
 
 /// The `RSA-SHA1` signature method.
 #[derive(Clone, Debug)]
@@ -39,6 +40,9 @@ pub struct RsaSha1Signature {
 }
 
 impl RsaSha1 {
+    /// The `oauth_signature_method` string for the signature method (`"RSA-SHA1"`).
+    pub const NAME: &'static str = "RSA-SHA1";
+
     /// Creates a new `RsaSha1` that signs a signature base string with the given RSA private key.
     pub const fn new(key: RsaPrivateKey) -> Self {
         RsaSha1 { key }
@@ -69,6 +73,10 @@ impl AsRef<RsaSha1> for RsaPrivateKey {
 impl SignatureMethod for RsaSha1 {
     type Sign = RsaSha1Sign;
 
+    fn name(&self) -> &'static str {
+        RsaSha1::NAME
+    }
+
     fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Self::Sign {
         RsaSha1Sign {
             inner: UpdateSign(Sha1::default()),
@@ -80,6 +88,10 @@ impl SignatureMethod for RsaSha1 {
 impl<'a> SignatureMethod for &'a RsaSha1 {
     type Sign = RsaSha1Sign<&'a RsaPrivateKey>;
 
+    fn name(&self) -> &'static str {
+        RsaSha1::NAME
+    }
+
     fn sign_with(self, _client_secret: &str, _token_secret: Option<&str>) -> Self::Sign {
         RsaSha1Sign {
             inner: UpdateSign(Sha1::default()),
@@ -92,7 +104,7 @@ impl<'a> Sign for RsaSha1Sign {
     type Signature = RsaSha1Signature;
 
     fn get_signature_method_name(&self) -> &'static str {
-        "RSA-SHA1"
+        RsaSha1::NAME
     }
 
     fn request_method(&mut self, method: &str) {
@@ -124,7 +136,7 @@ impl<'a> Sign for RsaSha1Sign<&'a RsaPrivateKey> {
     type Signature = RsaSha1Signature;
 
     fn get_signature_method_name(&self) -> &'static str {
-        "RSA-SHA1"
+        RsaSha1::NAME
     }
 
     fn request_method(&mut self, method: &str) {
@@ -148,11 +160,50 @@ impl<'a> Sign for RsaSha1Sign<&'a RsaPrivateKey> {
         let digest = self.inner.0.finalize();
         let signature = self.key.sign(padding, &digest).unwrap();
         RsaSha1Signature {
-            inner: Base64PercentEncodeDisplay(signature),
+            inner: Base64PercentEncodeDisplay(signature, base64::STANDARD),
         }
     }
 }
 
+/// Verifies an `RSA-SHA1` signature of a signature base string with the given RSA public key.
+///
+/// `signature` is the base64-encoded `oauth_signature` value (i.e. after percent decoding).
+/// You can reconstruct the signature base string of an incoming request with
+/// [`BaseString`](super::BaseString).
+///
+/// ## Example
+///
+/// ```
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::signature_method::rsa_sha1_06::{self, RsaPublicKey};
+/// # use oauth::signature_method::rsa_sha1_06::{DecodePrivateKey, RsaPrivateKey};
+///
+/// # fn get_public_key() -> RsaPublicKey {
+/// #     let der = base64::decode("MIICdgIBADANBgkqhkiG9w0BAQEFAASCAmAwggJcAgEAAoGBALRiMLAh9iimur8VA7qVvdqxevEuUkW4K+2KdMXmnQbG9Aa7k7eBjK1S+0LYmVjPKlJGNXHDGuy5Fw/d7rjVJ0BLB+ubPK8iA/Tw3hLQgXMRRGRXXCn8ikfuQfjUS1uZSatdLB81mydBETlJhI6GH4twrbDJCR2Bwy/XWXgqgGRzAgMBAAECgYBYWVtleUzavkbrPjy0T5FMou8HX9u2AC2ry8vD/l7cqedtwMPp9k7TubgNFo+NGvKsl2ynyprOZR1xjQ7WgrgVB+mmuScOM/5HVceFuGRDhYTCObE+y1kxRloNYXnx3ei1zbeYLPCHdhxRYW7T0qcynNmwrn05/KO2RLjgQNalsQJBANeA3Q4Nugqy4QBUCEC09SqylT2K9FrrItqL2QKc9v0ZzO2uwllCbg0dwpVuYPYXYvikNHHg+aCWF+VXsb9rpPsCQQDWR9TT4ORdzoj+NccnqkMsDmzt0EfNaAOwHOmVJ2RVBspPcxt5iN4HI7HNeG6U5YsFBb+/GZbgfBT3kpNGWPTpAkBI+gFhjfJvRw38n3g/+UeAkwMI2TJQS4n8+hid0uus3/zOjDySH3XHCUnocn1xOJAyZODBo47E+67R4jV1/gzbAkEAklJaspRPXP877NssM5nAZMU0/O/NGCZ+3jPgDUno6WbJn5cqm8MqWhW1xGkImgRk+fkDBquiq4gPiT898jusgQJAd5Zrr6Q8AO/0isr/3aa6O6NLQxISLKcPDk2NOccAfS/xOtfOz4sJYM3+Bs4Io9+dZGSDCA54Lw03eHTNQghS0A==").unwrap();
+/// #     RsaPublicKey::from(&RsaPrivateKey::from_pkcs8_der(&der).unwrap())
+/// # }
+/// // Test case from <https://wiki.oauth.net/w/page/12238556/TestCases>.
+/// let public_key = get_public_key();
+/// let base_string = "GET&http%3A%2F%2Fphotos.example.net%2Fphotos&file%3Dvacaction.jpg%26\
+///     oauth_consumer_key%3Ddpf43f3p2l4k3l03%26oauth_nonce%3D13917289812797014437%26\
+///     oauth_signature_method%3DRSA-SHA1%26oauth_timestamp%3D1196666512%26\
+///     oauth_version%3D1.0%26size%3Doriginal";
+/// let signature = "jvTp/wX1TYtByB1m+Pbyo0lnCOLIsyGCH7wke8AUs3BpnwZJtAuEJkvQL2/9n4s5wUmUl4aCI4BwpraNx4RtEXMe5qg5T1LVTGliMRpKasKsW//e+RinhejgCuzoH26dyF8iY2ZZ/5D1ilgeijhV/vBka5twt399mXwaYdCwFYE=";
+///
+/// assert!(rsa_sha1_06::verify(base_string, signature, &public_key).is_ok());
+/// assert!(rsa_sha1_06::verify("GET&foo&bar", signature, &public_key).is_err());
+/// ```
+pub fn verify(base_string: &str, signature: &str, key: &RsaPublicKey) -> Result<(), Error> {
+    let signature =
+        base64::decode_config(signature, base64::STANDARD).map_err(|_| Error::Verification)?;
+    let padding = PaddingScheme::new_pkcs1v15_sign(Some(Hash::SHA1));
+    let digest = Sha1::digest(base_string.as_bytes());
+    key.verify(padding, &digest, &signature)
+}
+
+impl Signature for RsaSha1Signature {}
+
 impl Display for RsaSha1Signature {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         self.inner.fmt(f)
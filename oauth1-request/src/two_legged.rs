@@ -0,0 +1,310 @@
+//! A `Builder` for two-legged OAuth.
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::fmt::{self, Debug, Display, Write};
+use core::num::NonZeroU64;
+
+use oauth_credentials::Credentials;
+
+use crate::request::Request;
+use crate::serializer::{self, auth};
+use crate::signature_method::SignatureMethod;
+use crate::Builder;
+#[cfg(feature = "alloc")]
+use crate::{BuilderError, TooManyParameters};
+
+cfg_type_param_hack! {
+    /// A [`Builder`] for two-legged OAuth, which never sends token credentials.
+    ///
+    /// Unlike `Builder`, whose token can be set at any time with [`Builder::token`], this type
+    /// has no token at all, so code that must only send client credentials (e.g. app-only API
+    /// clients) cannot set one by accident.
+    ///
+    /// `TwoLeggedBuilder` only exposes the setters of the options and the signing methods of
+    /// `Builder` that are relevant to two-legged requests. Use [`into_builder`] to convert it
+    /// into a `Builder` if you need a token after all.
+    ///
+    /// [`into_builder`]: TwoLeggedBuilder::into_builder
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(all(feature = "std", feature = "default-nonce", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "std", feature = "default-nonce", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// let client = oauth::Credentials::new("consumer_key", "consumer_secret");
+    ///
+    /// let mut builder = oauth::TwoLeggedBuilder::<_, _>::new(client, oauth::HMAC_SHA1);
+    /// builder.realm("Example");
+    ///
+    /// let authorization = builder.get("https://example.com/api/items", &());
+    /// assert!(authorization.starts_with("OAuth realm=\"Example\","));
+    /// assert!(!authorization.contains("oauth_token="));
+    /// ```
+    #[derive(Clone)]
+    pub struct TwoLeggedBuilder<
+        'a,
+        SM,
+        #[cfg(feature = "alloc")] C = String,
+        #[cfg(not(feature = "alloc"))] C,
+    > {
+        inner: Builder<'a, SM, C>,
+    }
+}
+
+macro_rules! two_legged_setters {
+    ($($(#[$($attr:tt)+])* $name:ident($arg:ident: $T:ty);)*) => {doc_auto_cfg! {$(
+        #[doc = concat!("See [`Builder::", stringify!($name), "`].")]
+        $(#[$($attr)+])*
+        pub fn $name(&mut self, $arg: $T) -> &mut Self {
+            self.inner.$name($arg);
+            self
+        }
+    )*}};
+}
+
+impl<'a, SM: SignatureMethod, C: AsRef<str>> TwoLeggedBuilder<'a, SM, C> {
+    /// Creates a `TwoLeggedBuilder` that signs requests using the specified client credentials
+    /// and signature method.
+    pub fn new(client: Credentials<C>, signature_method: SM) -> Self {
+        TwoLeggedBuilder {
+            inner: Builder::new(client, signature_method),
+        }
+    }
+
+    two_legged_setters! {
+        realm(realm: impl Into<Option<&'a str>>);
+        encode_realm(encode_realm: bool);
+        nonce(nonce: impl Into<Option<&'a str>>);
+        timestamp(timestamp: impl Into<Option<NonZeroU64>>);
+        version(version: bool);
+        keep_query_in_uri(keep_query_in_uri: bool);
+        trim_values(trim_values: bool);
+        encode_set(encode_set: serializer::EncodeSet);
+        legacy_encoding(legacy_encoding: bool);
+        rfc_strict(rfc_strict: bool);
+        #[cfg(feature = "alloc")]
+        max_parameters(max_parameters: impl Into<Option<usize>>);
+    }
+
+    /// Resets all the options to their default values.
+    ///
+    /// See [`Builder::reset_options`].
+    pub fn reset_options(&mut self) -> &mut Self {
+        self.inner.reset_options();
+        self
+    }
+
+    /// Converts the `TwoLeggedBuilder` into a `Builder`, which allows setting a token.
+    pub fn into_builder(self) -> Builder<'a, SM, C> {
+        self.inner
+    }
+}
+
+impl<'a, SM: SignatureMethod, C: AsRef<str>> TwoLeggedBuilder<'a, SM, C> {
+    /// Returns the options of the `TwoLeggedBuilder`.
+    ///
+    /// See [`Builder::get_options`].
+    pub fn get_options(&self) -> &auth::Options<'a> {
+        self.inner.get_options()
+    }
+
+    builder_authorize_shorthand! {
+        get("GET");
+        put("PUT");
+        post("POST");
+        delete("DELETE");
+        options("OPTIONS");
+        head("HEAD");
+        connect("CONNECT");
+        patch("PATCH");
+        trace("TRACE");
+    }
+
+    builder_to_form_shorthand! {
+        put_form("PUT");
+        post_form("POST");
+        options_form("OPTIONS");
+        patch_form("PATCH");
+    }
+
+    builder_to_query_shorthand! {
+        get_query("GET");
+        put_query("PUT");
+        post_query("POST");
+        delete_query("DELETE");
+        options_query("OPTIONS");
+        head_query("HEAD");
+        connect_query("CONNECT");
+        patch_query("PATCH");
+        trace_query("TRACE");
+    }
+
+    builder_header_value_shorthand! {
+        get_header("GET");
+        put_header("PUT");
+        post_header("POST");
+        delete_header("DELETE");
+        options_header("OPTIONS");
+        head_header("HEAD");
+        connect_header("CONNECT");
+        patch_header("PATCH");
+        trace_header("TRACE");
+    }
+
+    doc_auto_cfg! {
+        /// Authorizes a request to `uri` with a custom HTTP request method,
+        /// returning an HTTP `Authorization` header value.
+        ///
+        /// See [`Builder::authorize`].
+        #[cfg(feature = "alloc")]
+        pub fn authorize<U, R>(&self, method: &str, uri: U, request: &R) -> String
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            self.inner.authorize(method, uri, request)
+        }
+
+        /// Same as `authorize` except that this returns an error if `request` has too many
+        /// parameters.
+        ///
+        /// See [`Builder::try_authorize`].
+        #[cfg(feature = "alloc")]
+        pub fn try_authorize<U, R>(
+            &self,
+            method: &str,
+            uri: U,
+            request: &R,
+        ) -> Result<String, TooManyParameters>
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            self.inner.try_authorize(method, uri, request)
+        }
+
+        /// Authorizes a request to `uri` after validating it, returning an HTTP `Authorization`
+        /// header value.
+        ///
+        /// See [`Builder::try_build`].
+        #[cfg(feature = "alloc")]
+        pub fn try_build<U, R>(
+            &self,
+            method: &str,
+            uri: U,
+            request: &R,
+        ) -> Result<String, BuilderError>
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            self.inner.try_build(method, uri, request)
+        }
+
+        /// Same as `try_build` except that this panics instead of returning an error.
+        ///
+        /// See [`Builder::build`].
+        #[cfg(feature = "alloc")]
+        pub fn build<U, R>(&self, method: &str, uri: U, request: &R) -> String
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            self.inner.build(method, uri, request)
+        }
+    }
+
+    doc_auto_cfg! {
+        /// Authorizes a request to `uri` with a custom HTTP request method,
+        /// returning an HTTP `Authorization` header value as an
+        /// [`http::HeaderValue`][http1::HeaderValue].
+        ///
+        /// See [`Builder::authorize_header_value`].
+        #[cfg(feature = "http")]
+        pub fn authorize_header_value<U, R>(
+            &self,
+            method: &str,
+            uri: U,
+            request: &R,
+        ) -> http1::HeaderValue
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            self.inner.authorize_header_value(method, uri, request)
+        }
+
+        /// Authorizes a request to `uri` with a custom HTTP request method, writing the OAuth
+        /// protocol parameters to an `x-www-form-urlencoded` string along with the other request
+        /// parameters.
+        ///
+        /// See [`Builder::to_form`].
+        #[cfg(feature = "alloc")]
+        pub fn to_form<U, R>(&self, method: &str, uri: U, request: &R) -> String
+        where
+            U: Display,
+            R: Request + ?Sized,
+            SM: Clone,
+        {
+            self.inner.to_form(method, uri, request)
+        }
+    }
+
+    /// Authorizes a request to `uri` with a custom HTTP request method, appending the OAuth
+    /// protocol parameters to `uri` along with the other request parameters.
+    ///
+    /// See [`Builder::to_query`].
+    pub fn to_query<W, R>(&self, method: &str, uri: W, request: &R) -> W
+    where
+        W: Display + Write,
+        R: Request + ?Sized,
+        SM: Clone,
+    {
+        self.inner.to_query(method, uri, request)
+    }
+}
+
+impl<'a, SM: SignatureMethod + Clone, C: AsRef<str>> Debug for TwoLeggedBuilder<'a, SM, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TwoLeggedBuilder")
+            .field(&self.inner)
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::request;
+    use crate::signature_method::PLAINTEXT;
+
+    use super::*;
+
+    #[test]
+    fn two_legged_builder() {
+        let client = Credentials::new("ck", "cs");
+        let mut builder = Builder::<_, &str>::new(client, PLAINTEXT);
+        builder.nonce("nonce").timestamp(NonZeroU64::new(1));
+        let mut two_legged = TwoLeggedBuilder::<_, &str>::new(client, PLAINTEXT);
+        two_legged.nonce("nonce").timestamp(NonZeroU64::new(1));
+
+        let uri = "https://example.com/api/items";
+        let request = request! { "q" => "rust" };
+        assert_eq!(two_legged.get(uri, &request), builder.get(uri, &request));
+        assert_eq!(
+            two_legged.post_form(uri, &request),
+            builder.post_form(uri, &request)
+        );
+        assert_eq!(
+            two_legged.build("PUT", uri, &request),
+            builder.build("PUT", uri, &request),
+        );
+        assert!(!two_legged.get(uri, &request).contains("oauth_token"));
+    }
+}
@@ -1,8 +1,29 @@
+#[cfg(feature = "idna")]
+mod host;
 mod oauth_parameter;
 mod percent_encoding;
+#[cfg(feature = "alloc")]
+mod query;
+mod trim;
+mod uri;
+mod write;
 
+#[cfg(feature = "idna")]
+pub use self::host::host_to_ascii;
 pub use self::oauth_parameter::OAuthParameter;
-pub use self::percent_encoding::{percent_encode, DoublePercentEncode, PercentEncode};
+pub use self::percent_encoding::{
+    debug_assert_key, percent_encode, percent_encode_into, BufferTooSmall, DoublePercentEncode,
+    DoublePercentEncodeWith, EncodeSet, PercentEncode, PercentEncodeWith,
+};
+#[cfg(feature = "alloc")]
+pub use self::query::QueryParameters;
+pub use self::trim::TrimAsciiWhitespace;
+pub use self::uri::{LowercaseSchemeAndHost, WithoutDefaultPort, WithoutFragment};
+pub use self::write::CatchError;
+#[cfg(feature = "alloc")]
+pub use self::write::CountWrite;
+#[cfg(feature = "std")]
+pub use self::write::IoWrite;
 
 /// Converts from `struct Foo<#[cfg(pred)] T>(T);` to
 /// `#[cfg(pred)] struct Foo<T>(T); #[cfg(not(pred))] struct Foo<>(T);` so that `#[derive]` work
@@ -140,7 +161,7 @@ macro_rules! impl_setters {
     };
     ($(#[$attr:meta])* $setter:ident: $t:ty, $($rest:tt)*) => {
         $(#[$attr])*
-        pub fn $setter(&mut self, $setter: impl Into<Option<$t>>) -> &mut Self {
+        pub fn $setter(&mut self, $setter: $t) -> &mut Self {
             self.$setter = $setter;
             self
         }
@@ -0,0 +1,62 @@
+use alloc::string::String;
+
+/// Converts the host of `uri` to its ASCII (Punycode) form if the host contains non-ASCII
+/// characters, so that the signature base string URI does not depend on which form the user
+/// provided.
+///
+/// `uri` is returned as is if it does not contain a non-ASCII host or the host is not a valid
+/// internationalized domain name.
+pub fn host_to_ascii(uri: String) -> String {
+    let authority_start = match uri.find("://") {
+        Some(i) => i + 3,
+        None => return uri,
+    };
+    let authority_end = uri[authority_start..]
+        .find(&['/', '?', '#'][..])
+        .map_or(uri.len(), |i| authority_start + i);
+    let host_start = uri[authority_start..authority_end]
+        .rfind('@')
+        .map_or(authority_start, |i| authority_start + i + 1);
+    // A non-ASCII host cannot be an IP literal, so the last `:` (if any) delimits the port.
+    let host_end = uri[host_start..authority_end]
+        .rfind(':')
+        .map_or(authority_end, |i| host_start + i);
+
+    let host = &uri[host_start..host_end];
+    if host.is_ascii() {
+        return uri;
+    }
+
+    match idna02::domain_to_ascii(host) {
+        Ok(host) => {
+            let mut ret = String::with_capacity(uri.len() - (host_end - host_start) + host.len());
+            ret.push_str(&uri[..host_start]);
+            ret.push_str(&host);
+            ret.push_str(&uri[host_end..]);
+            ret
+        }
+        Err(_) => uri,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    #[test]
+    fn host_to_ascii() {
+        for &(uri, expected) in &[
+            ("http://例え.jp/", "http://xn--r8jz45g.jp/"),
+            ("http://xn--r8jz45g.jp/", "http://xn--r8jz45g.jp/"),
+            (
+                "https://user@例え.jp:8080/例?q=例#例",
+                "https://user@xn--r8jz45g.jp:8080/例?q=例#例",
+            ),
+            ("http://例え.jp", "http://xn--r8jz45g.jp"),
+            ("http://[::1]:8080/", "http://[::1]:8080/"),
+            ("/例え.jp", "/例え.jp"),
+        ] {
+            assert_eq!(super::host_to_ascii(uri.to_string()), expected);
+        }
+    }
+}
@@ -2,68 +2,145 @@ use core::fmt::{self, Display, Formatter, Write};
 use core::mem;
 use core::str;
 
-use percent_encoding::AsciiSet;
+use percent_encoding::{percent_encode_byte, AsciiSet};
 
 pub struct DoublePercentEncode<D>(pub D);
 
 pub struct PercentEncode<D>(pub D);
 
+/// Same as `DoublePercentEncode` but encodes the bytes in the given `EncodeSet`.
+pub struct DoublePercentEncodeWith<D>(pub D, pub EncodeSet);
+
+/// Same as `PercentEncode` but encodes the bytes in the given `EncodeSet`.
+pub struct PercentEncodeWith<D>(pub D, pub EncodeSet);
+
+/// The error returned by [`percent_encode_into`] when the destination buffer is too small to
+/// hold the encoded string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferTooSmall {
+    _priv: (),
+}
+
+/// A set of ASCII characters to be percent encoded.
+///
+/// This is similar to `percent_encoding::AsciiSet` of the [`percent-encoding`] crate. Non-ASCII
+/// bytes and the `%` character are always percent encoded regardless of the set.
+///
+/// [`percent-encoding`]: https://docs.rs/percent-encoding/2
+///
+/// ## Example
+///
+/// ```
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::serializer::EncodeSet;
+///
+/// // Leave `*` unencoded in addition to the unreserved characters.
+/// const ENCODE_SET: EncodeSet = EncodeSet::RFC5849.remove(b'*');
+///
+/// assert!(ENCODE_SET.contains(b'!'));
+/// assert!(!ENCODE_SET.contains(b'*'));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EncodeSet {
+    map: BitArray,
+}
+
 type BitBlock = u32;
 
 type BitArray = [BitBlock; 0x80 / BITS_PER_BLOCK];
 
 const BITS_PER_BLOCK: usize = 8 * mem::size_of::<BitBlock>();
 
-/// A bit array of ASCII character bytes that should be percent encoded.
-///
-/// Every character that is not an "unreserved character" in RFC 3986 should be encoded.
-/// <https://tools.ietf.org/html/rfc3986#section-2.3>
-const ENCODE_MAP: BitArray = [0xFFFF_FFFF, 0xFC00_9FFF, 0x7800_0001, 0xB800_0001];
-
 const RESERVED: &AsciiSet = &percent_encoding::NON_ALPHANUMERIC
     .remove(b'-')
     .remove(b'.')
     .remove(b'_')
     .remove(b'~');
 
-impl<D: Display> Display for DoublePercentEncode<D> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        struct Adapter<'a, 'b>(&'a mut Formatter<'b>);
+impl EncodeSet {
+    /// The set of characters that the OAuth standard ([RFC 5849 section 3.6.][rfc]) requires to
+    /// be encoded, i.e. every character that is not an "unreserved character" of RFC 3986.
+    ///
+    /// This is the default set.
+    ///
+    /// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.6
+    pub const RFC5849: Self = EncodeSet {
+        map: [0xFFFF_FFFF, 0xFC00_9FFF, 0x7800_0001, 0xB800_0001],
+    };
 
-        impl<'a, 'b: 'a> Write for Adapter<'a, 'b> {
-            fn write_str(&mut self, s: &str) -> fmt::Result {
-                let mut bytes = s.as_bytes();
-                while let Some((&b, rem)) = bytes.split_first() {
-                    if should_percent_encode(b) {
-                        self.0.write_str(double_encode_byte(b))?;
-                        bytes = rem;
-                        continue;
-                    }
-
-                    // Write as much characters as possible at once:
-                    if let Some((i, &b)) = bytes
-                        .iter()
-                        .enumerate()
-                        .skip(1)
-                        .find(|&(_, &b)| should_percent_encode(b))
-                    {
-                        let rem = &bytes[i + 1..];
-                        let s = &bytes[..i];
-                        debug_assert!(s.is_ascii());
-                        self.0.write_str(unsafe { str::from_utf8_unchecked(s) })?;
-                        self.0.write_str(double_encode_byte(b))?;
-                        bytes = rem;
-                    } else {
-                        debug_assert!(bytes.is_ascii());
-                        return self.0.write_str(unsafe { str::from_utf8_unchecked(bytes) });
-                    }
-                }
-
-                Ok(())
-            }
+    /// Same as `RFC5849` except that this leaves `!`, `'`, `(`, `)` and `*` unencoded, like
+    /// JavaScript's `encodeURIComponent` function does.
+    ///
+    /// Some nonconforming servers encode the signature base string with this set.
+    pub const ENCODE_URI_COMPONENT: Self = EncodeSet::RFC5849
+        .remove(b'!')
+        .remove(b'\'')
+        .remove(b'(')
+        .remove(b')')
+        .remove(b'*');
+
+    /// Same as `RFC5849` except that this also encodes `~`, as some legacy servers implementing
+    /// the OAuth Core 1.0 specification (prior to the 1.0a revision) expect.
+    pub const LEGACY: Self = EncodeSet::RFC5849.add(b'~');
+
+    /// The set that only encodes the characters that are always encoded, i.e. `%` and non-ASCII
+    /// bytes. Encoding a string that is already encoded with this set encodes it a second time.
+    #[cfg(feature = "alloc")]
+    pub(crate) const NONE: Self = EncodeSet {
+        map: [0; 0x80 / BITS_PER_BLOCK],
+    };
+
+    /// Returns a copy of the set with the given ASCII character added.
+    ///
+    /// Non-ASCII bytes are ignored since they are always encoded.
+    pub const fn add(self, byte: u8) -> Self {
+        if !byte.is_ascii() {
+            return self;
         }
+        let mut map = self.map;
+        let i = byte as usize;
+        map[i / BITS_PER_BLOCK] |= 1 << (i % BITS_PER_BLOCK);
+        EncodeSet { map }
+    }
 
-        write!(Adapter(f), "{}", self.0)
+    /// Returns a copy of the set with the given ASCII character removed.
+    ///
+    /// Non-ASCII bytes and `%` are ignored since they are always encoded.
+    pub const fn remove(self, byte: u8) -> Self {
+        if !byte.is_ascii() {
+            return self;
+        }
+        let mut map = self.map;
+        let i = byte as usize;
+        map[i / BITS_PER_BLOCK] &= !(1 << (i % BITS_PER_BLOCK));
+        EncodeSet { map }
+    }
+
+    /// Returns `true` if the given byte should be percent encoded.
+    pub const fn contains(&self, byte: u8) -> bool {
+        !byte.is_ascii() || byte == b'%' || get_bit(&self.map, byte as usize)
+    }
+}
+
+impl Display for BufferTooSmall {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("buffer too small to hold the percent-encoded string")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BufferTooSmall {}
+
+impl Default for EncodeSet {
+    fn default() -> Self {
+        EncodeSet::RFC5849
+    }
+}
+
+impl<D: Display> Display for DoublePercentEncode<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        DoublePercentEncodeWith(&self.0, EncodeSet::RFC5849).fmt(f)
     }
 }
 
@@ -79,10 +156,121 @@ impl<D: Display> Display for PercentEncode<D> {
     }
 }
 
+impl<D: Display> Display for DoublePercentEncodeWith<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(Adapter::new(f, self.1, double_encode_byte), "{}", self.0)
+    }
+}
+
+impl<D: Display> Display for PercentEncodeWith<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(Adapter::new(f, self.1, percent_encode_byte), "{}", self.0)
+    }
+}
+
+/// A `Write` adapter that percent encodes the bytes in `set` with `encode_byte`.
+struct Adapter<'a, 'b> {
+    f: &'a mut Formatter<'b>,
+    set: EncodeSet,
+    encode_byte: fn(u8) -> &'static str,
+}
+
+impl<'a, 'b> Adapter<'a, 'b> {
+    fn new(f: &'a mut Formatter<'b>, set: EncodeSet, encode_byte: fn(u8) -> &'static str) -> Self {
+        Adapter {
+            f,
+            set,
+            encode_byte,
+        }
+    }
+}
+
+impl<'a, 'b: 'a> Write for Adapter<'a, 'b> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut bytes = s.as_bytes();
+        while let Some((&b, rem)) = bytes.split_first() {
+            if self.set.contains(b) {
+                self.f.write_str((self.encode_byte)(b))?;
+                bytes = rem;
+                continue;
+            }
+
+            // Write as much characters as possible at once:
+            if let Some((i, &b)) = bytes
+                .iter()
+                .enumerate()
+                .skip(1)
+                .find(|&(_, &b)| self.set.contains(b))
+            {
+                let rem = &bytes[i + 1..];
+                let s = &bytes[..i];
+                debug_assert!(s.is_ascii());
+                self.f.write_str(unsafe { str::from_utf8_unchecked(s) })?;
+                self.f.write_str((self.encode_byte)(b))?;
+                bytes = rem;
+            } else {
+                debug_assert!(bytes.is_ascii());
+                return self.f.write_str(unsafe { str::from_utf8_unchecked(bytes) });
+            }
+        }
+
+        Ok(())
+    }
+}
+
 pub fn percent_encode(input: &str) -> percent_encoding::PercentEncode<'_> {
     percent_encoding::utf8_percent_encode(input, RESERVED)
 }
 
+/// Percent encodes `src` as the OAuth standard requires ([`EncodeSet::RFC5849`]) into `dst`,
+/// returning the number of bytes written.
+///
+/// This does not need a `fmt::Write` value nor an allocator, which is useful in the most
+/// constrained environments.
+///
+/// Returns an error if `dst` is too small to hold the encoded string, in which case the contents
+/// of `dst` are unspecified.
+///
+/// ## Example
+///
+/// ```
+/// # extern crate oauth1_request as oauth;
+/// #
+/// use oauth::serializer::percent_encode_into;
+///
+/// let mut buf = [0; 16];
+/// let n = percent_encode_into("a b&c", &mut buf).unwrap();
+/// assert_eq!(&buf[..n], b"a%20b%26c");
+///
+/// assert!(percent_encode_into("a b&c", &mut buf[..8]).is_err());
+/// ```
+pub fn percent_encode_into(src: &str, dst: &mut [u8]) -> Result<usize, BufferTooSmall> {
+    let mut len = 0;
+    for &b in src.as_bytes() {
+        let encoded = if should_percent_encode(b) {
+            percent_encode_byte(b).as_bytes()
+        } else {
+            core::slice::from_ref(&b)
+        };
+        let end = len + encoded.len();
+        dst.get_mut(len..end)
+            .ok_or(BufferTooSmall { _priv: () })?
+            .copy_from_slice(encoded);
+        len = end;
+    }
+    Ok(len)
+}
+
+/// Asserts in debug builds that `key` consists only of "unreserved characters" of RFC 3986,
+/// since parameter keys are not percent encoded by the serializers.
+pub fn debug_assert_key(key: &str) {
+    debug_assert!(
+        !key.bytes().any(should_percent_encode),
+        "parameter key contains a non-ASCII or reserved character\n key: `{:?}`",
+        key,
+    );
+}
+
 fn double_encode_byte(b: u8) -> &'static str {
     const ENCODE: &[u8; 0x100 * 5] = b"\
         %2500%2501%2502%2503%2504%2505%2506%2507%2508%2509%250A%250B%250C%250D%250E%250F\
@@ -107,7 +295,7 @@ fn double_encode_byte(b: u8) -> &'static str {
 }
 
 fn should_percent_encode(b: u8) -> bool {
-    !b.is_ascii() || get_bit(&ENCODE_MAP, usize::from(b))
+    EncodeSet::RFC5849.contains(b)
 }
 
 const fn get_bit(bits: &BitArray, i: usize) -> bool {
@@ -136,6 +324,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn encode_set() {
+        let set = EncodeSet::ENCODE_URI_COMPONENT;
+        assert_eq!(
+            PercentEncodeWith("(*'!') 100%", set).to_string(),
+            "(*'!')%20100%25",
+        );
+        assert_eq!(
+            DoublePercentEncodeWith("(*'!') 100%", set).to_string(),
+            "(*'!')%2520100%2525",
+        );
+        assert!(set.add(b'*').contains(b'*'));
+        assert!(set.remove(b'%').contains(b'%'));
+        assert!(set.remove(0xE3).contains(0xE3));
+
+        assert_eq!(
+            PercentEncodeWith("a~b", EncodeSet::LEGACY).to_string(),
+            "a%7Eb"
+        );
+    }
+
+    #[test]
+    fn percent_encode_into() {
+        let input = "Hello Ladies + Gentlemen, a signed OAuth request!";
+        let expected = percent_encode(input).to_string();
+
+        let mut buf = [0; 0x100];
+        let n = super::percent_encode_into(input, &mut buf).unwrap();
+        assert_eq!(&buf[..n], expected.as_bytes());
+
+        let n = super::percent_encode_into(input, &mut buf[..expected.len()]).unwrap();
+        assert_eq!(&buf[..n], expected.as_bytes());
+
+        for len in 0..expected.len() {
+            assert!(super::percent_encode_into(input, &mut buf[..len]).is_err());
+        }
+        assert_eq!(super::percent_encode_into("", &mut []), Ok(0));
+    }
+
     #[test]
     fn encode_map() {
         for b in 0..=0xFF {
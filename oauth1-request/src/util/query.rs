@@ -0,0 +1,159 @@
+//! Folding of the query part of a request URI into the signature base string
+//! ([RFC 5849 section 3.4.1.3.1.][rfc]).
+//!
+//! [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1.3.1
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Display;
+
+use super::{EncodeSet, PercentEncodeWith};
+
+/// Query parameters taken from a request URI that are yet to be fed to a `Sign`.
+///
+/// The keys and values are held percent encoded with the `EncodeSet` given to `parse`, and sorted
+/// by the encoded key and then by the encoded value as [RFC 5849 section 3.4.1.3.2.][rfc] requires.
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1.3.2
+#[derive(Clone, Debug, Default)]
+pub struct QueryParameters {
+    // Sorted in descending order so that the next (smallest) pair can be `pop`-ed.
+    pending: Vec<(String, String)>,
+}
+
+impl QueryParameters {
+    /// Parses the query part (without the leading `'?'`) of a URI.
+    pub fn parse(query: &str, encode_set: EncodeSet) -> Self {
+        let encode = |s: &str| PercentEncodeWith(decode(s), encode_set).to_string();
+        let mut pending: Vec<(String, String)> = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let mut kv = pair.splitn(2, '=');
+                let k = kv.next().unwrap();
+                let v = kv.next().unwrap_or("");
+                (encode(k), encode(v))
+            })
+            .collect();
+        pending.sort_unstable_by(|lhs, rhs| rhs.cmp(lhs));
+        QueryParameters { pending }
+    }
+
+    /// Returns `true` if the query contains a protocol parameter (a key starting with `oauth_`).
+    pub fn contains_oauth_parameters(&self) -> bool {
+        self.pending.iter().any(|(k, _)| is_oauth_parameter(k))
+    }
+
+    /// Removes the protocol parameters (keys starting with `oauth_`) from the query.
+    pub fn strip_oauth_parameters(&mut self) {
+        self.pending.retain(|(k, _)| !is_oauth_parameter(k));
+    }
+
+    /// Removes and returns the next pair if it precedes the given key in dictionary order.
+    ///
+    /// `key` must consist only of unreserved characters, so that it is the same when encoded.
+    pub fn next_before_key(&mut self, key: &str) -> Option<(String, String)> {
+        match self.pending.last() {
+            Some((k, _)) if **k < *key => self.pending.pop(),
+            _ => None,
+        }
+    }
+
+    /// Removes and returns the next pair if it precedes the given pair in dictionary order.
+    ///
+    /// `value` is compared in its form encoded with `encode_set`, which should be the one passed to
+    /// `parse`.
+    pub fn next_before<V: Display>(
+        &mut self,
+        key: &str,
+        value: &V,
+        encode_set: EncodeSet,
+    ) -> Option<(String, String)> {
+        let value = PercentEncodeWith(value, encode_set);
+        match self.pending.last() {
+            Some((k, v)) if (&**k, fmt_cmp::cmp(v, &value)) < (key, Ordering::Equal) => {
+                self.pending.pop()
+            }
+            _ => None,
+        }
+    }
+
+    /// Removes and returns the next pair.
+    pub fn next(&mut self) -> Option<(String, String)> {
+        self.pending.pop()
+    }
+}
+
+fn is_oauth_parameter(key: &str) -> bool {
+    key.starts_with("oauth_")
+}
+
+/// Decodes an `application/x-www-form-urlencoded` key or value.
+fn decode(s: &str) -> String {
+    let bytes: Vec<u8> = s
+        .split('+')
+        .enumerate()
+        .flat_map(|(i, chunk)| {
+            let space = if i == 0 { None } else { Some(b' ') };
+            space
+                .into_iter()
+                .chain(percent_encoding::percent_decode_str(chunk))
+        })
+        .collect();
+    match String::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let mut query = QueryParameters::parse("b=2&a=%E3%81%82&&c&a=1+2", EncodeSet::RFC5849);
+        assert_eq!(query.next_before_key("a"), None);
+        assert_eq!(query.next_before("a", &"あ", EncodeSet::RFC5849), None);
+        assert_eq!(
+            query.next_before("a", &"い", EncodeSet::RFC5849),
+            Some(("a".into(), "%E3%81%82".into()))
+        );
+        assert_eq!(query.next(), Some(("a".into(), "1%202".into())));
+        assert_eq!(query.next_before_key("c"), Some(("b".into(), "2".into())));
+        assert_eq!(query.next_before_key("c"), None);
+        assert_eq!(query.next(), Some(("c".into(), "".into())));
+        assert_eq!(query.next(), None);
+    }
+
+    #[test]
+    fn strip_oauth_parameters() {
+        let mut query = QueryParameters::parse(
+            "oauth_token=foo&a=1&oauth=2&oauth_nonce=bar",
+            EncodeSet::RFC5849,
+        );
+        assert!(query.contains_oauth_parameters());
+        query.strip_oauth_parameters();
+        assert!(!query.contains_oauth_parameters());
+        assert_eq!(query.next(), Some(("a".into(), "1".into())));
+        assert_eq!(query.next(), Some(("oauth".into(), "2".into())));
+        assert_eq!(query.next(), None);
+    }
+
+    #[test]
+    fn sort_encoded() {
+        // `ids[]` (`ids%5B%5D`) precedes `ids_` when encoded but not when decoded,
+        // and so does `~` (`%7E`) with `LEGACY` precede `a`.
+        let mut query = QueryParameters::parse("ids_=1&ids%5B%5D=2&x=a&x=%7E", EncodeSet::LEGACY);
+        assert_eq!(query.next(), Some(("ids%5B%5D".into(), "2".into())));
+        assert_eq!(query.next(), Some(("ids_".into(), "1".into())));
+        assert_eq!(query.next_before("x", &"!", EncodeSet::LEGACY), None);
+        assert_eq!(
+            query.next_before("x", &"A", EncodeSet::LEGACY),
+            Some(("x".into(), "%7E".into()))
+        );
+        assert_eq!(query.next(), Some(("x".into(), "a".into())));
+        assert_eq!(query.next(), None);
+    }
+}
@@ -0,0 +1,117 @@
+use core::fmt::{self, Display, Formatter, Write};
+use core::str;
+
+/// Displays a value with its leading and trailing ASCII whitespace removed.
+///
+/// This formats the inner value only once, holding back the whitespace written after the last
+/// non-whitespace character until it is known not to be trailing. Up to `MAX_PENDING` bytes of
+/// such whitespace are held back, and a longer run of whitespace is written out as is, so a value
+/// that ends with a run longer than that, which `Display` implementations rarely write in
+/// separate pieces, may be left with some trailing whitespace.
+pub struct TrimAsciiWhitespace<D>(pub D);
+
+const MAX_PENDING: usize = 64;
+
+/// A `Write` adapter that writes the input into `f` without leading and trailing ASCII whitespace.
+struct Trim<'a, 'b> {
+    f: &'a mut Formatter<'b>,
+    /// Whether a non-whitespace character has been written.
+    started: bool,
+    /// The whitespace held back since the last non-whitespace character.
+    pending: [u8; MAX_PENDING],
+    pending_len: usize,
+}
+
+impl<D: Display> Display for TrimAsciiWhitespace<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            Trim {
+                f,
+                started: false,
+                pending: [0; MAX_PENDING],
+                pending_len: 0,
+            },
+            "{}",
+            self.0
+        )
+    }
+}
+
+impl<'a, 'b> Trim<'a, 'b> {
+    fn flush(&mut self) -> fmt::Result {
+        let len = self.pending_len;
+        self.pending_len = 0;
+        // The buffer only contains ASCII whitespace characters.
+        self.f
+            .write_str(str::from_utf8(&self.pending[..len]).unwrap())
+    }
+
+    fn hold_back(&mut self, s: &str) -> fmt::Result {
+        if self.pending_len + s.len() > MAX_PENDING {
+            self.flush()?;
+            if s.len() > MAX_PENDING {
+                return self.f.write_str(s);
+            }
+        }
+        self.pending[self.pending_len..self.pending_len + s.len()].copy_from_slice(s.as_bytes());
+        self.pending_len += s.len();
+        Ok(())
+    }
+}
+
+impl<'a, 'b> Write for Trim<'a, 'b> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let s = if self.started {
+            s
+        } else {
+            s.trim_start_matches(|c: char| c.is_ascii_whitespace())
+        };
+        // ASCII whitespace characters are single bytes, so `end` is at a char boundary.
+        let end = match s.bytes().rposition(|b| !b.is_ascii_whitespace()) {
+            Some(i) => i + 1,
+            None => {
+                return if self.started {
+                    self.hold_back(s)
+                } else {
+                    Ok(())
+                }
+            }
+        };
+        self.started = true;
+        self.flush()?;
+        self.f.write_str(&s[..end])?;
+        self.hold_back(&s[end..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn trim_ascii_whitespace() {
+        struct Chunks<'a>(&'a [&'a str]);
+        impl<'a> Display for Chunks<'a> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                self.0.iter().try_for_each(|s| f.write_str(s))
+            }
+        }
+
+        for &(chunks, expected) in &[
+            (&[" \t foo bar \r\n"][..], "foo bar"),
+            (&["  ", " f", "o", "o ", " ", "bar", "  "][..], "foo  bar"),
+            (&[" ", "\u{3000}例\u{3000}", " "][..], "\u{3000}例\u{3000}"),
+            (&["foo"][..], "foo"),
+            (&["  ", "\n"][..], ""),
+            (&[][..], ""),
+            (&["a", " ", "\t", "b", "\n"][..], "a \tb"),
+        ] {
+            let trimmed = TrimAsciiWhitespace(Chunks(chunks)).to_string();
+            assert_eq!(trimmed, expected, "{:?}", chunks);
+        }
+    }
+}
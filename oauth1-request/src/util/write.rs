@@ -0,0 +1,79 @@
+use core::fmt::{self, Write};
+
+/// A `fmt::Write` adapter that stops writing into the underlying writer at the first error and
+/// remembers the error instead of propagating it.
+///
+/// The serializers `unwrap` the results of writes, so this is used to surface the errors of
+/// a fallible writer to the caller.
+pub struct CatchError<'a, W: ?Sized> {
+    inner: &'a mut W,
+    result: fmt::Result,
+}
+
+/// A `fmt::Write` implementation that discards the input and only counts its length in bytes.
+#[cfg(feature = "alloc")]
+pub struct CountWrite(pub usize);
+
+/// A `fmt::Write` adapter for an `io::Write` value, which remembers the first I/O error.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct IoWrite<W> {
+    inner: W,
+    error: Option<std::io::Error>,
+}
+
+impl<'a, W: Write + ?Sized> CatchError<'a, W> {
+    pub fn new(inner: &'a mut W) -> Self {
+        CatchError {
+            inner,
+            result: Ok(()),
+        }
+    }
+
+    pub fn into_result(self) -> fmt::Result {
+        self.result
+    }
+}
+
+impl<'a, W: Write + ?Sized> Write for CatchError<'a, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.result.is_ok() {
+            self.result = self.inner.write_str(s);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Write for CountWrite {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> IoWrite<W> {
+    pub fn new(inner: W) -> Self {
+        IoWrite { inner, error: None }
+    }
+
+    pub fn into_result(self) -> std::io::Result<W> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(self.inner),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Write for IoWrite<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.error.is_none() {
+            if let Err(e) = self.inner.write_all(s.as_bytes()) {
+                self.error = Some(e);
+            }
+        }
+        Ok(())
+    }
+}
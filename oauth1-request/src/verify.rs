@@ -0,0 +1,340 @@
+//! Verification of the signatures of requests received by a server.
+
+use alloc::string::String;
+
+use oauth_credentials::Credentials;
+
+#[cfg(feature = "hmac-sha1")]
+use crate::signature_method::HMAC_SHA1;
+use crate::signature_method::{self, SignatureMethod};
+use crate::util;
+
+doc_auto_cfg! {
+    /// Verifies an `HMAC-SHA1` signature of a request received by a server.
+    ///
+    /// `uri` is the base string URI ([RFC 5849 section 3.4.1.2.][rfc-uri]), i.e. the request URI
+    /// without the query part. `params` are the request parameters as received, *not*
+    /// percent-encoded, including the query parameters, the `x-www-form-urlencoded` body
+    /// parameters and the protocol parameters (except `realm`) from the `Authorization` header.
+    /// The `oauth_signature` parameter is ignored if present. `signature` is the Base64-encoded
+    /// value of `oauth_signature` after percent-decoding, with or without the trailing `=` padding
+    /// (see [`HmacSha1::unpadded_base64`](signature_method::HmacSha1::unpadded_base64)).
+    ///
+    /// The signatures are compared in constant time.
+    ///
+    /// [rfc-uri]: https://tools.ietf.org/html/rfc5849#section-3.4.1.2
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// // The example of RFC 5849 section 1.2.
+    /// let params = [
+    ///     ("file", "vacation.jpg"),
+    ///     ("size", "original"),
+    ///     ("oauth_consumer_key", "dpf43f3p2l4k3l03"),
+    ///     ("oauth_token", "nnch734d00sl2jdk"),
+    ///     ("oauth_signature_method", "HMAC-SHA1"),
+    ///     ("oauth_timestamp", "137131202"),
+    ///     ("oauth_nonce", "chapoH"),
+    /// ];
+    /// let verify = |signature| {
+    ///     oauth::verify_hmac_sha1(
+    ///         "GET",
+    ///         "http://photos.example.net/photos",
+    ///         &params,
+    ///         "kd94hf93k423kf44",
+    ///         Some("pfkkdhi9sl3r4s00"),
+    ///         signature,
+    ///     )
+    /// };
+    ///
+    /// assert!(verify("MdpQcU8iPSUjWoN/UDMsK2sui9I="));
+    /// assert!(verify("MdpQcU8iPSUjWoN/UDMsK2sui9I"));
+    /// assert!(!verify("MdpQcU8iPSUjWoN/UDMsK2sui9J="));
+    /// assert!(!verify("not a signature"));
+    /// ```
+    #[cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+    pub fn verify_hmac_sha1(
+        method: &str,
+        uri: &str,
+        params: &[(&str, &str)],
+        client_secret: &str,
+        token_secret: Option<&str>,
+        signature: &str,
+    ) -> bool {
+        verify_hmac_sha1_with_any(method, uri, params, &[client_secret], token_secret, signature)
+            .is_some()
+    }
+
+    /// Verifies an `HMAC-SHA1` signature against each of the candidate client secrets, returning
+    /// the index of the one that matched.
+    ///
+    /// This is useful for rotating a client secret without downtime: the server accepts both
+    /// the old and the new secret until all the clients have switched to the new one.
+    /// The arguments other than `client_secrets` are the same as those of [`verify_hmac_sha1`].
+    ///
+    /// Each signature comparison is done in constant time, and all the candidates are tried even
+    /// after a match so that the time taken does not reveal which one matched. The time taken
+    /// still grows linearly with the number of candidates, which is not secret in itself, so
+    /// keep the list short.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// let params = [
+    ///     ("file", "vacation.jpg"),
+    ///     ("size", "original"),
+    ///     ("oauth_consumer_key", "dpf43f3p2l4k3l03"),
+    ///     ("oauth_token", "nnch734d00sl2jdk"),
+    ///     ("oauth_signature_method", "HMAC-SHA1"),
+    ///     ("oauth_timestamp", "137131202"),
+    ///     ("oauth_nonce", "chapoH"),
+    /// ];
+    /// let verify = |client_secrets: &[&str]| {
+    ///     oauth::verify_hmac_sha1_with_any(
+    ///         "GET",
+    ///         "http://photos.example.net/photos",
+    ///         &params,
+    ///         client_secrets,
+    ///         Some("pfkkdhi9sl3r4s00"),
+    ///         "MdpQcU8iPSUjWoN/UDMsK2sui9I=",
+    ///     )
+    /// };
+    ///
+    /// assert_eq!(verify(&["new_secret", "kd94hf93k423kf44"]), Some(1));
+    /// assert_eq!(verify(&["new_secret"]), None);
+    /// assert_eq!(verify(&[]), None);
+    /// ```
+    #[cfg(all(feature = "alloc", feature = "hmac-sha1"))]
+    pub fn verify_hmac_sha1_with_any(
+        method: &str,
+        uri: &str,
+        params: &[(&str, &str)],
+        client_secrets: &[&str],
+        token_secret: Option<&str>,
+        signature: &str,
+    ) -> Option<usize> {
+        // The length of a Base64-encoded SHA-1 digest without the padding.
+        const SIGNATURE_LEN: usize = 27;
+
+        let signature = signature.strip_suffix('=').unwrap_or(signature);
+        let mut tag = [0_u8; SIGNATURE_LEN];
+        if signature.len() != SIGNATURE_LEN {
+            return None;
+        }
+        let tag = match base64::decode_config_slice(signature, base64::STANDARD_NO_PAD, &mut tag) {
+            Ok(n) => &tag[..n],
+            Err(_) => return None,
+        };
+
+        let params = encode_and_sort(params.iter().filter(|&&(k, _)| k != "oauth_signature"));
+
+        let mut ret = None;
+        for (i, client_secret) in client_secrets.iter().enumerate() {
+            let mut sign = HMAC_SHA1.sign_with(client_secret, token_secret);
+            feed_base_string(&mut sign, method, uri, &params);
+
+            // Keep trying the rest of the candidates after a match.
+            if sign.verify(tag) && ret.is_none() {
+                ret = Some(i);
+            }
+        }
+
+        ret
+    }
+}
+
+doc_auto_cfg! {
+    /// Verifies a signature of a request received by a server, using any signature method.
+    ///
+    /// `method`, `uri` and `params` are the same as those of `verify_hmac_sha1`, except that
+    /// the `oauth_consumer_key` and `oauth_token` parameters are taken from the identifiers of
+    /// `client` and `token` and the entries of those names in `params` are ignored.
+    /// `signature` is the value of `oauth_signature` after percent-decoding, which is compared
+    /// in constant time with the one `signature_method` produces.
+    ///
+    /// Unlike `verify_hmac_sha1`, which accepts an `HMAC-SHA1` signature with or without
+    /// the trailing Base64 padding, this compares `signature` as is, since the padding is
+    /// specific to the signature method. A signature that a client has sent without the padding
+    /// does not verify.
+    ///
+    /// ## Example
+    ///
+    #[cfg_attr(all(feature = "alloc", feature = "hmac-sha1"), doc = " ```")]
+    #[cfg_attr(not(all(feature = "alloc", feature = "hmac-sha1")), doc = " ```ignore")]
+    /// # extern crate oauth1_request as oauth;
+    /// #
+    /// use oauth::Credentials;
+    ///
+    /// // The example of RFC 5849 section 1.2.
+    /// let params = [
+    ///     ("file", "vacation.jpg"),
+    ///     ("size", "original"),
+    ///     ("oauth_signature_method", "HMAC-SHA1"),
+    ///     ("oauth_timestamp", "137131202"),
+    ///     ("oauth_nonce", "chapoH"),
+    /// ];
+    /// let verify = |signature| {
+    ///     oauth::verify(
+    ///         "GET",
+    ///         "http://photos.example.net/photos",
+    ///         &params,
+    ///         Credentials::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44"),
+    ///         Some(Credentials::new("nnch734d00sl2jdk", "pfkkdhi9sl3r4s00")),
+    ///         signature,
+    ///         oauth::HMAC_SHA1,
+    ///     )
+    /// };
+    ///
+    /// assert!(verify("MdpQcU8iPSUjWoN/UDMsK2sui9I="));
+    /// assert!(!verify("MdpQcU8iPSUjWoN/UDMsK2sui9J="));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn verify<C, T, SM>(
+        method: &str,
+        uri: &str,
+        params: &[(&str, &str)],
+        client: Credentials<C>,
+        token: Option<Credentials<T>>,
+        signature: &str,
+        signature_method: SM,
+    ) -> bool
+    where
+        C: AsRef<str>,
+        T: AsRef<str>,
+        SM: SignatureMethod,
+    {
+        use alloc::string::ToString;
+
+        use self::signature_method::Sign;
+        use self::util::percent_encode;
+
+        let client_id = client.identifier.as_ref();
+        let token_id = token.as_ref().map(|t| t.identifier.as_ref());
+        let params = encode_and_sort(
+            params
+                .iter()
+                .filter(|&&(k, _)| {
+                    !matches!(k, "oauth_signature" | "oauth_consumer_key" | "oauth_token")
+                })
+                .chain(Some(("oauth_consumer_key", client_id)).iter())
+                .chain(token_id.map(|t| ("oauth_token", t)).iter()),
+        );
+
+        let token_secret = token.as_ref().map(|t| t.secret.as_ref());
+        let mut sign = signature_method.sign_with(client.secret.as_ref(), token_secret);
+        feed_base_string(&mut sign, method, uri, &params);
+
+        // The `Display` representation of the signature is percent-encoded.
+        let expected = sign.end().to_string();
+        let signature = percent_encode(signature).to_string();
+        constant_time_eq(expected.as_bytes(), signature.as_bytes())
+    }
+}
+
+/// Percent-encodes the keys and values of `params` and sorts them, as the parameters of
+/// the signature base string ([RFC 5849 section 3.4.1.3.2.][rfc]).
+///
+/// The keys and values are encoded once more as they appear in the signature base string, so that
+/// they can be fed to a `Sign` as is. This does not change their order since every `%` is encoded
+/// into the same `%25`.
+///
+/// [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4.1.3.2
+#[cfg(feature = "alloc")]
+fn encode_and_sort<'p, I>(params: I) -> alloc::vec::Vec<(String, String)>
+where
+    I: Iterator<Item = &'p (&'p str, &'p str)>,
+{
+    use alloc::string::ToString;
+
+    use self::util::DoublePercentEncode;
+
+    let mut ret: alloc::vec::Vec<_> = params
+        .map(|&(k, v)| {
+            let k = DoublePercentEncode(k).to_string();
+            let v = DoublePercentEncode(v).to_string();
+            (k, v)
+        })
+        .collect();
+    ret.sort_unstable();
+    ret
+}
+
+/// Feeds the signature base string of a request with the parameters encoded and sorted by
+/// `encode_and_sort` to `sign`.
+#[cfg(feature = "alloc")]
+fn feed_base_string<S: signature_method::Sign>(
+    sign: &mut S,
+    method: &str,
+    uri: &str,
+    params: &[(String, String)],
+) {
+    use self::util::{LowercaseSchemeAndHost, PercentEncode};
+
+    sign.request_method(method);
+    sign.uri(PercentEncode(LowercaseSchemeAndHost(uri)));
+    let mut params = params.iter();
+    if let Some((k, v)) = params.next() {
+        sign.parameter(k, v);
+        for (k, v) in params {
+            sign.delimiter();
+            sign.parameter(k, v);
+        }
+    }
+}
+
+/// Compares two byte strings in time independent of their contents (but not of their lengths).
+#[cfg(feature = "alloc")]
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0_u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(all(test, feature = "hmac-sha1"))]
+mod tests {
+    use core::num::NonZeroU64;
+
+    use crate::{request, Builder};
+
+    use super::*;
+
+    #[test]
+    fn verify_hmac_sha1_encoded_keys() {
+        use crate::parse::parse_authorization_header;
+
+        let client = Credentials::new("ck", "cs");
+        let mut builder = Builder::<_, &str>::new(client, HMAC_SHA1);
+        builder.nonce("nonce").timestamp(NonZeroU64::new(1));
+
+        let uri = "https://example.com/";
+        let request = request! { "a" => "e f" };
+        let header = builder.post("https://example.com/?a%20b=%25&a%21=c%2Fd", &request);
+        let parsed = parse_authorization_header(&header).unwrap();
+
+        let params = [
+            ("a b", "%"),
+            ("a!", "c/d"),
+            ("a", "e f"),
+            ("oauth_consumer_key", "ck"),
+            ("oauth_nonce", "nonce"),
+            ("oauth_signature_method", "HMAC-SHA1"),
+            ("oauth_timestamp", "1"),
+        ];
+        let signature = &*parsed.signature;
+        assert!(verify_hmac_sha1(
+            "POST", uri, &params, "cs", None, signature
+        ));
+        assert_eq!(
+            verify_hmac_sha1_with_any("POST", uri, &params, &["xx", "cs"], None, signature),
+            Some(1),
+        );
+    }
+}
@@ -0,0 +1,43 @@
+//! Test that the `Debug` representation of `Builder` doesn't leak credentials.
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+use oauth1_request::{Builder, Credentials, HMAC_SHA1};
+
+#[test]
+fn builder_debug() {
+    let client = Credentials::new("client_identifier", "client_secret");
+    let token = Credentials::new("token_identifier", "token_secret");
+    let mut builder = Builder::<_, _>::new(client, HMAC_SHA1);
+    builder
+        .token(token)
+        .callback("https://example.com/callback")
+        .verifier("verifier_value")
+        .nonce("nonce_value")
+        .version(true);
+
+    let debug = format!("{:?}", builder);
+    for secret in &[
+        "client_secret",
+        "token_secret",
+        "token_identifier",
+        "verifier_value",
+    ] {
+        assert!(!debug.contains(secret), "{:?} leaked: {}", secret, debug);
+    }
+    for info in &[
+        "HMAC-SHA1",
+        "client_identifier",
+        "\"toke...\"",
+        "callback: true",
+        "verifier: true",
+        "version: true",
+    ] {
+        assert!(debug.contains(info), "{:?} missing: {}", info, debug);
+    }
+
+    let debug = format!("{:?}", Builder::<_, &str>::new(client, HMAC_SHA1));
+    assert!(debug.contains("token: None"), "{}", debug);
+    assert!(debug.contains("callback: false"), "{}", debug);
+}
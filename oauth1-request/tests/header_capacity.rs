@@ -0,0 +1,75 @@
+//! Test that `Builder::build` allocates the `Authorization` header value at once.
+//!
+//! The capacity of the header value is estimated with a counting pass over the OAuth protocol
+//! parameters, so the buffer is not reallocated however long the parameters are.
+
+// In debug builds with the `alloc` feature, `Authorizer` allocates to assert the ordering
+// of the parameters, so we only run the test when the assertion is disabled. The CI runs the test
+// in release builds for the feature sets including `alloc`.
+#![cfg(not(debug_assertions))]
+
+extern crate oauth1_request as oauth;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::num::NonZeroU64;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use oauth::{Builder, Credentials};
+
+struct CountingAlloc;
+
+static REALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        REALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[test]
+fn build_does_not_reallocate() {
+    let callback = format!("https://client.example.net/cb?state={}", "x".repeat(600));
+    let client = Credentials::new("xvz1evFS4wEEPTGEFPHBog", "consumer_secret");
+    let token = Credentials::new(
+        "370773112-GmHxMAgYyLbNEtIKZeRNFsMKPR9EyMZeS9weJAEb",
+        "token_secret",
+    );
+    let mut builder = Builder::new(client, oauth::HMAC_SHA1);
+    builder
+        .token(token)
+        .realm("Example Realm")
+        .callback(&*callback)
+        .timestamp(NonZeroU64::new(1318622958))
+        .version(true);
+    // The nonce is generated by the serializer unless the `default-nonce` feature is disabled.
+    if !cfg!(feature = "default-nonce") {
+        builder.nonce("kYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg");
+    }
+
+    let request = oauth::request! {
+        "include_entities" => true,
+        "status" => "Hello Ladies + Gentlemen, a signed OAuth request!",
+        "zzz" => "A very long value that would not fit in a small buffer on the stack",
+    };
+    let uri = "https://api.twitter.com/1.1/statuses/update.json";
+
+    let before = REALLOCATIONS.load(Ordering::SeqCst);
+    let authorization = builder.build("POST", uri, &request);
+    let after = REALLOCATIONS.load(Ordering::SeqCst);
+
+    assert_eq!(after - before, 0);
+    assert!(authorization.len() > callback.len());
+    assert!(authorization.capacity() < 2 * authorization.len());
+}
@@ -0,0 +1,87 @@
+//! Test that signing a request with `HMAC-SHA1` does not allocate on the heap.
+//!
+//! The `HMAC-SHA1` signature method feeds the signature base string to the hasher incrementally,
+//! so the only buffer involved is the one for the output `Authorization` header value.
+
+// In debug builds with the `alloc` feature, `Authorizer` allocates to assert the ordering
+// of the parameters, so we only run the test when the assertion is disabled. The CI runs the test
+// in release builds for the feature sets including `alloc`.
+#![cfg(not(all(feature = "alloc", debug_assertions)))]
+
+extern crate oauth1_request as oauth;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::num::NonZeroU64;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use oauth::request::ParameterList;
+use oauth::serializer::auth::{self, Authorizer};
+use oauth::{Credentials, Request};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[test]
+fn hmac_sha1_no_alloc() {
+    let client = Credentials::new(
+        "xvz1evFS4wEEPTGEFPHBog",
+        "kAcSOqF21Fu85e7zjz7ZN2U4ZRhfV3WpwPAoE3Z7kBw",
+    );
+    let token = Credentials::new(
+        "370773112-GmHxMAgYyLbNEtIKZeRNFsMKPR9EyMZeS9weJAEb",
+        "LswwdoUaIvS8ltyTt5jkRh4J50vUPVVHtR2YPi5kE",
+    );
+    let mut options = auth::Options::new();
+    options
+        .nonce("kYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg")
+        .timestamp(NonZeroU64::new(1318622958))
+        .version(true);
+    let request = ParameterList::new([
+        ("include_entities", "true"),
+        (
+            "status",
+            "Hello Ladies + Gentlemen, a signed OAuth request!",
+        ),
+        (
+            "zzz",
+            "A very long value that would not fit in a small buffer on the stack",
+        ),
+    ]);
+    let buf = String::with_capacity(512);
+
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    let serializer = Authorizer::authorization_with_buf(
+        buf,
+        "POST",
+        "https://api.twitter.com/1.1/statuses/update.json",
+        client,
+        Some(token),
+        &options,
+        oauth::HMAC_SHA1,
+    );
+    let authorization = request.serialize(serializer);
+    let after = ALLOCATIONS.load(Ordering::SeqCst);
+
+    assert_eq!(after - before, 0);
+    assert!(authorization.starts_with("OAuth oauth_consumer_key="));
+}
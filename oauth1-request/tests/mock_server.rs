@@ -0,0 +1,267 @@
+//! End-to-end test that sends signed requests to a mock OAuth 1.0 server over HTTP.
+//!
+//! The server verifies the `HMAC-SHA1` signature of each request independently of the client
+//! implementation, following [RFC 5849 section 3.4][rfc].
+//!
+//! [rfc]: https://tools.ietf.org/html/rfc5849#section-3.4
+
+#![cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+
+extern crate oauth1_request as oauth;
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread;
+
+use hmac::{Hmac, Mac};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use sha1::Sha1;
+
+const CK: &str = "xvz1evFS4wEEPTGEFPHBog";
+const CS: &str = "kAcSOqF21Fu85e7zjz7ZN2U4ZRhfV3WpwPAoE3Z7kBw";
+const AK: &str = "370773112-GmHxMAgYyLbNEtIKZeRNFsMKPR9EyMZeS9weJAEb";
+const AS: &str = "LswwdoUaIvS8ltyTt5jkRh4J50vUPVVHtR2YPi5kE";
+
+const UNRESERVED: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+struct HttpRequest {
+    method: String,
+    target: String,
+    host: String,
+    authorization: Option<String>,
+    content_type: Option<String>,
+    body: String,
+}
+
+/// Spawns a server that verifies the signatures of `n` requests and responds with
+/// `200 OK` or `401 Unauthorized` accordingly.
+fn spawn_server(n: usize) -> (SocketAddr, thread::JoinHandle<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let handle = thread::spawn(move || {
+        for stream in listener.incoming().take(n) {
+            let mut stream = stream.unwrap();
+            let req = read_request(&mut stream);
+            let status = if verify(&req) {
+                "200 OK"
+            } else {
+                "401 Unauthorized"
+            };
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                status,
+            )
+            .unwrap();
+        }
+    });
+    (addr, handle)
+}
+
+fn read_request(stream: &mut TcpStream) -> HttpRequest {
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    let mut request_line = line.split_whitespace();
+    let method = request_line.next().unwrap().to_owned();
+    let target = request_line.next().unwrap().to_owned();
+
+    let mut host = String::new();
+    let mut authorization = None;
+    let mut content_type = None;
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line.split_at(line.find(':').unwrap());
+        let value = value[1..].trim().to_owned();
+        match &*name.to_ascii_lowercase() {
+            "host" => host = value,
+            "authorization" => authorization = Some(value),
+            "content-type" => content_type = Some(value),
+            "content-length" => content_length = value.parse().unwrap(),
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+    let body = String::from_utf8(body).unwrap();
+
+    HttpRequest {
+        method,
+        target,
+        host,
+        authorization,
+        content_type,
+        body,
+    }
+}
+
+fn verify(req: &HttpRequest) -> bool {
+    let mut params = Vec::new();
+    let mut signature = None;
+
+    if let Some(authorization) = req.authorization.as_ref() {
+        let authorization = authorization.strip_prefix("OAuth ").unwrap();
+        for pair in authorization.split(',') {
+            let (k, v) = pair.split_at(pair.find('=').unwrap());
+            let v = v[1..].trim_matches('"');
+            params.push((decode(k), decode(v)));
+        }
+    }
+
+    let (path, query) = match req.target.find('?') {
+        Some(i) => (&req.target[..i], &req.target[i + 1..]),
+        None => (&*req.target, ""),
+    };
+    params.extend(parse_form(query));
+
+    if req.content_type.as_deref() == Some("application/x-www-form-urlencoded") {
+        params.extend(parse_form(&req.body));
+    }
+
+    params.retain(|(k, v)| match &**k {
+        "oauth_signature" => {
+            signature = Some(v.clone());
+            false
+        }
+        "realm" => false,
+        _ => true,
+    });
+    let signature = match signature {
+        Some(signature) => signature,
+        None => return false,
+    };
+
+    let mut params: Vec<(String, String)> = params
+        .into_iter()
+        .map(|(k, v)| (encode(&k), encode(&v)))
+        .collect();
+    params.sort();
+    let params = params
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    let base = format!(
+        "{}&{}&{}",
+        req.method,
+        encode(&format!("http://{}{}", req.host, path)),
+        encode(&params),
+    );
+    let key = format!("{}&{}", encode(CS), encode(AS));
+
+    let mut mac = Hmac::<Sha1>::new_from_slice(key.as_bytes()).unwrap();
+    mac.update(base.as_bytes());
+    let expected = base64::encode(mac.finalize().into_bytes());
+
+    signature == expected
+}
+
+fn parse_form(form: &str) -> impl Iterator<Item = (String, String)> + '_ {
+    form.split('&').filter(|pair| !pair.is_empty()).map(|pair| {
+        let (k, v) = match pair.find('=') {
+            Some(i) => (&pair[..i], &pair[i + 1..]),
+            None => (pair, ""),
+        };
+        (decode(&k.replace('+', " ")), decode(&v.replace('+', " ")))
+    })
+}
+
+fn decode(s: &str) -> String {
+    percent_decode_str(s).decode_utf8().unwrap().into_owned()
+}
+
+fn encode(s: &str) -> String {
+    utf8_percent_encode(s, UNRESERVED).to_string()
+}
+
+/// Sends a request and returns the status code of the response.
+fn send(
+    addr: SocketAddr,
+    method: &str,
+    target: &str,
+    authorization: Option<&str>,
+    form: Option<&str>,
+) -> u16 {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: {}\r\n",
+        method, target, addr
+    )
+    .unwrap();
+    if let Some(authorization) = authorization {
+        write!(stream, "Authorization: {}\r\n", authorization).unwrap();
+    }
+    let body = form.unwrap_or("");
+    if form.is_some() {
+        write!(
+            stream,
+            "Content-Type: application/x-www-form-urlencoded\r\n"
+        )
+        .unwrap();
+    }
+    write!(
+        stream,
+        "Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body,
+    )
+    .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response.split_whitespace().nth(1).unwrap().parse().unwrap()
+}
+
+#[test]
+fn mock_server() {
+    let (addr, server) = spawn_server(4);
+
+    let token = oauth::Token::from_parts(CK, CS, AK, AS);
+    let builder = oauth::Builder::with_token(token.as_ref(), oauth::HMAC_SHA1);
+
+    // `Authorization` header with query parameters in the URI.
+    let path = "/1.1/statuses/home_timeline.json?count=2&screen_name=%E9%85%92%E5%A0%B4";
+    let uri = format!("http://{}{}", addr, path);
+    let authorization = builder.get(&uri, &());
+    assert_eq!(send(addr, "GET", path, Some(&authorization), None), 200);
+
+    // `Authorization` header with an `x-www-form-urlencoded` body.
+    let path = "/1.1/statuses/update.json";
+    let uri = format!("http://{}{}", addr, path);
+    let request = oauth::ParameterList::new([("status", "Hello, world! 🦀")]);
+    let authorization = builder.post(&uri, &request);
+    let form = oauth::to_form(&request);
+    assert_eq!(
+        send(addr, "POST", path, Some(&authorization), Some(&form)),
+        200
+    );
+
+    // Protocol parameters in the query part.
+    let path = "/1.1/account/verify_credentials.json?include_email=true";
+    let uri = builder.to_query("GET", format!("http://{}{}", addr, path), &());
+    let target = &uri[uri.find(path).unwrap()..];
+    assert_eq!(send(addr, "GET", target, None, None), 200);
+
+    // Wrong credentials should be rejected.
+    let path = "/1.1/statuses/home_timeline.json";
+    let uri = format!("http://{}{}", addr, path);
+    let token = oauth::Token::from_parts(CK, "wrong", AK, AS);
+    let authorization = oauth::get(&uri, &(), &token, oauth::HMAC_SHA1);
+    assert_eq!(send(addr, "GET", path, Some(&authorization), None), 401);
+
+    server.join().unwrap();
+}
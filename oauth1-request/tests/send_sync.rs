@@ -0,0 +1,13 @@
+//! Test that `Builder` and the serializer options can be shared across threads.
+
+use oauth1_request::serializer::auth;
+use oauth1_request::signature_method::Plaintext;
+use oauth1_request::Builder;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn send_sync() {
+    assert_send_sync::<auth::Options<'static>>();
+    assert_send_sync::<Builder<'static, Plaintext<String>, &'static str>>();
+}